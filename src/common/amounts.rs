@@ -0,0 +1,369 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::tokens::{format_ui_amount, parse_ui_amount, raw_to_ui, ui_to_raw, TokenError};
+
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+pub const SOL_DECIMALS: u8 = 9;
+pub const MAX_SLIPPAGE_BPS: u16 = 10_000;
+// Prices keep nine decimals of the quote token per whole base token.
+pub const PRICE_DECIMALS: u8 = 9;
+
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum AmountError {
+    #[error(transparent)]
+    Token(#[from] TokenError),
+
+    #[error("Slippage of {0} bps is above 10000 bps")]
+    SlippageOutOfRange(u64),
+
+    #[error("Invalid {kind} {value}")]
+    Invalid { kind: &'static str, value: f64 },
+
+    #[error("Token amounts have different decimals: {0} and {1}")]
+    DecimalsMismatch(u8, u8),
+
+    #[error("Amount overflow")]
+    Overflow,
+}
+
+// Raw integer amount of a token together with its decimals. The API takes UI amounts as
+// floats, so `from_ui_f64`/`to_ui_f64` are the only lossy steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TokenAmount {
+    pub raw: u64,
+    pub decimals: u8,
+}
+
+impl TokenAmount {
+    pub fn new(raw: u64, decimals: u8) -> Self {
+        Self { raw, decimals }
+    }
+
+    pub fn zero(decimals: u8) -> Self {
+        Self::new(0, decimals)
+    }
+
+    // Exact, e.g. "0.1" with 9 decimals is 100_000_000.
+    pub fn from_ui(amount: &str, decimals: u8) -> Result<Self, AmountError> {
+        Ok(Self::new(parse_ui_amount(amount, decimals)?, decimals))
+    }
+
+    // Rounds to the nearest raw unit.
+    pub fn from_ui_f64(amount: f64, decimals: u8) -> Result<Self, AmountError> {
+        Ok(Self::new(ui_to_raw(amount, decimals)?, decimals))
+    }
+
+    pub fn to_ui_f64(&self) -> f64 {
+        raw_to_ui(self.raw, self.decimals)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.raw == 0
+    }
+
+    pub fn checked_add(self, other: Self) -> Result<Self, AmountError> {
+        let other = self.same_decimals(other)?;
+        let raw = self.raw.checked_add(other).ok_or(AmountError::Overflow)?;
+        Ok(Self::new(raw, self.decimals))
+    }
+
+    pub fn saturating_sub(self, other: Self) -> Result<Self, AmountError> {
+        let other = self.same_decimals(other)?;
+        Ok(Self::new(self.raw.saturating_sub(other), self.decimals))
+    }
+
+    // Splits evenly into `parts`, with the remainder going to the first amounts.
+    pub fn split(self, parts: u32) -> Vec<Self> {
+        let parts = parts.max(1) as u64;
+        let (share, remainder) = (self.raw / parts, self.raw % parts);
+        (0..parts)
+            .map(|i| Self::new(share + u64::from(i < remainder), self.decimals))
+            .collect()
+    }
+
+    fn same_decimals(&self, other: Self) -> Result<u64, AmountError> {
+        if self.decimals != other.decimals {
+            return Err(AmountError::DecimalsMismatch(self.decimals, other.decimals));
+        }
+        Ok(other.raw)
+    }
+}
+
+impl fmt::Display for TokenAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format_ui_amount(self.raw, self.decimals))
+    }
+}
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Lamports(pub u64);
+
+impl Lamports {
+    pub fn from_sol(sol: f64) -> Result<Self, AmountError> {
+        Ok(Self(ui_to_raw(sol, SOL_DECIMALS)?))
+    }
+
+    pub fn get(self) -> u64 {
+        self.0
+    }
+
+    pub fn to_sol(self) -> f64 {
+        raw_to_ui(self.0, SOL_DECIMALS)
+    }
+}
+
+impl From<Lamports> for TokenAmount {
+    fn from(lamports: Lamports) -> Self {
+        TokenAmount::new(lamports.0, SOL_DECIMALS)
+    }
+}
+
+impl fmt::Display for Lamports {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} SOL", format_ui_amount(self.0, SOL_DECIMALS))
+    }
+}
+
+// The API takes slippage in percent; keeping basis points on the SDK side avoids mixing the
+// two up, e.g. passing 50 meaning 0.5%.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(try_from = "u16", into = "u16")]
+pub struct SlippageBps(u16);
+
+impl SlippageBps {
+    pub fn new(bps: u16) -> Result<Self, AmountError> {
+        if bps > MAX_SLIPPAGE_BPS {
+            return Err(AmountError::SlippageOutOfRange(bps as u64));
+        }
+        Ok(Self(bps))
+    }
+
+    // For constants and defaults; anything above 100% becomes 100%.
+    pub const fn clamped(bps: u16) -> Self {
+        if bps > MAX_SLIPPAGE_BPS {
+            Self(MAX_SLIPPAGE_BPS)
+        } else {
+            Self(bps)
+        }
+    }
+
+    // Rounds to the nearest basis point.
+    pub fn from_percent(percent: f64) -> Result<Self, AmountError> {
+        let bps = ui_to_raw(percent, 2).map_err(|_| AmountError::Invalid {
+            kind: "slippage percent",
+            value: percent,
+        })?;
+        if bps > MAX_SLIPPAGE_BPS as u64 {
+            return Err(AmountError::SlippageOutOfRange(bps));
+        }
+        Ok(Self(bps as u16))
+    }
+
+    pub fn get(self) -> u16 {
+        self.0
+    }
+
+    pub fn to_percent(self) -> f64 {
+        raw_to_ui(self.0 as u64, 2)
+    }
+
+    // Least acceptable output for `amount` at this slippage, rounded down.
+    pub fn min_out(self, amount: TokenAmount) -> TokenAmount {
+        let kept = (MAX_SLIPPAGE_BPS - self.0) as u128;
+        let raw = amount.raw as u128 * kept / MAX_SLIPPAGE_BPS as u128;
+        TokenAmount::new(raw as u64, amount.decimals)
+    }
+}
+
+impl TryFrom<u16> for SlippageBps {
+    type Error = AmountError;
+
+    fn try_from(bps: u16) -> Result<Self, Self::Error> {
+        Self::new(bps)
+    }
+}
+
+impl From<SlippageBps> for u16 {
+    fn from(slippage: SlippageBps) -> Self {
+        slippage.0
+    }
+}
+
+impl fmt::Display for SlippageBps {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} bps", self.0)
+    }
+}
+
+// Quote tokens per whole base token, in fixed point with `PRICE_DECIMALS`.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Price(u64);
+
+impl Price {
+    pub fn from_raw(raw: u64) -> Self {
+        Self(raw)
+    }
+
+    pub fn from_f64(price: f64) -> Result<Self, AmountError> {
+        ui_to_raw(price, PRICE_DECIMALS)
+            .map(Self)
+            .map_err(|_| AmountError::Invalid {
+                kind: "price",
+                value: price,
+            })
+    }
+
+    // Exact up to the last price decimal, which is rounded down.
+    pub fn from_amounts(base: TokenAmount, quote: TokenAmount) -> Result<Self, AmountError> {
+        if base.is_zero() {
+            return Err(AmountError::Invalid {
+                kind: "base amount",
+                value: 0.0,
+            });
+        }
+
+        let numerator = pow10(base.decimals as u32 + PRICE_DECIMALS as u32)
+            .and_then(|scale| (quote.raw as u128).checked_mul(scale))
+            .ok_or(AmountError::Overflow)?;
+        let denominator = pow10(quote.decimals as u32)
+            .and_then(|scale| (base.raw as u128).checked_mul(scale))
+            .ok_or(AmountError::Overflow)?;
+
+        u64::try_from(numerator / denominator)
+            .map(Self)
+            .map_err(|_| AmountError::Overflow)
+    }
+
+    pub fn raw(self) -> u64 {
+        self.0
+    }
+
+    pub fn to_f64(self) -> f64 {
+        raw_to_ui(self.0, PRICE_DECIMALS)
+    }
+}
+
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format_ui_amount(self.0, PRICE_DECIMALS))
+    }
+}
+
+// Quote tokens per whole base token as a float, for prices too small for `Price`'s fixed
+// decimals such as memecoins at 1e-10 SOL. Computed from the raw amounts, so the only rounding
+// is the float's own.
+pub fn ui_price(base: TokenAmount, quote: TokenAmount) -> Result<f64, AmountError> {
+    if base.is_zero() {
+        return Err(AmountError::Invalid {
+            kind: "base amount",
+            value: 0.0,
+        });
+    }
+
+    let numerator = quote.raw as f64 * 10f64.powi(base.decimals as i32);
+    let denominator = base.raw as f64 * 10f64.powi(quote.decimals as i32);
+    Ok(numerator / denominator)
+}
+
+fn pow10(exp: u32) -> Option<u128> {
+    10u128.checked_pow(exp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_amounts() {
+        let amount = TokenAmount::from_ui("1.5", 6).unwrap();
+        assert_eq!(amount.raw, 1_500_000);
+        assert_eq!(amount.to_string(), "1.5");
+        assert_eq!(amount.to_ui_f64(), 1.5);
+        assert_eq!(TokenAmount::from_ui_f64(0.1, 9).unwrap().raw, 100_000_000);
+
+        let sum = amount.checked_add(TokenAmount::new(1, 6)).unwrap();
+        assert_eq!(sum.to_string(), "1.500001");
+        assert_eq!(
+            amount.checked_add(TokenAmount::new(1, 9)),
+            Err(AmountError::DecimalsMismatch(6, 9))
+        );
+
+        let parts = TokenAmount::new(10, 0).split(3);
+        assert_eq!(
+            parts.iter().map(|p| p.raw).collect::<Vec<_>>(),
+            vec![4, 3, 3]
+        );
+    }
+
+    #[test]
+    fn test_slippage_bps() {
+        let slippage = SlippageBps::from_percent(0.5).unwrap();
+        assert_eq!(slippage.get(), 50);
+        assert_eq!(slippage.to_percent(), 0.5);
+        assert_eq!(SlippageBps::from_percent(0.57).unwrap().get(), 57);
+        assert_eq!(SlippageBps::new(100).unwrap().to_percent(), 1.0);
+
+        assert!(SlippageBps::new(10_001).is_err());
+        assert_eq!(SlippageBps::clamped(10_001).get(), MAX_SLIPPAGE_BPS);
+        assert!(SlippageBps::from_percent(150.0).is_err());
+        assert!(SlippageBps::from_percent(-1.0).is_err());
+        assert!(serde_json::from_str::<SlippageBps>("20000").is_err());
+        assert_eq!(serde_json::to_string(&slippage).unwrap(), "50");
+
+        let min_out = SlippageBps::new(100)
+            .unwrap()
+            .min_out(TokenAmount::new(1_000_001, 6));
+        assert_eq!(min_out.raw, 990_000);
+    }
+
+    #[test]
+    fn test_lamports_and_prices() {
+        assert_eq!(Lamports::from_sol(0.001).unwrap(), Lamports(1_000_000));
+        assert_eq!(Lamports(1_500_000_000).to_string(), "1.5 SOL");
+        assert_eq!(
+            TokenAmount::from(Lamports(LAMPORTS_PER_SOL)).to_ui_f64(),
+            1.0
+        );
+
+        // 2 SOL for 300 USDC is 150 USDC per SOL.
+        let price = Price::from_amounts(
+            TokenAmount::new(2 * LAMPORTS_PER_SOL, 9),
+            TokenAmount::new(300_000_000, 6),
+        )
+        .unwrap();
+        assert_eq!(price.to_string(), "150");
+        assert_eq!(price, Price::from_f64(150.0).unwrap());
+        assert!(Price::from_amounts(TokenAmount::zero(9), TokenAmount::new(1, 6)).is_err());
+        assert!(Price::from_f64(f64::NAN).is_err());
+        assert_eq!(
+            ui_price(
+                TokenAmount::new(2 * LAMPORTS_PER_SOL, 9),
+                TokenAmount::new(300_000_000, 6),
+            ),
+            Ok(150.0)
+        );
+    }
+
+    #[test]
+    fn test_ui_price_below_price_decimals() {
+        // 1e9 tokens of a 6 decimal memecoin against 0.5 SOL is 5e-10 SOL per token, which
+        // `Price` rounds to zero.
+        let base = TokenAmount::new(1_000_000_000_000_000, 6);
+        let quote = TokenAmount::new(LAMPORTS_PER_SOL / 2, 9);
+        assert_eq!(Price::from_amounts(base, quote).unwrap().raw(), 0);
+
+        let price = ui_price(base, quote).unwrap();
+        assert!((price - 5e-10).abs() < 1e-22, "{}", price);
+        assert!(ui_price(TokenAmount::zero(6), quote).is_err());
+    }
+}
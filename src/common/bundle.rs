@@ -0,0 +1,337 @@
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use bincode::deserialize;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, VersionedTransaction},
+};
+use solana_trader_proto::api;
+
+use crate::common::signing::{
+    sign_transactions, submitted_signatures, SignedTransaction, SubmitOutcome, SubmitParams,
+};
+use crate::provider::utils::IntoTransactionMessage;
+
+pub const MAX_BUNDLE_SIZE: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TipPosition {
+    First,
+    Last,
+    At(usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct BundleBuilder {
+    transactions: Vec<api::TransactionMessage>,
+    tip: Option<(api::TransactionMessage, TipPosition)>,
+    max_size: usize,
+}
+
+impl Default for BundleBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BundleBuilder {
+    pub fn new() -> Self {
+        Self {
+            transactions: Vec::new(),
+            tip: None,
+            max_size: MAX_BUNDLE_SIZE,
+        }
+    }
+
+    pub fn push<T: IntoTransactionMessage>(mut self, tx: T) -> Self {
+        self.transactions.push(tx.into_transaction_message());
+        self
+    }
+
+    pub fn extend<T, I>(mut self, txs: I) -> Self
+    where
+        T: IntoTransactionMessage,
+        I: IntoIterator<Item = T>,
+    {
+        self.transactions.extend(
+            txs.into_iter()
+                .map(IntoTransactionMessage::into_transaction_message),
+        );
+        self
+    }
+
+    pub fn tip<T: IntoTransactionMessage>(mut self, tx: T, position: TipPosition) -> Self {
+        self.tip = Some((tx.into_transaction_message(), position));
+        self
+    }
+
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    pub fn build(self) -> Result<Bundle> {
+        let mut transactions = self.transactions;
+        let mut tip_index = None;
+
+        if let Some((tip, position)) = self.tip {
+            let index = match position {
+                TipPosition::First => 0,
+                TipPosition::Last => transactions.len(),
+                TipPosition::At(index) if index <= transactions.len() => index,
+                TipPosition::At(index) => {
+                    return Err(anyhow!(
+                        "Tip position {} is past the end of a {}-transaction bundle",
+                        index,
+                        transactions.len()
+                    ))
+                }
+            };
+            transactions.insert(index, tip);
+            tip_index = Some(index);
+        }
+
+        if transactions.is_empty() {
+            return Err(anyhow!("Bundle has no transactions"));
+        }
+        if transactions.len() > self.max_size {
+            return Err(anyhow!(
+                "Bundle has {} transactions, more than the limit of {}",
+                transactions.len(),
+                self.max_size
+            ));
+        }
+
+        Ok(Bundle {
+            transactions,
+            tip_index,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Bundle {
+    transactions: Vec<api::TransactionMessage>,
+    tip_index: Option<usize>,
+}
+
+impl Bundle {
+    pub fn len(&self) -> usize {
+        self.transactions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transactions.is_empty()
+    }
+
+    pub fn transactions(&self) -> &[api::TransactionMessage] {
+        &self.transactions
+    }
+
+    pub fn tip_index(&self) -> Option<usize> {
+        self.tip_index
+    }
+
+    // Signing only fills the first signature slot, so every transaction must be paid
+    // for by the signing keypair to come out fully signed.
+    pub fn check_fee_payers(&self, signer: &Pubkey) -> Result<()> {
+        for (index, tx) in self.transactions.iter().enumerate() {
+            let payer = decode_fee_payer(&tx.content)?;
+            if payer != *signer {
+                return Err(anyhow!(
+                    "Bundle transaction {} is paid by {}, expected the signer {}",
+                    index,
+                    payer,
+                    signer
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    // Every transaction is signed against the same blockhash, with a memo when
+    // `submit_opts.add_memo` is set.
+    pub async fn sign(
+        &self,
+        keypair: &Keypair,
+        blockhash: &str,
+        submit_opts: &SubmitParams,
+    ) -> Result<Vec<SignedTransaction>> {
+        self.check_fee_payers(&keypair.pubkey())?;
+        sign_transactions(self.transactions.clone(), keypair, blockhash, submit_opts).await
+    }
+
+    pub fn result(&self, response: Vec<api::PostSubmitBatchResponseEntry>) -> BundleResult {
+        BundleResult {
+            entries: SubmitOutcome::from_batch(self.transactions.len(), response),
+            tip_index: self.tip_index,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BundleResult {
+    pub entries: Vec<SubmitOutcome>,
+    pub tip_index: Option<usize>,
+}
+
+impl BundleResult {
+    pub fn all_submitted(&self) -> bool {
+        self.entries.iter().all(|entry| entry.submitted)
+    }
+
+    pub fn tip(&self) -> Option<&SubmitOutcome> {
+        self.tip_index.and_then(|index| self.entries.get(index))
+    }
+
+    pub fn signatures(&self) -> Vec<String> {
+        submitted_signatures(&self.entries)
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &SubmitOutcome> {
+        self.entries.iter().filter(|entry| !entry.submitted)
+    }
+}
+
+fn decode_fee_payer(content: &str) -> Result<Pubkey> {
+    let rawbytes = STANDARD.decode(content)?;
+
+    let keys = match deserialize::<VersionedTransaction>(&rawbytes) {
+        Ok(tx) => tx.message.static_account_keys().to_vec(),
+        Err(_) => {
+            let tx: Transaction = deserialize(&rawbytes)?;
+            tx.message.account_keys
+        }
+    };
+
+    keys.first()
+        .copied()
+        .ok_or_else(|| anyhow!("Transaction has no fee payer"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::constants::TRADER_API_MEMO_PROGRAM;
+    use solana_sdk::{hash::Hash, message::Message, system_instruction};
+
+    fn transfer(payer: &Pubkey, lamports: u64) -> api::TransactionMessage {
+        let ix = system_instruction::transfer(payer, &Pubkey::new_unique(), lamports);
+        let tx = Transaction::new_unsigned(Message::new(&[ix], Some(payer)));
+        api::TransactionMessage {
+            content: STANDARD.encode(bincode::serialize(&tx).unwrap()),
+            is_cleanup: false,
+        }
+    }
+
+    #[test]
+    fn test_build_orders_tip() {
+        let payer = Pubkey::new_unique();
+        let bundle = BundleBuilder::new()
+            .push(transfer(&payer, 1))
+            .push(transfer(&payer, 2))
+            .tip(transfer(&payer, 3), TipPosition::At(1))
+            .build()
+            .unwrap();
+
+        assert_eq!(bundle.len(), 3);
+        assert_eq!(bundle.tip_index(), Some(1));
+
+        let too_big = BundleBuilder::new()
+            .extend((0..3).map(|i| transfer(&payer, i)))
+            .max_size(2)
+            .build();
+        assert!(too_big.is_err());
+
+        let bad_tip = BundleBuilder::new()
+            .push(transfer(&payer, 1))
+            .tip(transfer(&payer, 2), TipPosition::At(5))
+            .build();
+        assert!(bad_tip.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sign_requires_signer_as_payer() {
+        let keypair = Keypair::new();
+        let other = Pubkey::new_unique();
+        let blockhash = Hash::new_unique().to_string();
+
+        let bundle = BundleBuilder::new()
+            .push(transfer(&keypair.pubkey(), 1))
+            .tip(transfer(&keypair.pubkey(), 2), TipPosition::Last)
+            .build()
+            .unwrap();
+        let signed = bundle
+            .sign(&keypair, &blockhash, &SubmitParams::default())
+            .await
+            .unwrap();
+        for tx in &signed {
+            let rawbytes = STANDARD.decode(&tx.content).unwrap();
+            let tx: Transaction = deserialize(&rawbytes).unwrap();
+            assert_eq!(tx.message.account_keys[0], keypair.pubkey());
+            assert_eq!(tx.message.recent_blockhash.to_string(), blockhash);
+            assert!(tx.is_signed());
+        }
+
+        let foreign = BundleBuilder::new()
+            .push(transfer(&keypair.pubkey(), 1))
+            .push(transfer(&other, 2))
+            .build()
+            .unwrap();
+        assert!(foreign
+            .sign(&keypair, &blockhash, &SubmitParams::default())
+            .await
+            .is_err());
+
+        let with_memo = SubmitParams {
+            add_memo: true,
+            ..Default::default()
+        };
+        let signed = bundle.sign(&keypair, &blockhash, &with_memo).await.unwrap();
+        for tx in &signed {
+            let rawbytes = STANDARD.decode(&tx.content).unwrap();
+            let tx: VersionedTransaction = deserialize(&rawbytes).unwrap();
+            assert!(tx
+                .message
+                .static_account_keys()
+                .contains(&TRADER_API_MEMO_PROGRAM));
+        }
+    }
+
+    #[test]
+    fn test_result_keeps_every_entry() {
+        let payer = Pubkey::new_unique();
+        let bundle = BundleBuilder::new()
+            .push(transfer(&payer, 1))
+            .push(transfer(&payer, 2))
+            .tip(transfer(&payer, 3), TipPosition::First)
+            .build()
+            .unwrap();
+
+        let result = bundle.result(vec![
+            api::PostSubmitBatchResponseEntry {
+                signature: "tip".to_string(),
+                error: String::new(),
+                submitted: true,
+            },
+            api::PostSubmitBatchResponseEntry {
+                signature: "swap".to_string(),
+                error: "simulation failed".to_string(),
+                submitted: false,
+            },
+        ]);
+
+        assert_eq!(result.entries.len(), 3);
+        assert_eq!(result.tip().map(|tip| tip.signature.as_str()), Some("tip"));
+        assert_eq!(result.signatures(), vec!["tip".to_string()]);
+        assert_eq!(
+            result.entries[1].error.as_deref(),
+            Some("simulation failed")
+        );
+        assert!(!result.entries[2].submitted);
+        assert_eq!(result.failures().count(), 2);
+        assert!(!result.all_submitted());
+    }
+}
@@ -0,0 +1,108 @@
+use anyhow::{anyhow, Result};
+use solana_trader_proto::api;
+
+// The v1 trade endpoints serve every CLOB the Trader API supports and pick one from the
+// `project` field; the wrappers set it so a request can't reach the wrong venue.
+pub trait ProjectRequest: Clone {
+    fn for_project(&self, project: api::Project) -> Self;
+}
+
+macro_rules! impl_project_request {
+    ($($request:ty),*) => {
+        $(
+            impl ProjectRequest for $request {
+                fn for_project(&self, project: api::Project) -> Self {
+                    Self {
+                        project: project as i32,
+                        ..self.clone()
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_project_request!(
+    api::PostOrderRequest,
+    api::PostCancelOrderRequest,
+    api::PostCancelAllRequest,
+    api::PostReplaceOrderRequest,
+    api::PostSettleRequest,
+    api::GetOpenOrdersRequest,
+    api::GetUnsettledRequest
+);
+
+// A cancel without an order id or client order id would cancel every open order on the
+// market; that has to go through the explicit cancel-all calls instead.
+pub fn require_order_id(request: &api::PostCancelOrderRequestV2) -> Result<()> {
+    if request.order_id.is_empty() && request.client_order_id == 0 {
+        return Err(anyhow!(
+            "Cancel needs an order id or client order id; use cancel all to cancel every order"
+        ));
+    }
+    Ok(())
+}
+
+pub fn order_transaction(
+    method: &str,
+    response: api::PostOrderResponse,
+) -> Result<Vec<api::TransactionMessage>> {
+    response
+        .transaction
+        .map(|tx| vec![tx])
+        .ok_or_else(|| anyhow!("{} returned no transaction", method))
+}
+
+pub fn require_transactions(
+    method: &str,
+    transactions: Vec<api::TransactionMessage>,
+) -> Result<Vec<api::TransactionMessage>> {
+    if transactions.is_empty() {
+        return Err(anyhow!("{} returned no transactions", method));
+    }
+    Ok(transactions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel_requires_an_order_id() {
+        let request = api::PostCancelOrderRequestV2 {
+            market_address: "SOL/USDC".to_string(),
+            owner_address: "owner".to_string(),
+            ..Default::default()
+        };
+        assert!(require_order_id(&request).is_err());
+
+        let by_client_id = api::PostCancelOrderRequestV2 {
+            client_order_id: 7,
+            ..request.clone()
+        };
+        assert!(require_order_id(&by_client_id).is_ok());
+
+        let by_order_id = api::PostCancelOrderRequestV2 {
+            order_id: "42".to_string(),
+            ..request
+        };
+        assert!(require_order_id(&by_order_id).is_ok());
+
+        assert!(order_transaction("PostOrderV2", Default::default()).is_err());
+        assert!(require_transactions("PostSettleV2", vec![]).is_err());
+    }
+
+    #[test]
+    fn test_for_project_sets_only_the_project() {
+        let request = api::PostCancelAllRequest {
+            market: "SOL/USDC".to_string(),
+            owner_address: "owner".to_string(),
+            ..Default::default()
+        };
+
+        let phoenix = request.for_project(api::Project::PPhoenix);
+        assert_eq!(phoenix.project, api::Project::PPhoenix as i32);
+        assert_eq!(phoenix.market, "SOL/USDC");
+        assert_eq!(phoenix.owner_address, "owner");
+    }
+}
@@ -1,3 +1,5 @@
+use solana_sdk::{pubkey, pubkey::Pubkey};
+
 pub const LOCAL: &str = "localhost:9000";
 pub const TESTNET: &str = "solana.dex.bxrtest.com";
 pub const MAINNET_NY: &str = "ny.solana.dex.blxrbdn.com";
@@ -8,7 +10,13 @@ pub const MAINNET_PUMP_UK: &str = "pump-uk.solana.dex.blxrbdn.com";
 // Common tokens
 pub const WRAPPED_SOL: &str = "So11111111111111111111111111111111111111112";
 pub const USDC: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+pub const USDT: &str = "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB";
 pub const SAMPLE_TX_SIGNATURE: &str =
     "3tBRLPDgihGortkMmpLtxwoWTyfdRxykdz8c9kGtmX3d2ZF4g3NUhEvsxGj4n1mD6F5eUWp5HBzjaQ9vUEZdepMc";
 
 pub const SAMPLE_OWNER_ADDR: &str = "BvdnPWXm1zAvUSUjL6KBRzATP3Q5QJY7ZTMtEwxyvdNB";
+
+// Programs
+pub const TRADER_API_MEMO_PROGRAM: Pubkey = pubkey!("HQ2UUt18uJqKaQFJhgV9zaTdQxUZjNrsKFgoEDquBkcx");
+pub const TOKEN_PROGRAM: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+pub const TOKEN_2022_PROGRAM: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
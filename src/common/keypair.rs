@@ -0,0 +1,521 @@
+use std::{env, fmt, fs, path::PathBuf, process::Command, str::FromStr};
+
+use bip39::{Language, Mnemonic};
+use solana_sdk::{
+    bs58,
+    derivation_path::DerivationPath,
+    pubkey::Pubkey,
+    signature::{
+        generate_seed_from_seed_phrase_and_passphrase, keypair_from_seed_and_derivation_path,
+        keypair_from_seed_phrase_and_passphrase, Keypair,
+    },
+    signer::Signer,
+};
+use thiserror::Error;
+
+pub const PRIVATE_KEY_ENV: &str = "PRIVATE_KEY";
+pub const KEYPAIR_PATH_ENV: &str = "KEYPAIR_PATH";
+pub const SEED_PHRASE_ENV: &str = "SEED_PHRASE";
+pub const SEED_PASSPHRASE_ENV: &str = "SEED_PASSPHRASE";
+pub const DERIVATION_PATH_ENV: &str = "DERIVATION_PATH";
+pub const KEYPAIR_COMMAND_ENV: &str = "KEYPAIR_COMMAND";
+pub const PUBLIC_KEY_ENV: &str = "PUBLIC_KEY";
+
+const SEED_PHRASE_WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24];
+
+#[derive(Debug, Error)]
+pub enum KeypairError {
+    #[error("Failed to read keypair file {path}: {source}")]
+    ReadFile {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Invalid keypair file {path}: {reason}")]
+    InvalidFile { path: PathBuf, reason: String },
+    #[error("Invalid base58 private key: {0}")]
+    InvalidBase58(String),
+    #[error("Invalid byte array private key: {0}")]
+    InvalidBytes(String),
+    #[error("Invalid seed phrase: {0}")]
+    InvalidSeedPhrase(String),
+    #[error("Invalid derivation path {path}: {reason}")]
+    InvalidDerivationPath { path: String, reason: String },
+    #[error("Keypair command `{command}` failed: {reason}")]
+    Command { command: String, reason: String },
+    #[error("Only one keypair source may be configured, found {0}")]
+    MultipleSources(String),
+    #[error("Invalid PUBLIC_KEY {value}: {reason}")]
+    InvalidPublicKey { value: String, reason: String },
+    #[error("PUBLIC_KEY {expected} does not match the loaded keypair {actual}")]
+    PublicKeyMismatch { expected: Pubkey, actual: Pubkey },
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub enum KeypairSource {
+    // Solana CLI keyfile, a JSON array of the 64 secret key bytes.
+    File(PathBuf),
+    Base58(String),
+    // JSON byte array as found in Solana CLI keyfiles, e.g. "[12,34,...]".
+    Bytes(String),
+    // Without a derivation path the seed is used directly, as `solana-keygen recover`
+    // does; wallets such as Phantom derive with "m/44'/501'/0'/0'".
+    SeedPhrase {
+        phrase: String,
+        passphrase: String,
+        derivation_path: Option<String>,
+    },
+    // Runs the program and reads a base58 or byte array key from its stdout, e.g. to fetch
+    // the key from a password manager or secrets store.
+    Command {
+        program: String,
+        args: Vec<String>,
+    },
+}
+
+struct Redacted;
+
+impl fmt::Debug for Redacted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<redacted>")
+    }
+}
+
+// Keys, seed phrases and passphrases are redacted, so sources can be logged.
+impl fmt::Debug for KeypairSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeypairSource::File(path) => f.debug_tuple("File").field(path).finish(),
+            KeypairSource::Base58(_) => f.debug_tuple("Base58").field(&Redacted).finish(),
+            KeypairSource::Bytes(_) => f.debug_tuple("Bytes").field(&Redacted).finish(),
+            KeypairSource::SeedPhrase {
+                derivation_path, ..
+            } => f
+                .debug_struct("SeedPhrase")
+                .field("phrase", &Redacted)
+                .field("passphrase", &Redacted)
+                .field("derivation_path", derivation_path)
+                .finish(),
+            KeypairSource::Command { program, args } => f
+                .debug_struct("Command")
+                .field("program", program)
+                .field("args", args)
+                .finish(),
+        }
+    }
+}
+
+impl KeypairSource {
+    // Reads at most one of PRIVATE_KEY, KEYPAIR_PATH, SEED_PHRASE or KEYPAIR_COMMAND.
+    pub fn from_env() -> Result<Option<Self>, KeypairError> {
+        let configured: Vec<&str> = [
+            PRIVATE_KEY_ENV,
+            KEYPAIR_PATH_ENV,
+            SEED_PHRASE_ENV,
+            KEYPAIR_COMMAND_ENV,
+        ]
+        .into_iter()
+        .filter(|name| env::var(name).is_ok_and(|value| !value.trim().is_empty()))
+        .collect();
+
+        let name = match configured.as_slice() {
+            [] => return Ok(None),
+            [name] => *name,
+            names => return Err(KeypairError::MultipleSources(names.join(", "))),
+        };
+        let value = env::var(name).unwrap_or_default().trim().to_string();
+
+        let source = match name {
+            PRIVATE_KEY_ENV if value.starts_with('[') => KeypairSource::Bytes(value),
+            PRIVATE_KEY_ENV => KeypairSource::Base58(value),
+            KEYPAIR_PATH_ENV => KeypairSource::File(expand_home(&value)),
+            SEED_PHRASE_ENV => KeypairSource::SeedPhrase {
+                phrase: value,
+                passphrase: env::var(SEED_PASSPHRASE_ENV).unwrap_or_default(),
+                derivation_path: env::var(DERIVATION_PATH_ENV)
+                    .ok()
+                    .filter(|path| !path.trim().is_empty()),
+            },
+            _ => {
+                let mut parts = split_command(&value)
+                    .map_err(|reason| KeypairError::Command {
+                        command: value.clone(),
+                        reason,
+                    })?
+                    .into_iter();
+                KeypairSource::Command {
+                    program: parts.next().unwrap_or_default(),
+                    args: parts.collect(),
+                }
+            }
+        };
+        Ok(Some(source))
+    }
+
+    pub fn load(&self) -> Result<Keypair, KeypairError> {
+        match self {
+            KeypairSource::File(path) => {
+                let content =
+                    fs::read_to_string(path).map_err(|source| KeypairError::ReadFile {
+                        path: path.clone(),
+                        source,
+                    })?;
+                parse_byte_array(&content).map_err(|e| KeypairError::InvalidFile {
+                    path: path.clone(),
+                    reason: e.to_string(),
+                })
+            }
+            KeypairSource::Base58(key) => parse_base58(key),
+            KeypairSource::Bytes(bytes) => parse_byte_array(bytes),
+            KeypairSource::SeedPhrase {
+                phrase,
+                passphrase,
+                derivation_path,
+            } => from_seed_phrase(phrase, passphrase, derivation_path.as_deref()),
+            KeypairSource::Command { program, args } => run_command(program, args),
+        }
+    }
+}
+
+// Loads the keypair and checks it against an expected public key, if one is configured.
+pub fn load_verified(
+    source: &KeypairSource,
+    public_key: Option<&Pubkey>,
+) -> Result<Keypair, KeypairError> {
+    let keypair = source.load()?;
+    if let Some(expected) = public_key {
+        if keypair.pubkey() != *expected {
+            return Err(KeypairError::PublicKeyMismatch {
+                expected: *expected,
+                actual: keypair.pubkey(),
+            });
+        }
+    }
+    Ok(keypair)
+}
+
+pub fn public_key_from_env() -> Result<Option<Pubkey>, KeypairError> {
+    match env::var(PUBLIC_KEY_ENV) {
+        Ok(value) if !value.trim().is_empty() => {
+            Pubkey::from_str(value.trim())
+                .map(Some)
+                .map_err(|e| KeypairError::InvalidPublicKey {
+                    value,
+                    reason: e.to_string(),
+                })
+        }
+        _ => Ok(None),
+    }
+}
+
+fn parse_base58(key: &str) -> Result<Keypair, KeypairError> {
+    let bytes = bs58::decode(key.trim())
+        .into_vec()
+        .map_err(|e| KeypairError::InvalidBase58(e.to_string()))?;
+    Keypair::from_bytes(&bytes).map_err(|e| KeypairError::InvalidBase58(e.to_string()))
+}
+
+fn parse_byte_array(content: &str) -> Result<Keypair, KeypairError> {
+    let bytes: Vec<u8> = serde_json::from_str(content.trim())
+        .map_err(|e| KeypairError::InvalidBytes(e.to_string()))?;
+    Keypair::from_bytes(&bytes).map_err(|e| KeypairError::InvalidBytes(e.to_string()))
+}
+
+fn from_seed_phrase(
+    phrase: &str,
+    passphrase: &str,
+    derivation_path: Option<&str>,
+) -> Result<Keypair, KeypairError> {
+    let words: Vec<&str> = phrase.split_whitespace().collect();
+    if !SEED_PHRASE_WORD_COUNTS.contains(&words.len()) {
+        return Err(KeypairError::InvalidSeedPhrase(format!(
+            "expected 12, 15, 18, 21 or 24 words, found {}",
+            words.len()
+        )));
+    }
+    let phrase = words.join(" ");
+    // Checked against the English wordlist and checksum like `solana-keygen recover`, so a
+    // typo fails here instead of deriving a different wallet.
+    Mnemonic::from_phrase(&phrase, Language::English)
+        .map_err(|e| KeypairError::InvalidSeedPhrase(e.to_string()))?;
+
+    let Some(path) = derivation_path else {
+        return keypair_from_seed_phrase_and_passphrase(&phrase, passphrase)
+            .map_err(|e| KeypairError::InvalidSeedPhrase(e.to_string()));
+    };
+
+    let derivation_path = DerivationPath::from_absolute_path_str(path).map_err(|e| {
+        KeypairError::InvalidDerivationPath {
+            path: path.to_string(),
+            reason: e.to_string(),
+        }
+    })?;
+    let seed = generate_seed_from_seed_phrase_and_passphrase(&phrase, passphrase);
+    keypair_from_seed_and_derivation_path(&seed, Some(derivation_path)).map_err(|e| {
+        KeypairError::InvalidDerivationPath {
+            path: path.to_string(),
+            reason: e.to_string(),
+        }
+    })
+}
+
+fn run_command(program: &str, args: &[String]) -> Result<Keypair, KeypairError> {
+    let command = std::iter::once(program)
+        .chain(args.iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let error = |reason: String| KeypairError::Command {
+        command: command.clone(),
+        reason,
+    };
+
+    if program.is_empty() {
+        return Err(error("no program given".to_string()));
+    }
+
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| error(e.to_string()))?;
+    if !output.status.success() {
+        return Err(error(format!("exited with {}", output.status)));
+    }
+
+    let stdout = String::from_utf8(output.stdout).map_err(|e| error(e.to_string()))?;
+    let key = stdout.trim();
+    let keypair = if key.starts_with('[') {
+        parse_byte_array(key)
+    } else {
+        parse_base58(key)
+    };
+    // The key itself never ends up in the error message.
+    keypair.map_err(|_| error("output is not a base58 or byte array private key".to_string()))
+}
+
+// Splits a command line the way a POSIX shell would, without expanding anything: single
+// quotes are literal, double quotes allow backslash escapes of `"`, `\` and `$`, and a
+// backslash outside quotes escapes the next character.
+fn split_command(line: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => words.extend(word.take()),
+            '\'' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err("unterminated single quote".to_string()),
+                    }
+                }
+            }
+            '"' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$')) => word.push(c),
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => return Err("unterminated double quote".to_string()),
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err("unterminated double quote".to_string()),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                Some(c) => word.get_or_insert_with(String::new).push(c),
+                None => return Err("trailing backslash".to_string()),
+            },
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), env::var("HOME")) {
+        (Some(rest), Ok(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PHRASE: &str =
+        "park remain person kitchen mule spell knee armed position rail grid ankle";
+
+    fn byte_array(keypair: &Keypair) -> String {
+        serde_json::to_string(&keypair.to_bytes().to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_load_key_strings_and_file() {
+        let keypair = Keypair::new();
+
+        let base58 = KeypairSource::Base58(keypair.to_base58_string());
+        assert_eq!(base58.load().unwrap().pubkey(), keypair.pubkey());
+
+        let bytes = KeypairSource::Bytes(byte_array(&keypair));
+        assert_eq!(bytes.load().unwrap().pubkey(), keypair.pubkey());
+
+        let path = env::temp_dir().join(format!("trader-api-{}.json", keypair.pubkey()));
+        fs::write(&path, byte_array(&keypair)).unwrap();
+        let file = KeypairSource::File(path.clone());
+        assert_eq!(file.load().unwrap().pubkey(), keypair.pubkey());
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(
+            KeypairSource::Base58("not-base58!".to_string()).load(),
+            Err(KeypairError::InvalidBase58(_))
+        ));
+        assert!(matches!(
+            KeypairSource::Bytes("[1,2,3]".to_string()).load(),
+            Err(KeypairError::InvalidBytes(_))
+        ));
+        assert!(matches!(file.load(), Err(KeypairError::ReadFile { .. })));
+    }
+
+    #[test]
+    fn test_seed_phrase_derivation() {
+        let source = |derivation_path: Option<&str>| KeypairSource::SeedPhrase {
+            phrase: PHRASE.to_string(),
+            passphrase: String::new(),
+            derivation_path: derivation_path.map(str::to_string),
+        };
+
+        let plain = source(None).load().unwrap();
+        let account0 = source(Some("m/44'/501'/0'/0'")).load().unwrap();
+        let account1 = source(Some("m/44'/501'/1'/0'")).load().unwrap();
+
+        assert_eq!(plain.pubkey(), source(None).load().unwrap().pubkey());
+        assert_ne!(plain.pubkey(), account0.pubkey());
+        assert_ne!(account0.pubkey(), account1.pubkey());
+
+        assert!(matches!(
+            source(Some("44/501")).load(),
+            Err(KeypairError::InvalidDerivationPath { .. })
+        ));
+        let short = KeypairSource::SeedPhrase {
+            phrase: "park remain person".to_string(),
+            passphrase: String::new(),
+            derivation_path: None,
+        };
+        assert!(matches!(
+            short.load(),
+            Err(KeypairError::InvalidSeedPhrase(_))
+        ));
+
+        // One misspelled word, and two swapped words that fail the checksum.
+        for phrase in [
+            "park remain person kitchen mule spell knee armed position rail grid ankel",
+            "remain park person kitchen mule spell knee armed position rail grid ankle",
+        ] {
+            let typo = KeypairSource::SeedPhrase {
+                phrase: phrase.to_string(),
+                passphrase: String::new(),
+                derivation_path: None,
+            };
+            match typo.load() {
+                Err(KeypairError::InvalidSeedPhrase(reason)) => {
+                    assert!(!reason.contains("ankel"), "{}", reason)
+                }
+                other => panic!("expected an invalid seed phrase, got {:?}", other.err()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_debug_redacts_secrets() {
+        let keypair = Keypair::new();
+        let sources = [
+            KeypairSource::Base58(keypair.to_base58_string()),
+            KeypairSource::Bytes(byte_array(&keypair)),
+            KeypairSource::SeedPhrase {
+                phrase: PHRASE.to_string(),
+                passphrase: "hunter2".to_string(),
+                derivation_path: Some("m/44'/501'/0'/0'".to_string()),
+            },
+        ];
+
+        for source in sources {
+            let debug = format!("{:?}", source);
+            assert!(debug.contains("<redacted>"), "{}", debug);
+            assert!(!debug.contains(&keypair.to_base58_string()));
+            assert!(
+                !debug.contains("park") && !debug.contains("hunter2"),
+                "{}",
+                debug
+            );
+        }
+        assert_eq!(
+            format!("{:?}", KeypairSource::File(PathBuf::from("id.json"))),
+            "File(\"id.json\")"
+        );
+    }
+
+    #[test]
+    fn test_load_verified_checks_public_key() {
+        let keypair = Keypair::new();
+        let source = KeypairSource::Base58(keypair.to_base58_string());
+
+        assert!(load_verified(&source, Some(&keypair.pubkey())).is_ok());
+        assert!(load_verified(&source, None).is_ok());
+
+        let other = Pubkey::new_unique();
+        match load_verified(&source, Some(&other)) {
+            Err(KeypairError::PublicKeyMismatch { expected, actual }) => {
+                assert_eq!(expected, other);
+                assert_eq!(actual, keypair.pubkey());
+            }
+            other => panic!("expected a public key mismatch, got {:?}", other.err()),
+        }
+    }
+
+    #[test]
+    fn test_split_command() {
+        assert_eq!(
+            split_command(r#"op read "op://Trading Vault/key" --account 'my team'"#).unwrap(),
+            vec![
+                "op",
+                "read",
+                "op://Trading Vault/key",
+                "--account",
+                "my team"
+            ]
+        );
+        assert_eq!(
+            split_command(r#"  /opt/my\ tools/get-key "a\"b" '' x"#).unwrap(),
+            vec!["/opt/my tools/get-key", "a\"b", "", "x"]
+        );
+        assert!(split_command("get-key 'unterminated").is_err());
+        assert!(split_command(r#"get-key "unterminated"#).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_source() {
+        let keypair = Keypair::new();
+        let command = KeypairSource::Command {
+            program: "echo".to_string(),
+            args: vec![keypair.to_base58_string()],
+        };
+        assert_eq!(command.load().unwrap().pubkey(), keypair.pubkey());
+
+        let failing = KeypairSource::Command {
+            program: "false".to_string(),
+            args: vec![],
+        };
+        assert!(matches!(failing.load(), Err(KeypairError::Command { .. })));
+    }
+}
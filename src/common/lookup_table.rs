@@ -0,0 +1,472 @@
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use solana_sdk::{
+    address_lookup_table::{state::AddressLookupTable, AddressLookupTableAccount},
+    clock::Slot,
+    hash::Hash,
+    instruction::Instruction,
+    message::VersionedMessage,
+    pubkey::Pubkey,
+};
+use solana_trader_proto::api;
+
+use crate::common::transaction::TransactionBuilder;
+use crate::provider::utils::convert_address_lookup_table;
+
+// A lookup table reference costs its 32-byte address plus index bytes, while every key it
+// replaces saves 31 bytes, so a table is only worth including once it covers two keys.
+const MIN_KEYS_PER_TABLE: usize = 2;
+
+// Fetches raw lookup table account data, e.g. through an RPC `getAccountInfo` call.
+#[async_trait]
+pub trait LookupTableSource: Send + Sync {
+    async fn fetch_lookup_table(&self, address: &Pubkey) -> Result<Vec<u8>>;
+}
+
+// Deactivated and deactivating tables are rejected, since they stop resolving once closed.
+pub fn parse_lookup_table(address: Pubkey, data: &[u8]) -> Result<AddressLookupTableAccount> {
+    parse_cached_table(address, data)?
+        .map(|cached| cached.account)
+        .ok_or_else(|| anyhow!("Lookup table {} is deactivated", address))
+}
+
+fn parse_cached_table(address: Pubkey, data: &[u8]) -> Result<Option<CachedTable>> {
+    let table = AddressLookupTable::deserialize(data)
+        .map_err(|e| anyhow!("Invalid lookup table account {}: {}", address, e))?;
+    if table.meta.deactivation_slot != Slot::MAX {
+        return Ok(None);
+    }
+
+    Ok(Some(CachedTable {
+        account: AddressLookupTableAccount {
+            key: address,
+            addresses: table.addresses.to_vec(),
+        },
+        fetched_at: Instant::now(),
+        last_extension: Some((
+            table.meta.last_extended_slot,
+            table.meta.last_extended_slot_start_index as usize,
+        )),
+    }))
+}
+
+// Picks tables greedily by how many of the instructions' lookup-eligible keys they cover.
+// Payer, signers and invoked programs must stay static and are never eligible.
+pub fn select_lookup_tables<'a>(
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    candidates: impl IntoIterator<Item = &'a AddressLookupTableAccount>,
+) -> Vec<AddressLookupTableAccount> {
+    let mut static_keys = HashSet::from([*payer]);
+    for ix in instructions {
+        static_keys.insert(ix.program_id);
+        static_keys.extend(ix.accounts.iter().filter(|a| a.is_signer).map(|a| a.pubkey));
+    }
+
+    let mut remaining: HashSet<Pubkey> = instructions
+        .iter()
+        .flat_map(|ix| ix.accounts.iter().map(|a| a.pubkey))
+        .filter(|key| !static_keys.contains(key))
+        .collect();
+
+    let mut candidates: Vec<&AddressLookupTableAccount> = candidates.into_iter().collect();
+    // Deterministic tie-breaking regardless of the caller's ordering.
+    candidates.sort_by_key(|account| account.key);
+
+    let mut selected = Vec::new();
+    while !remaining.is_empty() {
+        let best = candidates
+            .iter()
+            .enumerate()
+            .map(|(i, account)| {
+                let covered = account
+                    .addresses
+                    .iter()
+                    .filter(|key| remaining.contains(key))
+                    .collect::<HashSet<_>>()
+                    .len();
+                (i, covered)
+            })
+            .max_by(|(a_index, a_covered), (b_index, b_covered)| {
+                a_covered.cmp(b_covered).then(b_index.cmp(a_index))
+            });
+
+        match best {
+            Some((i, covered)) if covered >= MIN_KEYS_PER_TABLE => {
+                let account = candidates.remove(i);
+                for key in &account.addresses {
+                    remaining.remove(key);
+                }
+                selected.push(account.clone());
+            }
+            _ => break,
+        }
+    }
+
+    selected
+}
+
+#[derive(Debug, Clone)]
+struct CachedTable {
+    account: AddressLookupTableAccount,
+    fetched_at: Instant,
+    // Slot of the last extension and the index of its first address, for fetched tables.
+    last_extension: Option<(Slot, usize)>,
+}
+
+impl CachedTable {
+    // Addresses appended in a slot cannot be looked up until the next one.
+    fn usable(&self, current_slot: Option<Slot>) -> AddressLookupTableAccount {
+        match (self.last_extension, current_slot) {
+            (Some((extended_slot, start)), Some(slot)) if slot <= extended_slot => {
+                AddressLookupTableAccount {
+                    key: self.account.key,
+                    addresses: self.account.addresses[..start.min(self.account.addresses.len())]
+                        .to_vec(),
+                }
+            }
+            _ => self.account.clone(),
+        }
+    }
+}
+
+pub struct LookupTableManager {
+    tables: HashMap<Pubkey, CachedTable>,
+    source: Option<Box<dyn LookupTableSource>>,
+    ttl: Option<Duration>,
+    current_slot: Option<Slot>,
+}
+
+impl Default for LookupTableManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LookupTableManager {
+    pub fn new() -> Self {
+        Self {
+            tables: HashMap::new(),
+            source: None,
+            ttl: None,
+            current_slot: None,
+        }
+    }
+
+    pub fn with_source(source: impl LookupTableSource + 'static) -> Self {
+        Self {
+            source: Some(Box::new(source)),
+            ..Self::new()
+        }
+    }
+
+    // Cached tables older than `ttl` are fetched again on the next resolve.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    // Without a current slot, addresses from a table's latest extension are assumed usable.
+    pub fn set_current_slot(&mut self, slot: Slot) {
+        self.current_slot = Some(slot);
+    }
+
+    pub fn insert(&mut self, account: AddressLookupTableAccount) {
+        self.tables.insert(
+            account.key,
+            CachedTable {
+                account,
+                fetched_at: Instant::now(),
+                last_extension: None,
+            },
+        );
+    }
+
+    pub fn get(&self, address: &Pubkey) -> Option<&AddressLookupTableAccount> {
+        self.tables.get(address).map(|cached| &cached.account)
+    }
+
+    pub fn len(&self) -> usize {
+        self.tables.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+
+    pub fn remove(&mut self, address: &Pubkey) -> Option<AddressLookupTableAccount> {
+        self.tables.remove(address).map(|cached| cached.account)
+    }
+
+    pub fn clear(&mut self) {
+        self.tables.clear();
+    }
+
+    fn is_fresh(&self, address: &Pubkey) -> bool {
+        match (self.tables.get(address), self.ttl) {
+            (Some(cached), Some(ttl)) => cached.fetched_at.elapsed() < ttl,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+
+    // Tables that turn out to be deactivated are dropped from the cache and left out.
+    pub async fn resolve(
+        &mut self,
+        addresses: &[Pubkey],
+    ) -> Result<Vec<AddressLookupTableAccount>> {
+        let mut resolved = Vec::with_capacity(addresses.len());
+
+        for address in addresses {
+            if !self.is_fresh(address) {
+                let source = self.source.as_ref().ok_or_else(|| {
+                    anyhow!(
+                        "Lookup table {} is not cached and no source is configured",
+                        address
+                    )
+                })?;
+                let data = source.fetch_lookup_table(address).await?;
+                match parse_cached_table(*address, &data)? {
+                    Some(table) => self.tables.insert(*address, table),
+                    None => {
+                        self.tables.remove(address);
+                        continue;
+                    }
+                };
+            }
+            resolved.push(self.tables[address].usable(self.current_slot));
+        }
+
+        Ok(resolved)
+    }
+
+    // Lookup tables listed in a swap-instructions response. With a source configured the
+    // contents are resolved independently; otherwise the response contents are cached as-is.
+    pub async fn resolve_response_tables(
+        &mut self,
+        tables: &HashMap<String, api::PublicKeys>,
+    ) -> Result<Vec<AddressLookupTableAccount>> {
+        if self.source.is_none() {
+            let accounts = convert_address_lookup_table(tables)?;
+            for account in &accounts {
+                self.insert(account.clone());
+            }
+            return Ok(accounts);
+        }
+
+        let addresses = tables
+            .keys()
+            .map(|key| Pubkey::from_str(key))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.resolve(&addresses).await
+    }
+
+    pub fn tables(&self) -> Vec<AddressLookupTableAccount> {
+        self.tables
+            .values()
+            .map(|cached| cached.account.clone())
+            .collect()
+    }
+
+    pub fn select_tables(
+        &self,
+        payer: &Pubkey,
+        instructions: &[Instruction],
+    ) -> Vec<AddressLookupTableAccount> {
+        let candidates: Vec<AddressLookupTableAccount> = self
+            .tables
+            .values()
+            .map(|cached| cached.usable(self.current_slot))
+            .collect();
+        select_lookup_tables(payer, instructions, &candidates)
+    }
+
+    // Compiles to a v0 message with the best-fitting cached tables, or to a legacy message
+    // when none of them applies.
+    pub fn compile(
+        &self,
+        payer: &Pubkey,
+        instructions: &[Instruction],
+        recent_blockhash: Hash,
+    ) -> Result<VersionedMessage> {
+        self.builder(payer, instructions).compile(recent_blockhash)
+    }
+
+    pub fn compile_transaction(
+        &self,
+        payer: &Pubkey,
+        instructions: &[Instruction],
+        recent_blockhash: &str,
+    ) -> Result<api::TransactionMessage> {
+        self.builder(payer, instructions).build(recent_blockhash)
+    }
+
+    fn builder(&self, payer: &Pubkey, instructions: &[Instruction]) -> TransactionBuilder {
+        TransactionBuilder::new(*payer)
+            .extend(instructions.iter().cloned())
+            .lookup_tables(self.select_tables(payer, instructions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::*;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use solana_sdk::{
+        address_lookup_table::state::LookupTableMeta, instruction::AccountMeta,
+        transaction::VersionedTransaction,
+    };
+
+    fn instruction(program: Pubkey, keys: &[Pubkey]) -> Instruction {
+        Instruction {
+            program_id: program,
+            accounts: keys
+                .iter()
+                .map(|key| AccountMeta::new(*key, false))
+                .collect(),
+            data: vec![1],
+        }
+    }
+
+    struct StaticSource(HashMap<Pubkey, Vec<u8>>);
+
+    #[async_trait]
+    impl LookupTableSource for StaticSource {
+        async fn fetch_lookup_table(&self, address: &Pubkey) -> Result<Vec<u8>> {
+            self.0
+                .get(address)
+                .cloned()
+                .ok_or_else(|| anyhow!("not found"))
+        }
+    }
+
+    #[test]
+    fn test_compile_prefers_covering_table() {
+        let payer = Pubkey::new_unique();
+        let program = Pubkey::new_unique();
+        let keys: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+
+        let mut manager = LookupTableManager::new();
+        let narrow = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: vec![keys[0], keys[1]],
+        };
+        let wide = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: vec![keys[0], keys[1], keys[2], keys[3], program],
+        };
+        manager.insert(narrow);
+        manager.insert(wide.clone());
+
+        let ixs = vec![instruction(program, &keys)];
+        let selected = manager.select_tables(&payer, &ixs);
+        assert_eq!(selected, vec![wide]);
+
+        let message = manager.compile(&payer, &ixs, Hash::new_unique()).unwrap();
+        match message {
+            VersionedMessage::V0(message) => {
+                assert_eq!(message.account_keys, vec![payer, program]);
+                assert_eq!(message.address_table_lookups.len(), 1);
+            }
+            VersionedMessage::Legacy(_) => panic!("expected a v0 message"),
+        }
+    }
+
+    #[test]
+    fn test_compile_falls_back_to_legacy() {
+        let payer = Pubkey::new_unique();
+        let program = Pubkey::new_unique();
+        let keys: Vec<Pubkey> = (0..2).map(|_| Pubkey::new_unique()).collect();
+
+        let mut manager = LookupTableManager::new();
+        manager.insert(AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: vec![keys[0], Pubkey::new_unique()],
+        });
+
+        let ixs = vec![instruction(program, &keys)];
+        let message = manager.compile(&payer, &ixs, Hash::new_unique()).unwrap();
+        assert!(matches!(message, VersionedMessage::Legacy(_)));
+
+        let tx = manager
+            .compile_transaction(&payer, &ixs, &Hash::new_unique().to_string())
+            .unwrap();
+        let rawbytes = STANDARD.decode(tx.content).unwrap();
+        let decoded: VersionedTransaction = bincode::deserialize(&rawbytes).unwrap();
+        assert_eq!(decoded.message.static_account_keys()[0], payer);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_caches_fetched_tables() {
+        let address = Pubkey::new_unique();
+        let addresses = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+        let data = AddressLookupTable {
+            meta: LookupTableMeta::default(),
+            addresses: Cow::Owned(addresses.clone()),
+        }
+        .serialize_for_tests()
+        .unwrap();
+
+        let mut manager =
+            LookupTableManager::with_source(StaticSource(HashMap::from([(address, data)])));
+        let resolved = manager.resolve(&[address]).await.unwrap();
+        assert_eq!(resolved[0].addresses, addresses);
+        assert_eq!(manager.get(&address).unwrap().addresses, addresses);
+
+        assert!(manager.resolve(&[Pubkey::new_unique()]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_skips_deactivated_and_fresh_extensions() {
+        let keys: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let table = |meta: LookupTableMeta| {
+            AddressLookupTable {
+                meta,
+                addresses: Cow::Owned(keys.clone()),
+            }
+            .serialize_for_tests()
+            .unwrap()
+        };
+
+        let deactivated = Pubkey::new_unique();
+        let extended = Pubkey::new_unique();
+        let source = StaticSource(HashMap::from([
+            (
+                deactivated,
+                table(LookupTableMeta {
+                    deactivation_slot: 90,
+                    ..Default::default()
+                }),
+            ),
+            (
+                extended,
+                table(LookupTableMeta {
+                    last_extended_slot: 100,
+                    last_extended_slot_start_index: 2,
+                    ..Default::default()
+                }),
+            ),
+        ]));
+        assert!(parse_lookup_table(deactivated, &source.0[&deactivated]).is_err());
+
+        let mut manager = LookupTableManager::with_source(source);
+        manager.set_current_slot(100);
+        let resolved = manager.resolve(&[deactivated, extended]).await.unwrap();
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].addresses, keys[..2].to_vec());
+        assert!(manager.get(&deactivated).is_none());
+
+        manager.set_current_slot(101);
+        let payer = Pubkey::new_unique();
+        let selected = manager.select_tables(&payer, &[instruction(Pubkey::new_unique(), &keys)]);
+        assert_eq!(selected[0].addresses, keys);
+    }
+}
@@ -0,0 +1,225 @@
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use solana_sdk::{
+    instruction::{AccountMeta, CompiledInstruction, Instruction},
+    message::{MessageHeader, VersionedMessage},
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::VersionedTransaction,
+};
+
+use crate::common::constants::TRADER_API_MEMO_PROGRAM;
+
+pub const MEMO_MESSAGE: &str = "Powered by bloXroute Trader Api";
+
+pub fn memo_instruction(message: &str) -> Instruction {
+    Instruction {
+        program_id: TRADER_API_MEMO_PROGRAM,
+        accounts: vec![AccountMeta::new(TRADER_API_MEMO_PROGRAM, false)],
+        data: message.as_bytes().to_vec(),
+    }
+}
+
+pub fn add_memo_instruction(instructions: &mut Vec<Instruction>, message: &str) {
+    if !instructions
+        .iter()
+        .any(|ix| ix.program_id == TRADER_API_MEMO_PROGRAM)
+    {
+        instructions.push(memo_instruction(message));
+    }
+}
+
+// Appends the memo instruction to a base64 legacy or v0 transaction. The existing
+// signatures are cleared since the message changes; transactions that already carry a
+// Trader API memo are returned unchanged.
+pub fn add_memo_to_serialized_tx(content: &str, message: &str) -> Result<String> {
+    let rawbytes = STANDARD.decode(content)?;
+    let mut tx: VersionedTransaction = bincode::deserialize(&rawbytes)?;
+    let data = message.as_bytes().to_vec();
+
+    match &mut tx.message {
+        VersionedMessage::Legacy(legacy) => {
+            if has_memo(&legacy.account_keys, &legacy.instructions) {
+                return Ok(content.to_string());
+            }
+            let index = memo_program_index(
+                &mut legacy.account_keys,
+                &mut legacy.header,
+                &mut legacy.instructions,
+                0,
+            )?;
+            legacy
+                .instructions
+                .push(CompiledInstruction::new_from_raw_parts(index, data, vec![]));
+        }
+        VersionedMessage::V0(v0) => {
+            if has_memo(&v0.account_keys, &v0.instructions) {
+                return Ok(content.to_string());
+            }
+            let loaded = v0
+                .address_table_lookups
+                .iter()
+                .map(|lookup| lookup.writable_indexes.len() + lookup.readonly_indexes.len())
+                .sum();
+            let index = memo_program_index(
+                &mut v0.account_keys,
+                &mut v0.header,
+                &mut v0.instructions,
+                loaded,
+            )?;
+            v0.instructions
+                .push(CompiledInstruction::new_from_raw_parts(index, data, vec![]));
+        }
+    }
+
+    tx.signatures =
+        vec![Signature::default(); tx.message.header().num_required_signatures as usize];
+    Ok(STANDARD.encode(bincode::serialize(&tx)?))
+}
+
+fn has_memo(account_keys: &[Pubkey], instructions: &[CompiledInstruction]) -> bool {
+    instructions
+        .iter()
+        .any(|ix| account_keys.get(ix.program_id_index as usize) == Some(&TRADER_API_MEMO_PROGRAM))
+}
+
+// Index of the memo program among the static keys, appending it as a readonly non-signer
+// when missing. `loaded` is the number of lookup table addresses following the static keys.
+fn memo_program_index(
+    account_keys: &mut Vec<Pubkey>,
+    header: &mut MessageHeader,
+    instructions: &mut [CompiledInstruction],
+    loaded: usize,
+) -> Result<u8> {
+    if let Some(index) = account_keys
+        .iter()
+        .position(|key| *key == TRADER_API_MEMO_PROGRAM)
+    {
+        return Ok(index as u8);
+    }
+
+    let static_len = account_keys.len();
+    if static_len + loaded > u8::MAX as usize {
+        return Err(anyhow!("Transaction has too many accounts to add a memo"));
+    }
+
+    // Indexes past the static keys point into lookup table addresses, which shift by one
+    // once the memo program is appended to the static keys.
+    for ix in instructions.iter_mut() {
+        for index in std::iter::once(&mut ix.program_id_index).chain(ix.accounts.iter_mut()) {
+            if *index as usize >= static_len {
+                *index += 1;
+            }
+        }
+    }
+
+    account_keys.push(TRADER_API_MEMO_PROGRAM);
+    header.num_readonly_unsigned_accounts += 1;
+    Ok(static_len as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{
+        address_lookup_table::AddressLookupTableAccount,
+        hash::Hash,
+        message::{v0, Message},
+        system_instruction,
+    };
+
+    fn encode(message: VersionedMessage) -> String {
+        let tx = VersionedTransaction {
+            signatures: vec![
+                Signature::default();
+                message.header().num_required_signatures as usize
+            ],
+            message,
+        };
+        STANDARD.encode(bincode::serialize(&tx).unwrap())
+    }
+
+    fn decode(content: &str) -> VersionedTransaction {
+        bincode::deserialize(&STANDARD.decode(content).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_add_memo_instruction_once() {
+        let payer = Pubkey::new_unique();
+        let mut instructions = vec![system_instruction::transfer(&payer, &payer, 1)];
+
+        add_memo_instruction(&mut instructions, MEMO_MESSAGE);
+        add_memo_instruction(&mut instructions, MEMO_MESSAGE);
+
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(instructions[1].program_id, TRADER_API_MEMO_PROGRAM);
+        assert_eq!(instructions[1].data, MEMO_MESSAGE.as_bytes());
+    }
+
+    #[test]
+    fn test_add_memo_to_legacy_tx() {
+        let payer = Pubkey::new_unique();
+        let ix = system_instruction::transfer(&payer, &Pubkey::new_unique(), 1);
+        let message = Message::new_with_blockhash(&[ix], Some(&payer), &Hash::new_unique());
+        let readonly = message.header.num_readonly_unsigned_accounts;
+
+        let content =
+            add_memo_to_serialized_tx(&encode(VersionedMessage::Legacy(message)), MEMO_MESSAGE)
+                .unwrap();
+        let tx = decode(&content);
+
+        let keys = tx.message.static_account_keys();
+        let memo = tx.message.instructions().last().unwrap();
+        assert_eq!(
+            keys[memo.program_id_index as usize],
+            TRADER_API_MEMO_PROGRAM
+        );
+        assert_eq!(memo.data, MEMO_MESSAGE.as_bytes());
+        assert_eq!(
+            tx.message.header().num_readonly_unsigned_accounts,
+            readonly + 1
+        );
+
+        // Adding a second time leaves the transaction as it is.
+        assert_eq!(
+            add_memo_to_serialized_tx(&content, MEMO_MESSAGE).unwrap(),
+            content
+        );
+    }
+
+    #[test]
+    fn test_add_memo_to_v0_tx_reindexes_loaded_accounts() {
+        let payer = Pubkey::new_unique();
+        let program = Pubkey::new_unique();
+        let loaded = Pubkey::new_unique();
+        let table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: vec![loaded],
+        };
+        let ix = Instruction {
+            program_id: program,
+            accounts: vec![AccountMeta::new(loaded, false)],
+            data: vec![7],
+        };
+        let message =
+            v0::Message::try_compile(&payer, &[ix], &[table], Hash::new_unique()).unwrap();
+        let static_len = message.account_keys.len();
+        assert_eq!(message.instructions[0].accounts, vec![static_len as u8]);
+
+        let content =
+            add_memo_to_serialized_tx(&encode(VersionedMessage::V0(message)), MEMO_MESSAGE)
+                .unwrap();
+        let tx = decode(&content);
+
+        let VersionedMessage::V0(message) = tx.message else {
+            panic!("expected a v0 message");
+        };
+        assert_eq!(message.account_keys.len(), static_len + 1);
+        assert_eq!(message.account_keys[static_len], TRADER_API_MEMO_PROGRAM);
+        assert_eq!(message.instructions[0].accounts, vec![static_len as u8 + 1]);
+        assert_eq!(
+            message.instructions[1].program_id_index as usize,
+            static_len
+        );
+    }
+}
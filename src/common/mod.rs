@@ -1,12 +1,26 @@
+pub mod amounts;
+pub mod bundle;
+pub mod clob;
 pub mod constants;
+pub mod keypair;
+pub mod lookup_table;
+pub mod memo;
+pub mod pools;
+pub mod pump;
+pub mod quote;
 pub mod signing;
+pub mod slippage;
+pub mod tokens;
+pub mod transaction;
 
-use std::{env, str::FromStr};
+use std::env;
 
 use anyhow::{anyhow, Result};
 use constants::{LOCAL, MAINNET_NY, MAINNET_PUMP_NY, MAINNET_PUMP_UK, MAINNET_UK, TESTNET};
 use dotenv::dotenv;
-use solana_sdk::{bs58::decode, pubkey::Pubkey, signature::Keypair};
+use keypair::{load_verified, public_key_from_env, KeypairSource};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+use tracing::debug;
 
 pub fn http_endpoint(base_url: &str, secure: bool) -> String {
     let prefix = if secure { "https" } else { "http" };
@@ -27,8 +41,7 @@ pub fn grpc_endpoint(base_url: &str, secure: bool) -> String {
 pub fn get_base_url_from_env() -> (String, bool) {
     let network = std::env::var("NETWORK").unwrap_or_else(|_| "mainnet".to_string());
     let region = std::env::var("REGION").unwrap_or_else(|_| "NY".to_string());
-    println!("network {}", network);
-    println!("region {}", region);
+    debug!(network = %network, region = %region, "Resolved Trader API environment");
     match (network.as_str(), region.as_str()) {
         ("LOCAL", _) => (LOCAL.to_string(), false),
         ("TESTNET", _) => (TESTNET.to_string(), true),
@@ -50,36 +63,16 @@ impl BaseConfig {
     pub fn try_from_env() -> Result<Self> {
         dotenv().ok();
 
-        let auth_header = env::var("AUTH_HEADER")
-            .map_err(|_| anyhow!("AUTH_HEADER environment variable not set"))?;
+        let auth_header = auth_header_from_env()?;
 
-        let public_key = env::var("PUBLIC_KEY").ok().and_then(|pk_str| {
-            Pubkey::from_str(&pk_str)
-                .map_err(|e| {
-                    println!("Warning: Failed to parse public key: {}", e);
-                    e
-                })
-                .ok()
-        });
-
-        let keypair = if let Ok(private_key) = env::var("PRIVATE_KEY") {
-            let mut output = [0; 64];
-            match decode(private_key).onto(&mut output) {
-                Ok(_) => match Keypair::from_bytes(&output) {
-                    Ok(kp) => Some(kp),
-                    Err(e) => {
-                        println!("Warning: Failed to create keypair: {}", e);
-                        None
-                    }
-                },
-                Err(e) => {
-                    println!("Warning: Failed to decode private key: {}", e);
-                    None
-                }
-            }
-        } else {
-            None
+        // A configured keypair must load and match PUBLIC_KEY; misconfiguration fails here
+        // instead of when the first transaction is signed.
+        let public_key = public_key_from_env()?;
+        let keypair = match KeypairSource::from_env()? {
+            Some(source) => Some(load_verified(&source, public_key.as_ref())?),
+            None => None,
         };
+        let public_key = public_key.or_else(|| keypair.as_ref().map(|kp| kp.pubkey()));
 
         Ok(Self {
             keypair,
@@ -87,4 +80,25 @@ impl BaseConfig {
             public_key,
         })
     }
+
+    // Loading may read a keyfile or run KEYPAIR_COMMAND, so async constructors do it on
+    // the blocking pool instead of stalling the runtime.
+    pub async fn try_from_env_async() -> Result<Self> {
+        tokio::task::spawn_blocking(Self::try_from_env).await?
+    }
+
+    // Skips every keypair source, so a read-only client never fails on signing config.
+    pub fn read_only_from_env() -> Result<Self> {
+        dotenv().ok();
+
+        Ok(Self {
+            auth_header: auth_header_from_env()?,
+            keypair: None,
+            public_key: public_key_from_env()?,
+        })
+    }
+}
+
+fn auth_header_from_env() -> Result<String> {
+    env::var("AUTH_HEADER").map_err(|_| anyhow!("AUTH_HEADER environment variable not set"))
 }
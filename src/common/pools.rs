@@ -0,0 +1,316 @@
+use std::collections::{BTreeSet, HashMap};
+
+use anyhow::{anyhow, Result};
+use futures_util::{Stream, StreamExt};
+use solana_trader_proto::api;
+use tokio::sync::RwLock;
+use tracing::warn;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PoolKind {
+    Amm,
+    Cpmm,
+    Clmm,
+}
+
+impl PoolKind {
+    // The new pools stream labels each pool with its Trader API project name. Unknown
+    // labels are rejected rather than guessed, since the kind decides which swap is built.
+    pub fn from_label(label: &str) -> Option<Self> {
+        match label.trim().to_ascii_lowercase().as_str() {
+            "raydium" | "raydium amm" => Some(PoolKind::Amm),
+            "raydium cpmm" => Some(PoolKind::Cpmm),
+            "raydium clmm" => Some(PoolKind::Clmm),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolInfo {
+    pub address: String,
+    pub kind: PoolKind,
+    pub name: String,
+    pub token1_mint: String,
+    pub token2_mint: String,
+    // Raw reserves as last reported, in base units of each token.
+    pub token1_reserves: f64,
+    pub token2_reserves: f64,
+}
+
+impl PoolInfo {
+    pub fn has_pair(&self, in_token: &str, out_token: &str) -> bool {
+        pair_key(&self.token1_mint, &self.token2_mint) == pair_key(in_token, out_token)
+    }
+
+    // Reserves ordered as (in, out) for a swap from `in_token`.
+    pub fn reserves_for(&self, in_token: &str) -> Option<(f64, f64)> {
+        if in_token == self.token1_mint {
+            Some((self.token1_reserves, self.token2_reserves))
+        } else if in_token == self.token2_mint {
+            Some((self.token2_reserves, self.token1_reserves))
+        } else {
+            None
+        }
+    }
+}
+
+pub trait ApiPool {
+    fn to_pool_info(&self, kind: PoolKind) -> PoolInfo;
+}
+
+macro_rules! impl_api_pool {
+    ($($pool:ty),*) => {
+        $(
+            impl ApiPool for $pool {
+                fn to_pool_info(&self, kind: PoolKind) -> PoolInfo {
+                    PoolInfo {
+                        address: self.pool_address.clone(),
+                        kind,
+                        name: self.pool.clone(),
+                        token1_mint: self.token1_mint_address.clone(),
+                        token2_mint: self.token2_mint_address.clone(),
+                        token1_reserves: self.token1_reserves as f64,
+                        token2_reserves: self.token2_reserves as f64,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_api_pool!(
+    api::ProjectPool,
+    api::RaydiumPool,
+    api::RaydiumCpmmPool,
+    api::RaydiumClmmPool
+);
+
+fn pair_key(a: &str, b: &str) -> (String, String) {
+    if a <= b {
+        (a.to_string(), b.to_string())
+    } else {
+        (b.to_string(), a.to_string())
+    }
+}
+
+// Pools indexed by address and by unordered token pair, so pair lookups don't need a
+// round-trip to the API.
+#[derive(Debug, Default, Clone)]
+pub struct PoolCache {
+    pools: HashMap<String, PoolInfo>,
+    pairs: HashMap<(String, String), BTreeSet<String>>,
+}
+
+impl PoolCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, pool: PoolInfo) {
+        if let Some(previous) = self.pools.get(&pool.address) {
+            let key = pair_key(&previous.token1_mint, &previous.token2_mint);
+            if let Some(addresses) = self.pairs.get_mut(&key) {
+                addresses.remove(&pool.address);
+                if addresses.is_empty() {
+                    self.pairs.remove(&key);
+                }
+            }
+        }
+
+        self.pairs
+            .entry(pair_key(&pool.token1_mint, &pool.token2_mint))
+            .or_default()
+            .insert(pool.address.clone());
+        self.pools.insert(pool.address.clone(), pool);
+    }
+
+    pub fn extend<'a, P: ApiPool + 'a>(
+        &mut self,
+        kind: PoolKind,
+        pools: impl IntoIterator<Item = &'a P>,
+    ) -> usize {
+        let mut count = 0;
+        for pool in pools {
+            self.insert(pool.to_pool_info(kind));
+            count += 1;
+        }
+        count
+    }
+
+    pub fn remove(&mut self, address: &str) -> Option<PoolInfo> {
+        let pool = self.pools.remove(address)?;
+        let key = pair_key(&pool.token1_mint, &pool.token2_mint);
+        if let Some(addresses) = self.pairs.get_mut(&key) {
+            addresses.remove(address);
+            if addresses.is_empty() {
+                self.pairs.remove(&key);
+            }
+        }
+        Some(pool)
+    }
+
+    pub fn get(&self, address: &str) -> Option<&PoolInfo> {
+        self.pools.get(address)
+    }
+
+    pub fn len(&self) -> usize {
+        self.pools.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pools.is_empty()
+    }
+
+    // Either token order matches; results are ordered by address.
+    pub fn pools_for_pair(&self, in_token: &str, out_token: &str) -> Vec<&PoolInfo> {
+        self.pairs
+            .get(&pair_key(in_token, out_token))
+            .map(|addresses| {
+                addresses
+                    .iter()
+                    .filter_map(|address| self.pools.get(address))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn apply_new_pool(&mut self, response: &api::GetNewRaydiumPoolsResponse) -> Result<()> {
+        let pool = response
+            .pool
+            .as_ref()
+            .ok_or_else(|| anyhow!("New pool response without pool"))?;
+
+        let kind = PoolKind::from_label(&pool.pool).ok_or_else(|| {
+            anyhow!(
+                "Unknown pool type {:?} for {}",
+                pool.pool,
+                pool.pool_address
+            )
+        })?;
+        self.insert(pool.to_pool_info(kind));
+        Ok(())
+    }
+}
+
+// Keeps a shared cache current with `get_new_raydium_pools_stream` updates until the stream
+// ends or fails. Updates that can't be applied are logged and skipped. Usually spawned next
+// to the code reading the cache.
+pub async fn refresh_from_stream<S, E>(cache: &RwLock<PoolCache>, mut stream: S) -> Result<()>
+where
+    S: Stream<Item = std::result::Result<api::GetNewRaydiumPoolsResponse, E>> + Unpin,
+    E: Into<anyhow::Error>,
+{
+    while let Some(update) = stream.next().await {
+        let update = update.map_err(Into::into)?;
+        if let Err(e) = cache.write().await.apply_new_pool(&update) {
+            warn!(slot = update.slot, error = %e, "Skipping new pool update");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(address: &str, token1: &str, token2: &str, kind: PoolKind) -> PoolInfo {
+        PoolInfo {
+            address: address.to_string(),
+            kind,
+            name: String::new(),
+            token1_mint: token1.to_string(),
+            token2_mint: token2.to_string(),
+            token1_reserves: 100.0,
+            token2_reserves: 200.0,
+        }
+    }
+
+    #[test]
+    fn test_pools_for_pair_ignores_token_order() {
+        let mut cache = PoolCache::new();
+        cache.insert(pool("b", "sol", "usdc", PoolKind::Amm));
+        cache.insert(pool("a", "usdc", "sol", PoolKind::Cpmm));
+        cache.insert(pool("c", "sol", "bonk", PoolKind::Clmm));
+
+        let addresses: Vec<&str> = cache
+            .pools_for_pair("sol", "usdc")
+            .iter()
+            .map(|pool| pool.address.as_str())
+            .collect();
+        assert_eq!(addresses, vec!["a", "b"]);
+        assert_eq!(cache.pools_for_pair("usdc", "sol").len(), 2);
+        assert!(cache.pools_for_pair("usdc", "bonk").is_empty());
+
+        assert_eq!(
+            cache.get("a").unwrap().reserves_for("sol"),
+            Some((200.0, 100.0))
+        );
+
+        // Re-inserting under a different pair moves the pool between indexes.
+        cache.insert(pool("b", "sol", "bonk", PoolKind::Amm));
+        assert_eq!(cache.pools_for_pair("sol", "usdc").len(), 1);
+        assert_eq!(cache.pools_for_pair("bonk", "sol").len(), 2);
+
+        cache.insert(pool("a", "sol", "bonk", PoolKind::Cpmm));
+        assert!(!cache.pairs.contains_key(&pair_key("sol", "usdc")));
+
+        cache.remove("a");
+        assert!(cache.pools_for_pair("sol", "usdc").is_empty());
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_from_stream() {
+        let update = api::GetNewRaydiumPoolsResponse {
+            slot: 1,
+            pool: Some(api::ProjectPool {
+                pool: "Raydium CPMM".to_string(),
+                pool_address: "pool".to_string(),
+                token1_mint_address: "sol".to_string(),
+                token2_mint_address: "usdc".to_string(),
+                ..Default::default()
+            }),
+        };
+        let unknown = api::GetNewRaydiumPoolsResponse {
+            slot: 2,
+            pool: Some(api::ProjectPool {
+                pool: "Raydium CPMM v2".to_string(),
+                pool_address: "unknown".to_string(),
+                ..Default::default()
+            }),
+        };
+        let amm = api::GetNewRaydiumPoolsResponse {
+            slot: 3,
+            pool: Some(api::ProjectPool {
+                pool: "Raydium".to_string(),
+                pool_address: "amm".to_string(),
+                token1_mint_address: "sol".to_string(),
+                token2_mint_address: "bonk".to_string(),
+                ..Default::default()
+            }),
+        };
+        let stream = futures_util::stream::iter(vec![
+            Ok::<_, anyhow::Error>(update),
+            Ok(api::GetNewRaydiumPoolsResponse::default()),
+            Ok(unknown),
+            Ok(amm),
+        ]);
+
+        // Malformed updates are skipped without ending the refresh.
+        let cache = RwLock::new(PoolCache::new());
+        refresh_from_stream(&cache, stream).await.unwrap();
+
+        let cache = cache.read().await;
+        assert_eq!(cache.len(), 2);
+        let pools = cache.pools_for_pair("usdc", "sol");
+        assert_eq!(pools.len(), 1);
+        assert_eq!(pools[0].kind, PoolKind::Cpmm);
+        assert_eq!(cache.get("amm").unwrap().kind, PoolKind::Amm);
+
+        // Stream errors still end it.
+        let failing = futures_util::stream::iter(vec![Err(anyhow!("stream closed"))]);
+        let empty = RwLock::new(PoolCache::new());
+        assert!(refresh_from_stream(&empty, failing).await.is_err());
+    }
+}
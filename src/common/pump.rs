@@ -0,0 +1,313 @@
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use solana_sdk::pubkey::Pubkey;
+use solana_trader_proto::api;
+
+use super::quote::{SwapQuote, SwapRequest, SwapSettings};
+
+pub const PUMP_FUN_PROGRAM: &str = "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P";
+pub const PUMP_FUN_FEE_BPS: u64 = 100;
+pub const PUMP_FUN_TOKEN_DECIMALS: u8 = 6;
+pub const PUMP_FUN_TOTAL_SUPPLY: u64 = 1_000_000_000_000_000;
+pub const PUMP_FUN_INITIAL_VIRTUAL_TOKEN_RESERVES: u64 = 1_073_000_000_000_000;
+pub const PUMP_FUN_INITIAL_REAL_TOKEN_RESERVES: u64 = 793_100_000_000_000;
+
+const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const ASSOCIATED_TOKEN_PROGRAM: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PumpFunQuoteType {
+    // Spends `amount` SOL; the quote's out amount is in tokens.
+    Buy,
+    // Sells `amount` tokens; the quote's out amount is in SOL.
+    Sell,
+}
+
+impl PumpFunQuoteType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PumpFunQuoteType::Buy => "buy",
+            PumpFunQuoteType::Sell => "sell",
+        }
+    }
+
+    pub fn is_buy(&self) -> bool {
+        *self == PumpFunQuoteType::Buy
+    }
+}
+
+impl fmt::Display for PumpFunQuoteType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PumpFunQuoteType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "buy" => Ok(PumpFunQuoteType::Buy),
+            "sell" => Ok(PumpFunQuoteType::Sell),
+            _ => Err(anyhow!("Invalid pump.fun quote type: {}", s)),
+        }
+    }
+}
+
+pub fn pump_fun_quote_request(
+    quote_type: PumpFunQuoteType,
+    mint_address: &str,
+    bonding_curve_address: &str,
+    amount: f64,
+) -> api::GetPumpFunQuotesRequest {
+    api::GetPumpFunQuotesRequest {
+        quote_type: quote_type.as_str().to_string(),
+        mint_address: mint_address.to_string(),
+        bonding_curve_address: bonding_curve_address.to_string(),
+        amount,
+    }
+}
+
+pub fn bonding_curve_address(mint: &Pubkey) -> Result<Pubkey> {
+    let program = Pubkey::from_str(PUMP_FUN_PROGRAM)?;
+    let (address, _) = Pubkey::find_program_address(&[b"bonding-curve", mint.as_ref()], &program);
+    Ok(address)
+}
+
+pub fn associated_bonding_curve_address(mint: &Pubkey) -> Result<Pubkey> {
+    let bonding_curve = bonding_curve_address(mint)?;
+    let token_program = Pubkey::from_str(TOKEN_PROGRAM)?;
+    let associated_token_program = Pubkey::from_str(ASSOCIATED_TOKEN_PROGRAM)?;
+
+    let (address, _) = Pubkey::find_program_address(
+        &[
+            bonding_curve.as_ref(),
+            token_program.as_ref(),
+            mint.as_ref(),
+        ],
+        &associated_token_program,
+    );
+    Ok(address)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BondingCurve {
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+}
+
+impl BondingCurve {
+    pub fn new(virtual_sol_reserves: u64, virtual_token_reserves: u64) -> Self {
+        Self {
+            virtual_sol_reserves,
+            virtual_token_reserves,
+        }
+    }
+
+    pub fn price_in_sol(&self) -> f64 {
+        if self.virtual_token_reserves == 0 {
+            return 0.0;
+        }
+        let sol = self.virtual_sol_reserves as f64 / LAMPORTS_PER_SOL;
+        let tokens =
+            self.virtual_token_reserves as f64 / 10f64.powi(PUMP_FUN_TOKEN_DECIMALS as i32);
+        sol / tokens
+    }
+
+    pub fn market_cap_sol(&self) -> f64 {
+        let supply = PUMP_FUN_TOTAL_SUPPLY as f64 / 10f64.powi(PUMP_FUN_TOKEN_DECIMALS as i32);
+        self.price_in_sol() * supply
+    }
+
+    // The curve completes, and the token can migrate, once every real token has been sold.
+    pub fn progress_percent(&self) -> f64 {
+        let sold =
+            PUMP_FUN_INITIAL_VIRTUAL_TOKEN_RESERVES.saturating_sub(self.virtual_token_reserves);
+        (sold as f64 / PUMP_FUN_INITIAL_REAL_TOKEN_RESERVES as f64 * 100.0).min(100.0)
+    }
+
+    // `None` when the curve cannot price the trade, e.g. empty reserves.
+    pub fn buy_quote(&self, lamports_in: u64) -> Option<u64> {
+        let lamports_in = lamports_in.checked_sub(fee(lamports_in)?)? as u128;
+        let sol = self.virtual_sol_reserves as u128;
+        let tokens = self.virtual_token_reserves as u128;
+
+        let out = tokens
+            .checked_mul(lamports_in)?
+            .checked_div(sol.checked_add(lamports_in)?)?;
+        u64::try_from(out).ok()
+    }
+
+    pub fn sell_quote(&self, tokens_in: u64) -> Option<u64> {
+        let tokens_in = tokens_in as u128;
+        let sol = self.virtual_sol_reserves as u128;
+        let tokens = self.virtual_token_reserves as u128;
+
+        let out = sol
+            .checked_mul(tokens_in)?
+            .checked_div(tokens.checked_add(tokens_in)?)?;
+        let out = u64::try_from(out).ok()?;
+        out.checked_sub(fee(out)?)
+    }
+}
+
+fn fee(lamports: u64) -> Option<u64> {
+    let fee = (lamports as u128)
+        .checked_mul(PUMP_FUN_FEE_BPS as u128)?
+        .div_ceil(10_000);
+    u64::try_from(fee).ok()
+}
+
+pub fn pump_fun_swap_request(
+    quote_request: &api::GetPumpFunQuotesRequest,
+    quote: &api::GetPumpFunQuotesResponse,
+    settings: &SwapSettings,
+) -> Result<api::PostPumpFunSwapRequest> {
+    Ok(pump_fun_quoted_swap(
+        quote_request.quote_type.parse()?,
+        &quote_request.mint_address,
+        &quote_request.bonding_curve_address,
+        quote.in_amount,
+        quote.out_amount,
+        settings,
+    ))
+}
+
+// The swap always names a token amount and a SOL bound: the SOL spent on a buy, or the SOL
+// received on a sell. Both are the quote's raw amounts; the API applies `slippage` to the
+// bound, so it is never lowered here as well.
+pub fn pump_fun_quoted_swap(
+    quote_type: PumpFunQuoteType,
+    mint_address: &str,
+    bonding_curve_address: &str,
+    quote_in_amount: f64,
+    quote_out_amount: f64,
+    settings: &SwapSettings,
+) -> api::PostPumpFunSwapRequest {
+    let (token_amount, sol_threshold) = match quote_type {
+        PumpFunQuoteType::Buy => (quote_out_amount, quote_in_amount),
+        PumpFunQuoteType::Sell => (quote_in_amount, quote_out_amount),
+    };
+
+    api::PostPumpFunSwapRequest {
+        user_address: settings.owner_address.clone(),
+        bonding_curve_address: bonding_curve_address.to_string(),
+        token_address: mint_address.to_string(),
+        token_amount,
+        sol_threshold,
+        compute_limit: settings.compute_limit,
+        compute_price: settings.compute_price,
+        tip: settings.tip_lamports(),
+        is_buy: quote_type.is_buy(),
+        slippage: settings.slippage_percent(),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PumpFunQuote {
+    pub request: api::GetPumpFunQuotesRequest,
+    pub response: api::GetPumpFunQuotesResponse,
+}
+
+impl SwapQuote for PumpFunQuote {
+    fn swap_request(&self, settings: &SwapSettings) -> Result<SwapRequest> {
+        Ok(SwapRequest::PumpFun(pump_fun_swap_request(
+            &self.request,
+            &self.response,
+            settings,
+        )?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::amounts::SlippageBps;
+
+    #[test]
+    fn test_quote_type_parsing() {
+        assert_eq!(
+            "buy".parse::<PumpFunQuoteType>().unwrap(),
+            PumpFunQuoteType::Buy
+        );
+        assert_eq!(
+            "SELL".parse::<PumpFunQuoteType>().unwrap(),
+            PumpFunQuoteType::Sell
+        );
+        assert!("swap".parse::<PumpFunQuoteType>().is_err());
+    }
+
+    #[test]
+    fn test_bonding_curve_address() {
+        let mint = Pubkey::from_str("2DEsbYgW94AtZxgUfYXoL8DqJAorsLrEWZdSfriipump").unwrap();
+        assert_eq!(
+            bonding_curve_address(&mint).unwrap().to_string(),
+            "Fh8fnZUVEpPStJ2hKFNNjMAyuyvoJLMouENawg4DYCBc"
+        );
+    }
+
+    #[test]
+    fn test_bonding_curve_quotes() {
+        // Initial pump.fun curve: 30 SOL and 1.073B tokens of virtual reserves.
+        let curve = BondingCurve::new(30_000_000_000, 1_073_000_000_000_000);
+
+        let tokens = curve.buy_quote(1_000_000_000).unwrap();
+        assert_eq!(tokens, 34_277_831_558_567);
+
+        let lamports = curve.sell_quote(tokens).unwrap();
+        assert!(lamports < 1_000_000_000);
+        assert!((curve.price_in_sol() - 0.000000028).abs() < 1e-9);
+        assert_eq!(curve.progress_percent(), 0.0);
+        assert!((curve.market_cap_sol() - 27.96).abs() < 0.01);
+
+        let completed = BondingCurve::new(
+            85_000_000_000,
+            PUMP_FUN_INITIAL_VIRTUAL_TOKEN_RESERVES - PUMP_FUN_INITIAL_REAL_TOKEN_RESERVES,
+        );
+        assert_eq!(completed.progress_percent(), 100.0);
+
+        let empty = BondingCurve::new(0, 0);
+        assert_eq!(empty.buy_quote(0), None);
+        assert_eq!(empty.sell_quote(0), None);
+        assert_eq!(empty.buy_quote(1_000), Some(0));
+
+        let max = BondingCurve::new(u64::MAX, u64::MAX);
+        assert!(max.buy_quote(u64::MAX).is_some());
+        assert!(max.sell_quote(u64::MAX).is_some());
+    }
+
+    #[test]
+    fn test_swap_request_direction() {
+        let settings = SwapSettings {
+            owner_address: "owner".to_string(),
+            slippage: SlippageBps::new(1000).unwrap(),
+            ..Default::default()
+        };
+        let quote = api::GetPumpFunQuotesResponse {
+            in_amount: 0.5,
+            out_amount: 1000.0,
+            ..Default::default()
+        };
+
+        let buy = pump_fun_quote_request(PumpFunQuoteType::Buy, "mint", "curve", 0.5);
+        let request = pump_fun_swap_request(&buy, &quote, &settings).unwrap();
+        assert!(request.is_buy);
+        assert_eq!(request.token_amount, 1000.0);
+        assert_eq!(request.sol_threshold, 0.5);
+
+        let sell = pump_fun_quote_request(PumpFunQuoteType::Sell, "mint", "curve", 1000.0);
+        let quote = api::GetPumpFunQuotesResponse {
+            in_amount: 1000.0,
+            out_amount: 0.5,
+            ..Default::default()
+        };
+        let request = pump_fun_swap_request(&sell, &quote, &settings).unwrap();
+        assert!(!request.is_buy);
+        assert_eq!(request.token_amount, 1000.0);
+        assert_eq!(request.sol_threshold, 0.5);
+        assert_eq!(request.token_address, "mint");
+    }
+}
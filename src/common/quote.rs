@@ -0,0 +1,952 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use futures_util::future::join_all;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+use solana_trader_proto::api;
+use tokio::time::timeout;
+
+use super::amounts::{Lamports, SlippageBps, TokenAmount};
+use super::constants::WRAPPED_SOL;
+use super::pump::{pump_fun_quote_request, pump_fun_quoted_swap, PumpFunQuoteType};
+
+const DEFAULT_QUOTE_DEADLINE: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Venue {
+    Raydium,
+    RaydiumCpmm,
+    RaydiumClmm,
+    Jupiter,
+    PumpFun,
+}
+
+impl Venue {
+    pub fn all() -> Vec<Venue> {
+        vec![
+            Venue::Raydium,
+            Venue::RaydiumCpmm,
+            Venue::RaydiumClmm,
+            Venue::Jupiter,
+            Venue::PumpFun,
+        ]
+    }
+}
+
+// Proto requests carry UI amounts without the token's decimals, so those are passed through
+// as given instead of being fitted to a guessed scale.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QuoteAmount {
+    Exact(TokenAmount),
+    Ui(f64),
+}
+
+impl QuoteAmount {
+    pub fn to_ui_f64(&self) -> f64 {
+        match self {
+            QuoteAmount::Exact(amount) => amount.to_ui_f64(),
+            QuoteAmount::Ui(amount) => *amount,
+        }
+    }
+}
+
+impl From<TokenAmount> for QuoteAmount {
+    fn from(amount: TokenAmount) -> Self {
+        QuoteAmount::Exact(amount)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BestQuoteRequest {
+    pub in_token: String,
+    pub out_token: String,
+    // Converted to the UI amount the quote endpoints take only when a request is built.
+    pub in_amount: QuoteAmount,
+    pub slippage: SlippageBps,
+    pub venues: Vec<Venue>,
+    pub deadline: Duration,
+    // Pump.fun quotes are keyed by bonding curve, so the venue is skipped without one.
+    pub bonding_curve_address: Option<String>,
+}
+
+impl BestQuoteRequest {
+    pub fn new(
+        in_token: &str,
+        out_token: &str,
+        in_amount: impl Into<QuoteAmount>,
+        slippage: SlippageBps,
+    ) -> Self {
+        Self {
+            in_token: in_token.to_string(),
+            out_token: out_token.to_string(),
+            in_amount: in_amount.into(),
+            slippage,
+            venues: Self::default_venues(),
+            deadline: DEFAULT_QUOTE_DEADLINE,
+            bonding_curve_address: None,
+        }
+    }
+
+    // For UI amounts taken from a proto request, where the token decimals are not known.
+    pub fn from_proto(
+        in_token: &str,
+        out_token: &str,
+        in_amount: f64,
+        slippage_percent: f64,
+    ) -> Result<Self> {
+        if !in_amount.is_finite() || in_amount < 0.0 {
+            return Err(anyhow!("Invalid in amount {}", in_amount));
+        }
+
+        Ok(Self::new(
+            in_token,
+            out_token,
+            QuoteAmount::Ui(in_amount),
+            SlippageBps::from_percent(slippage_percent)?,
+        ))
+    }
+
+    pub fn default_venues() -> Vec<Venue> {
+        vec![
+            Venue::Raydium,
+            Venue::RaydiumCpmm,
+            Venue::RaydiumClmm,
+            Venue::Jupiter,
+        ]
+    }
+
+    pub fn raydium_request(&self) -> api::GetRaydiumQuotesRequest {
+        api::GetRaydiumQuotesRequest {
+            in_token: self.in_token.clone(),
+            out_token: self.out_token.clone(),
+            in_amount: self.in_amount.to_ui_f64(),
+            slippage: self.slippage.to_percent(),
+        }
+    }
+
+    pub fn raydium_cpmm_request(&self) -> api::GetRaydiumCpmmQuotesRequest {
+        api::GetRaydiumCpmmQuotesRequest {
+            in_token: self.in_token.clone(),
+            out_token: self.out_token.clone(),
+            in_amount: self.in_amount.to_ui_f64(),
+            slippage: self.slippage.to_percent(),
+        }
+    }
+
+    pub fn raydium_clmm_request(&self) -> api::GetRaydiumClmmQuotesRequest {
+        api::GetRaydiumClmmQuotesRequest {
+            in_token: self.in_token.clone(),
+            out_token: self.out_token.clone(),
+            in_amount: self.in_amount.to_ui_f64(),
+            slippage: self.slippage.to_percent(),
+        }
+    }
+
+    pub fn jupiter_request(&self) -> api::GetJupiterQuotesRequest {
+        api::GetJupiterQuotesRequest {
+            in_token: self.in_token.clone(),
+            out_token: self.out_token.clone(),
+            in_amount: self.in_amount.to_ui_f64(),
+            slippage: self.slippage.to_percent(),
+            fast_mode: None,
+        }
+    }
+
+    pub fn pump_fun_request(&self) -> Result<api::GetPumpFunQuotesRequest> {
+        let bonding_curve_address = self
+            .bonding_curve_address
+            .clone()
+            .ok_or_else(|| anyhow!("Pump.fun quotes require a bonding curve address"))?;
+
+        let (quote_type, mint_address) = if self.in_token == WRAPPED_SOL {
+            (PumpFunQuoteType::Buy, &self.out_token)
+        } else {
+            (PumpFunQuoteType::Sell, &self.in_token)
+        };
+
+        Ok(pump_fun_quote_request(
+            quote_type,
+            mint_address,
+            &bonding_curve_address,
+            self.in_amount.to_ui_f64(),
+        ))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteLeg {
+    pub label: String,
+    pub pool_address: String,
+    pub in_token: String,
+    pub out_token: String,
+    pub in_amount: f64,
+    pub out_amount: f64,
+    pub out_amount_min: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NormalizedQuote {
+    pub venue: Venue,
+    pub in_token: String,
+    pub out_token: String,
+    pub in_amount: f64,
+    pub out_amount: f64,
+    pub out_amount_min: f64,
+    pub price_impact_percent: Option<f64>,
+    // Summed per fee mint, since the steps of a route can charge fees in different tokens.
+    pub fees: BTreeMap<String, f64>,
+    pub route: Vec<RouteLeg>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SwapSettings {
+    pub owner_address: String,
+    pub slippage: SlippageBps,
+    pub compute_limit: u32,
+    // Micro-lamports per compute unit.
+    pub compute_price: u64,
+    pub tip: Option<Lamports>,
+}
+
+impl SwapSettings {
+    pub fn slippage_percent(&self) -> f64 {
+        self.slippage.to_percent()
+    }
+
+    pub fn tip_lamports(&self) -> Option<u64> {
+        self.tip.map(Lamports::get)
+    }
+
+    // Falls back to the client's own key when no owner was set explicitly.
+    pub fn with_default_owner(&self, public_key: Option<Pubkey>) -> Result<SwapSettings> {
+        let mut settings = self.clone();
+        if settings.owner_address.is_empty() {
+            settings.owner_address = public_key
+                .ok_or_else(|| anyhow!("An owner address is required to build a swap"))?
+                .to_string();
+        }
+        Ok(settings)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "venue", content = "request", rename_all = "snake_case")]
+pub enum SwapRequest {
+    Raydium(api::PostRaydiumSwapRequest),
+    RaydiumRoute(api::PostRaydiumRouteSwapRequest),
+    RaydiumCpmm(api::PostRaydiumCpmmSwapRequest),
+    RaydiumClmm(api::PostRaydiumSwapRequest),
+    RaydiumClmmRoute(api::PostRaydiumRouteSwapRequest),
+    Jupiter(api::PostJupiterSwapRequest),
+    JupiterRoute(api::PostJupiterRouteSwapRequest),
+    PumpFun(api::PostPumpFunSwapRequest),
+}
+
+pub trait SwapQuote {
+    fn swap_request(&self, settings: &SwapSettings) -> Result<SwapRequest>;
+}
+
+pub fn raydium_route_swap_request(
+    route: &api::RaydiumQuoteRoute,
+    settings: &SwapSettings,
+) -> api::PostRaydiumRouteSwapRequest {
+    api::PostRaydiumRouteSwapRequest {
+        owner_address: settings.owner_address.clone(),
+        slippage: settings.slippage_percent(),
+        steps: route
+            .steps
+            .iter()
+            .map(|step| api::RaydiumRouteStep {
+                in_token: step.in_token.clone(),
+                out_token: step.out_token.clone(),
+                in_amount: step.in_amount,
+                out_amount: step.out_amount,
+                out_amount_min: step.out_amount_min,
+                pool_address: step.pool_address.clone(),
+                project: step.project.clone(),
+            })
+            .collect(),
+        compute_limit: settings.compute_limit,
+        compute_price: settings.compute_price,
+        tip: settings.tip_lamports(),
+    }
+}
+
+pub fn raydium_cpmm_swap_request(
+    route: &api::RaydiumQuoteRoute,
+    settings: &SwapSettings,
+) -> Result<api::PostRaydiumCpmmSwapRequest> {
+    let [step] = route.steps.as_slice() else {
+        return Err(anyhow!(
+            "CPMM swaps execute against a single pool, route has {} steps",
+            route.steps.len()
+        ));
+    };
+
+    Ok(api::PostRaydiumCpmmSwapRequest {
+        owner_address: settings.owner_address.clone(),
+        pool_address: step.pool_address.clone(),
+        in_token: step.in_token.clone(),
+        out_token: step.out_token.clone(),
+        in_amount: route.in_amount,
+        slippage: settings.slippage_percent(),
+        compute_limit: settings.compute_limit,
+        compute_price: settings.compute_price,
+        tip: settings.tip_lamports(),
+    })
+}
+
+pub fn jupiter_route_swap_request(
+    route: &api::JupiterQuoteRoute,
+    settings: &SwapSettings,
+) -> api::PostJupiterRouteSwapRequest {
+    api::PostJupiterRouteSwapRequest {
+        owner_address: settings.owner_address.clone(),
+        slippage: settings.slippage_percent(),
+        steps: route
+            .steps
+            .iter()
+            .map(|step| api::JupiterRouteStep {
+                project: step.project.clone(),
+                in_token: step.in_token.clone(),
+                out_token: step.out_token.clone(),
+                in_amount: step.in_amount,
+                out_amount: step.out_amount,
+                out_amount_min: step.out_amount_min,
+                fee: step.fee.clone(),
+            })
+            .collect(),
+        compute_limit: settings.compute_limit,
+        compute_price: settings.compute_price,
+        tip: settings.tip_lamports(),
+    }
+}
+
+fn best_raydium_route(routes: &[api::RaydiumQuoteRoute]) -> Result<&api::RaydiumQuoteRoute> {
+    routes
+        .iter()
+        .max_by(|a, b| a.out_amount_min.total_cmp(&b.out_amount_min))
+        .ok_or_else(|| anyhow!("Quote response has no routes"))
+}
+
+impl SwapQuote for api::GetRaydiumQuotesResponse {
+    fn swap_request(&self, settings: &SwapSettings) -> Result<SwapRequest> {
+        let route = best_raydium_route(&self.routes)?;
+        Ok(SwapRequest::RaydiumRoute(raydium_route_swap_request(
+            route, settings,
+        )))
+    }
+}
+
+impl SwapQuote for api::GetRaydiumClmmQuotesResponse {
+    fn swap_request(&self, settings: &SwapSettings) -> Result<SwapRequest> {
+        let route = best_raydium_route(&self.routes)?;
+        Ok(SwapRequest::RaydiumClmmRoute(raydium_route_swap_request(
+            route, settings,
+        )))
+    }
+}
+
+impl SwapQuote for api::GetRaydiumCpmmQuotesResponse {
+    fn swap_request(&self, settings: &SwapSettings) -> Result<SwapRequest> {
+        let route = best_raydium_route(&self.routes)?;
+        Ok(SwapRequest::RaydiumCpmm(raydium_cpmm_swap_request(
+            route, settings,
+        )?))
+    }
+}
+
+impl SwapQuote for api::GetJupiterQuotesResponse {
+    fn swap_request(&self, settings: &SwapSettings) -> Result<SwapRequest> {
+        let route = self
+            .routes
+            .iter()
+            .max_by(|a, b| a.out_amount_min.total_cmp(&b.out_amount_min))
+            .ok_or_else(|| anyhow!("Quote response has no routes"))?;
+        Ok(SwapRequest::JupiterRoute(jupiter_route_swap_request(
+            route, settings,
+        )))
+    }
+}
+
+impl SwapQuote for NormalizedQuote {
+    fn swap_request(&self, settings: &SwapSettings) -> Result<SwapRequest> {
+        let request = match self.venue {
+            Venue::Raydium => SwapRequest::Raydium(self.raydium_swap_request(settings)),
+            Venue::RaydiumClmm => SwapRequest::RaydiumClmm(self.raydium_swap_request(settings)),
+            Venue::RaydiumCpmm => SwapRequest::RaydiumCpmm(api::PostRaydiumCpmmSwapRequest {
+                owner_address: settings.owner_address.clone(),
+                pool_address: self.first_pool_address()?,
+                in_token: self.in_token.clone(),
+                out_token: self.out_token.clone(),
+                in_amount: self.in_amount,
+                slippage: settings.slippage_percent(),
+                compute_limit: settings.compute_limit,
+                compute_price: settings.compute_price,
+                tip: settings.tip_lamports(),
+            }),
+            Venue::Jupiter => SwapRequest::Jupiter(api::PostJupiterSwapRequest {
+                owner_address: settings.owner_address.clone(),
+                in_token: self.in_token.clone(),
+                out_token: self.out_token.clone(),
+                in_amount: self.in_amount,
+                slippage: settings.slippage_percent(),
+                compute_limit: settings.compute_limit,
+                compute_price: settings.compute_price,
+                tip: settings.tip_lamports(),
+                fast_mode: None,
+            }),
+            Venue::PumpFun => {
+                let (quote_type, mint_address) = if self.in_token == WRAPPED_SOL {
+                    (PumpFunQuoteType::Buy, &self.out_token)
+                } else {
+                    (PumpFunQuoteType::Sell, &self.in_token)
+                };
+
+                SwapRequest::PumpFun(pump_fun_quoted_swap(
+                    quote_type,
+                    mint_address,
+                    &self.first_pool_address()?,
+                    self.in_amount,
+                    self.out_amount,
+                    settings,
+                ))
+            }
+        };
+
+        Ok(request)
+    }
+}
+
+impl NormalizedQuote {
+    fn raydium_swap_request(&self, settings: &SwapSettings) -> api::PostRaydiumSwapRequest {
+        api::PostRaydiumSwapRequest {
+            owner_address: settings.owner_address.clone(),
+            in_token: self.in_token.clone(),
+            out_token: self.out_token.clone(),
+            in_amount: self.in_amount,
+            slippage: settings.slippage_percent(),
+            compute_limit: settings.compute_limit,
+            compute_price: settings.compute_price,
+            tip: settings.tip_lamports(),
+        }
+    }
+
+    fn first_pool_address(&self) -> Result<String> {
+        self.route
+            .first()
+            .map(|leg| leg.pool_address.clone())
+            .filter(|address| !address.is_empty())
+            .ok_or_else(|| anyhow!("{:?} quote has no pool address", self.venue))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BestQuote {
+    pub best: NormalizedQuote,
+    pub alternatives: Vec<NormalizedQuote>,
+    pub failures: Vec<(Venue, String)>,
+}
+
+impl SwapQuote for BestQuote {
+    fn swap_request(&self, settings: &SwapSettings) -> Result<SwapRequest> {
+        self.best.swap_request(settings)
+    }
+}
+
+pub fn normalize_raydium_routes(
+    venue: Venue,
+    in_token: &str,
+    out_token: &str,
+    routes: &[api::RaydiumQuoteRoute],
+) -> Vec<NormalizedQuote> {
+    routes
+        .iter()
+        .map(|route| {
+            let legs: Vec<RouteLeg> = route
+                .steps
+                .iter()
+                .map(|step| RouteLeg {
+                    label: step
+                        .project
+                        .as_ref()
+                        .map(|p| p.label.clone())
+                        .unwrap_or_default(),
+                    pool_address: step.pool_address.clone(),
+                    in_token: step.in_token.clone(),
+                    out_token: step.out_token.clone(),
+                    in_amount: step.in_amount,
+                    out_amount: step.out_amount,
+                    out_amount_min: step.out_amount_min,
+                })
+                .collect();
+
+            let price_impact_percent = route
+                .steps
+                .iter()
+                .filter_map(|step| step.price_impact_percent.as_ref().map(|p| p.percent))
+                .reduce(|a, b| a + b);
+
+            let fees = fees_by_mint(route.steps.iter().flat_map(|step| step.fee.iter()));
+
+            NormalizedQuote {
+                venue,
+                in_token: in_token.to_string(),
+                out_token: out_token.to_string(),
+                in_amount: route.in_amount,
+                out_amount: route.out_amount,
+                out_amount_min: route.out_amount_min,
+                price_impact_percent,
+                fees,
+                route: legs,
+            }
+        })
+        .collect()
+}
+
+pub fn normalize_jupiter_quotes(response: &api::GetJupiterQuotesResponse) -> Vec<NormalizedQuote> {
+    response
+        .routes
+        .iter()
+        .map(|route| {
+            let legs: Vec<RouteLeg> = route
+                .steps
+                .iter()
+                .map(|step| RouteLeg {
+                    label: step
+                        .project
+                        .as_ref()
+                        .map(|p| p.label.clone())
+                        .unwrap_or_default(),
+                    pool_address: step
+                        .project
+                        .as_ref()
+                        .map(|p| p.id.clone())
+                        .unwrap_or_default(),
+                    in_token: step.in_token.clone(),
+                    out_token: step.out_token.clone(),
+                    in_amount: step.in_amount,
+                    out_amount: step.out_amount,
+                    out_amount_min: step.out_amount_min,
+                })
+                .collect();
+
+            let fees = fees_by_mint(route.steps.iter().filter_map(|step| step.fee.as_ref()));
+
+            NormalizedQuote {
+                venue: Venue::Jupiter,
+                in_token: response.in_token.clone(),
+                out_token: response.out_token.clone(),
+                in_amount: route.in_amount,
+                out_amount: route.out_amount,
+                out_amount_min: route.out_amount_min,
+                price_impact_percent: Some(route.price_impact_percent),
+                fees,
+                route: legs,
+            }
+        })
+        .collect()
+}
+
+fn fees_by_mint<'a>(fees: impl Iterator<Item = &'a api::Fee>) -> BTreeMap<String, f64> {
+    let mut by_mint = BTreeMap::new();
+    for fee in fees {
+        *by_mint.entry(fee.mint.clone()).or_insert(0.0) += fee.amount;
+    }
+    by_mint
+}
+
+pub fn normalize_pump_fun_quote(
+    request: &BestQuoteRequest,
+    response: &api::GetPumpFunQuotesResponse,
+) -> NormalizedQuote {
+    let out_amount_min = response.out_amount * (1.0 - request.slippage.to_percent() / 100.0);
+
+    NormalizedQuote {
+        venue: Venue::PumpFun,
+        in_token: request.in_token.clone(),
+        out_token: request.out_token.clone(),
+        in_amount: response.in_amount,
+        out_amount: response.out_amount,
+        out_amount_min,
+        price_impact_percent: None,
+        fees: BTreeMap::new(),
+        route: vec![RouteLeg {
+            label: "Pump.fun".to_string(),
+            pool_address: request.bonding_curve_address.clone().unwrap_or_default(),
+            in_token: request.in_token.clone(),
+            out_token: request.out_token.clone(),
+            in_amount: response.in_amount,
+            out_amount: response.out_amount,
+            out_amount_min,
+        }],
+    }
+}
+
+pub fn compare_quotes(a: &NormalizedQuote, b: &NormalizedQuote) -> Ordering {
+    b.out_amount_min
+        .total_cmp(&a.out_amount_min)
+        .then_with(|| b.out_amount.total_cmp(&a.out_amount))
+        .then_with(|| {
+            let a_impact = a.price_impact_percent.unwrap_or(f64::MAX);
+            let b_impact = b.price_impact_percent.unwrap_or(f64::MAX);
+            a_impact.total_cmp(&b_impact)
+        })
+}
+
+pub fn rank_quotes(
+    quotes: Vec<NormalizedQuote>,
+    failures: Vec<(Venue, String)>,
+) -> Result<BestQuote> {
+    let mut quotes: Vec<NormalizedQuote> = quotes
+        .into_iter()
+        .filter(|q| q.out_amount > 0.0 && q.out_amount.is_finite())
+        .collect();
+    quotes.sort_by(compare_quotes);
+
+    if quotes.is_empty() {
+        let reasons: Vec<String> = failures
+            .iter()
+            .map(|(venue, e)| format!("{:?}: {}", venue, e))
+            .collect();
+        return Err(anyhow!(
+            "No venue returned a quote [{}]",
+            reasons.join("; ")
+        ));
+    }
+
+    let best = quotes.remove(0);
+    Ok(BestQuote {
+        best,
+        alternatives: quotes,
+        failures,
+    })
+}
+
+pub async fn aggregate_quotes<F, Fut>(request: &BestQuoteRequest, fetch: F) -> Result<BestQuote>
+where
+    F: Fn(Venue) -> Fut,
+    Fut: Future<Output = Result<Vec<NormalizedQuote>>>,
+{
+    let venues: Vec<Venue> = request
+        .venues
+        .iter()
+        .copied()
+        .filter(|v| *v != Venue::PumpFun || request.bonding_curve_address.is_some())
+        .collect();
+
+    let results = join_all(venues.into_iter().map(|venue| {
+        let fetched = fetch(venue);
+        async move { (venue, timeout(request.deadline, fetched).await) }
+    }))
+    .await;
+
+    let mut quotes = Vec::new();
+    let mut failures = Vec::new();
+    for (venue, result) in results {
+        match result {
+            Ok(Ok(venue_quotes)) => quotes.extend(venue_quotes),
+            Ok(Err(e)) => failures.push((venue, e.to_string())),
+            Err(_) => failures.push((venue, "deadline exceeded".to_string())),
+        }
+    }
+
+    rank_quotes(quotes, failures)
+}
+
+// The per-venue quote calls behind `best_quote`, implemented once per transport.
+#[async_trait]
+pub trait QuoteSource: Sync {
+    async fn raydium_quotes(
+        &self,
+        request: &api::GetRaydiumQuotesRequest,
+    ) -> Result<api::GetRaydiumQuotesResponse>;
+
+    async fn raydium_cpmm_quotes(
+        &self,
+        request: &api::GetRaydiumCpmmQuotesRequest,
+    ) -> Result<api::GetRaydiumCpmmQuotesResponse>;
+
+    async fn raydium_clmm_quotes(
+        &self,
+        request: &api::GetRaydiumClmmQuotesRequest,
+    ) -> Result<api::GetRaydiumClmmQuotesResponse>;
+
+    async fn jupiter_quotes(
+        &self,
+        request: &api::GetJupiterQuotesRequest,
+    ) -> Result<api::GetJupiterQuotesResponse>;
+
+    async fn pump_fun_quotes(
+        &self,
+        request: &api::GetPumpFunQuotesRequest,
+    ) -> Result<api::GetPumpFunQuotesResponse>;
+}
+
+pub async fn venue_quotes<S: QuoteSource + ?Sized>(
+    source: &S,
+    venue: Venue,
+    request: &BestQuoteRequest,
+) -> Result<Vec<NormalizedQuote>> {
+    let quotes = match venue {
+        Venue::Raydium => {
+            let response = source.raydium_quotes(&request.raydium_request()).await?;
+            normalize_raydium_routes(
+                venue,
+                &request.in_token,
+                &request.out_token,
+                &response.routes,
+            )
+        }
+        Venue::RaydiumCpmm => {
+            let response = source
+                .raydium_cpmm_quotes(&request.raydium_cpmm_request())
+                .await?;
+            normalize_raydium_routes(
+                venue,
+                &request.in_token,
+                &request.out_token,
+                &response.routes,
+            )
+        }
+        Venue::RaydiumClmm => {
+            let response = source
+                .raydium_clmm_quotes(&request.raydium_clmm_request())
+                .await?;
+            normalize_raydium_routes(
+                venue,
+                &request.in_token,
+                &request.out_token,
+                &response.routes,
+            )
+        }
+        Venue::Jupiter => {
+            let response = source.jupiter_quotes(&request.jupiter_request()).await?;
+            normalize_jupiter_quotes(&response)
+        }
+        Venue::PumpFun => {
+            let response = source.pump_fun_quotes(&request.pump_fun_request()?).await?;
+            vec![normalize_pump_fun_quote(request, &response)]
+        }
+    };
+
+    Ok(quotes)
+}
+
+pub async fn best_quote<S: QuoteSource + ?Sized>(
+    source: &S,
+    request: &BestQuoteRequest,
+) -> Result<BestQuote> {
+    aggregate_quotes(request, |venue| venue_quotes(source, venue, request)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::pump::pump_fun_swap_request;
+
+    fn quote(venue: Venue, out_amount: f64, out_amount_min: f64) -> NormalizedQuote {
+        NormalizedQuote {
+            venue,
+            in_token: WRAPPED_SOL.to_string(),
+            out_token: "USDC".to_string(),
+            in_amount: 1.0,
+            out_amount,
+            out_amount_min,
+            price_impact_percent: None,
+            fees: BTreeMap::new(),
+            route: vec![RouteLeg {
+                label: "test".to_string(),
+                pool_address: "pool".to_string(),
+                in_token: WRAPPED_SOL.to_string(),
+                out_token: "USDC".to_string(),
+                in_amount: 1.0,
+                out_amount,
+                out_amount_min,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_rank_quotes_prefers_min_out() {
+        let quotes = vec![
+            quote(Venue::Raydium, 101.0, 95.0),
+            quote(Venue::Jupiter, 100.0, 99.0),
+            quote(Venue::RaydiumCpmm, 0.0, 0.0),
+        ];
+
+        let best = rank_quotes(quotes, vec![]).unwrap();
+        assert_eq!(best.best.venue, Venue::Jupiter);
+        assert_eq!(best.alternatives.len(), 1);
+        assert_eq!(best.alternatives[0].venue, Venue::Raydium);
+    }
+
+    #[test]
+    fn test_rank_quotes_reports_failures() {
+        let failures = vec![(Venue::Jupiter, "deadline exceeded".to_string())];
+        let err = rank_quotes(vec![], failures).unwrap_err();
+        assert!(err.to_string().contains("deadline exceeded"));
+    }
+
+    #[test]
+    fn test_pump_fun_request_direction() {
+        let mut request = BestQuoteRequest::new(
+            WRAPPED_SOL,
+            "mint",
+            TokenAmount::new(100_000_000, 9),
+            SlippageBps::new(500).unwrap(),
+        );
+        assert_eq!(request.raydium_request().in_amount, 0.1);
+        assert_eq!(request.raydium_request().slippage, 5.0);
+        assert!(request.pump_fun_request().is_err());
+
+        request.bonding_curve_address = Some("curve".to_string());
+        let buy = request.pump_fun_request().unwrap();
+        assert_eq!(buy.quote_type, "buy");
+        assert_eq!(buy.mint_address, "mint");
+
+        let request = BestQuoteRequest {
+            in_token: "mint".to_string(),
+            out_token: WRAPPED_SOL.to_string(),
+            ..request
+        };
+        let sell = request.pump_fun_request().unwrap();
+        assert_eq!(sell.quote_type, "sell");
+        assert_eq!(sell.mint_address, "mint");
+    }
+
+    #[test]
+    fn test_pump_fun_swaps_match_across_quote_paths() {
+        let settings = SwapSettings {
+            owner_address: "owner".to_string(),
+            slippage: SlippageBps::new(500).unwrap(),
+            ..Default::default()
+        };
+        let buy = BestQuoteRequest {
+            bonding_curve_address: Some("curve".to_string()),
+            ..BestQuoteRequest::new(
+                WRAPPED_SOL,
+                "mint",
+                TokenAmount::new(100_000_000, 9),
+                settings.slippage,
+            )
+        };
+        let sell = BestQuoteRequest {
+            in_token: "mint".to_string(),
+            out_token: WRAPPED_SOL.to_string(),
+            ..buy.clone()
+        };
+
+        for (request, response) in [
+            (
+                buy,
+                api::GetPumpFunQuotesResponse {
+                    in_amount: 0.1,
+                    out_amount: 3_500_000.0,
+                    ..Default::default()
+                },
+            ),
+            (
+                sell,
+                api::GetPumpFunQuotesResponse {
+                    in_amount: 3_500_000.0,
+                    out_amount: 0.098,
+                    ..Default::default()
+                },
+            ),
+        ] {
+            let direct =
+                pump_fun_swap_request(&request.pump_fun_request().unwrap(), &response, &settings)
+                    .unwrap();
+            let SwapRequest::PumpFun(best) = normalize_pump_fun_quote(&request, &response)
+                .swap_request(&settings)
+                .unwrap()
+            else {
+                panic!("expected a pump.fun swap");
+            };
+            assert_eq!(best, direct);
+            // Slippage is left to the API rather than applied to the bound as well.
+            assert_eq!(best.slippage, 5.0);
+            let sol = if best.is_buy { 0.1 } else { 0.098 };
+            assert_eq!(best.sol_threshold, sol);
+        }
+    }
+
+    #[test]
+    fn test_from_proto_keeps_the_ui_amount() {
+        let request = BestQuoteRequest::from_proto(WRAPPED_SOL, "mint", 1.5, 0.5).unwrap();
+        assert_eq!(request.in_amount, QuoteAmount::Ui(1.5));
+        assert_eq!(request.jupiter_request().in_amount, 1.5);
+        assert_eq!(request.slippage, SlippageBps::new(50).unwrap());
+
+        // Larger than any u64 raw amount at nine decimals.
+        let request = BestQuoteRequest::from_proto("mint", WRAPPED_SOL, 5e10, 0.5).unwrap();
+        assert_eq!(request.raydium_request().in_amount, 5e10);
+
+        assert!(BestQuoteRequest::from_proto(WRAPPED_SOL, "mint", -1.0, 0.5).is_err());
+        assert!(BestQuoteRequest::from_proto(WRAPPED_SOL, "mint", f64::NAN, 0.5).is_err());
+    }
+
+    #[test]
+    fn test_raydium_route_swap_request_carries_steps() {
+        let route = api::RaydiumQuoteRoute {
+            in_amount: 1.0,
+            out_amount: 150.0,
+            out_amount_min: 149.0,
+            steps: vec![api::RaydiumQuoteStep {
+                project: Some(api::StepProject {
+                    label: "Raydium".to_string(),
+                    id: "amm".to_string(),
+                }),
+                in_token: WRAPPED_SOL.to_string(),
+                out_token: "USDC".to_string(),
+                in_amount: 1.0,
+                out_amount: 150.0,
+                out_amount_min: 149.0,
+                pool_address: "pool".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let settings = SwapSettings {
+            owner_address: "owner".to_string(),
+            slippage: SlippageBps::new(50).unwrap(),
+            compute_limit: 300000,
+            compute_price: 10000,
+            tip: Some(Lamports(1000)),
+        };
+
+        let request = raydium_route_swap_request(&route, &settings);
+        assert_eq!(request.owner_address, "owner");
+        assert_eq!(request.slippage, 0.5);
+        assert_eq!(request.tip, Some(1000));
+        assert_eq!(request.steps.len(), 1);
+        assert_eq!(request.steps[0].pool_address, "pool");
+        assert_eq!(request.steps[0].out_amount_min, 149.0);
+        assert_eq!(request.steps[0].project, route.steps[0].project);
+
+        let cpmm = raydium_cpmm_swap_request(&route, &settings).unwrap();
+        assert_eq!(cpmm.pool_address, "pool");
+        assert_eq!(cpmm.in_amount, 1.0);
+    }
+
+    #[test]
+    fn test_fees_are_kept_per_mint() {
+        let fee = |amount: f64, mint: &str| api::Fee {
+            amount,
+            mint: mint.to_string(),
+            ..Default::default()
+        };
+        let fees = [fee(0.5, "USDC"), fee(0.001, WRAPPED_SOL), fee(0.25, "USDC")];
+
+        let by_mint = fees_by_mint(fees.iter());
+        assert_eq!(by_mint.len(), 2);
+        assert_eq!(by_mint["USDC"], 0.75);
+        assert_eq!(by_mint[WRAPPED_SOL], 0.001);
+    }
+}
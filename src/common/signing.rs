@@ -1,4 +1,4 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use base64::{engine::general_purpose::STANDARD, Engine};
 use bincode::{deserialize, serialize};
 use serde::Serialize;
@@ -10,6 +10,8 @@ use solana_sdk::{
 };
 use solana_trader_proto::api;
 
+use crate::common::memo::{add_memo_to_serialized_tx, MEMO_MESSAGE};
+use crate::common::slippage::SlippageGuard;
 use crate::provider::utils::IntoTransactionMessage;
 
 #[derive(Debug, Clone, Serialize)]
@@ -21,6 +23,9 @@ pub struct SubmitParams {
     pub submit_strategy: api::SubmitStrategy,
     pub allow_back_run: Option<bool>,
     pub revenue_address: Option<String>,
+    pub slippage_guard: Option<SlippageGuard>,
+    // Appends the Trader API memo to every transaction before it is signed.
+    pub add_memo: bool,
 }
 
 impl Default for SubmitParams {
@@ -33,16 +38,142 @@ impl Default for SubmitParams {
             submit_strategy: api::SubmitStrategy::PSubmitAll,
             allow_back_run: None,
             revenue_address: None,
+            slippage_guard: None,
+            add_memo: false,
         }
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SignedTransaction {
     pub content: String,
     pub is_cleanup: bool,
 }
 
+impl SignedTransaction {
+    // The fee payer's signature, known before the transaction is submitted.
+    pub fn signature(&self) -> Result<String> {
+        let rawbytes = STANDARD.decode(&self.content)?;
+        let signatures = match deserialize::<VersionedTransaction>(&rawbytes) {
+            Ok(tx) => tx.signatures,
+            Err(_) => deserialize::<Transaction>(&rawbytes)?.signatures,
+        };
+        signatures
+            .first()
+            .map(Signature::to_string)
+            .ok_or_else(|| anyhow!("Transaction has no signatures"))
+    }
+}
+
+// Result of submitting one input transaction; `index` is its position in the submitted list.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SubmitOutcome {
+    pub index: usize,
+    pub signature: String,
+    pub submitted: bool,
+    pub error: Option<String>,
+}
+
+impl SubmitOutcome {
+    pub fn submitted(index: usize, signature: String) -> Self {
+        Self {
+            index,
+            signature,
+            submitted: true,
+            error: None,
+        }
+    }
+
+    pub fn rejected(index: usize, signature: String, error: String) -> Self {
+        Self {
+            index,
+            signature,
+            submitted: false,
+            error: Some(error),
+        }
+    }
+
+    // A rejected single transaction is reported like a rejected batch entry.
+    pub fn from_single(signed_tx: &SignedTransaction, result: Result<String>) -> Self {
+        match result {
+            Ok(signature) => Self::submitted(0, signature),
+            Err(e) => Self::rejected(0, signed_tx.signature().unwrap_or_default(), e.to_string()),
+        }
+    }
+
+    // Batch responses are matched to inputs by position; inputs the server did not report
+    // are marked as not submitted rather than dropped.
+    pub fn from_batch(count: usize, entries: Vec<api::PostSubmitBatchResponseEntry>) -> Vec<Self> {
+        let mut entries = entries.into_iter();
+
+        (0..count)
+            .map(|index| match entries.next() {
+                Some(entry) => Self {
+                    index,
+                    signature: entry.signature,
+                    submitted: entry.submitted,
+                    error: (!entry.error.is_empty()).then_some(entry.error),
+                },
+                None => Self {
+                    index,
+                    signature: String::new(),
+                    submitted: false,
+                    error: Some("Missing from submit response".to_string()),
+                },
+            })
+            .collect()
+    }
+}
+
+pub fn submitted_signatures(outcomes: &[SubmitOutcome]) -> Vec<String> {
+    outcomes
+        .iter()
+        .filter(|outcome| outcome.submitted)
+        .map(|outcome| outcome.signature.clone())
+        .collect()
+}
+
+// Fails on the first rejected transaction, for callers that need every entry to land.
+pub fn require_submitted(outcomes: Vec<SubmitOutcome>) -> Result<Vec<String>> {
+    if let Some(failed) = outcomes.iter().find(|outcome| !outcome.submitted) {
+        return Err(anyhow!(
+            "Transaction {} was not submitted: {}",
+            failed.index,
+            failed.error.as_deref().unwrap_or("unknown error")
+        ));
+    }
+
+    Ok(outcomes
+        .into_iter()
+        .map(|outcome| outcome.signature)
+        .collect())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClientCapability {
+    // Quotes, prices, streams and unsigned transaction building only.
+    ReadOnly,
+    Signing,
+}
+
+impl ClientCapability {
+    pub fn of(keypair: Option<&Keypair>) -> Self {
+        match keypair {
+            Some(_) => ClientCapability::Signing,
+            None => ClientCapability::ReadOnly,
+        }
+    }
+}
+
+// Returned by every signing method of a read-only client, before any request is made.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("No signer configured: the client is read-only, configure a keypair to sign transactions")]
+pub struct NoSignerError;
+
+pub fn require_signer(keypair: Option<&Keypair>) -> Result<&Keypair, NoSignerError> {
+    keypair.ok_or(NoSignerError)
+}
+
 pub async fn sign_transaction<T>(
     tx: &T,
     keypair: &Keypair,
@@ -66,6 +197,28 @@ where
     })
 }
 
+pub async fn sign_transactions<T>(
+    txs: Vec<T>,
+    keypair: &Keypair,
+    blockhash: &str,
+    submit_opts: &SubmitParams,
+) -> Result<Vec<SignedTransaction>>
+where
+    T: IntoTransactionMessage,
+{
+    let mut signed_txs = Vec::with_capacity(txs.len());
+
+    for tx in txs {
+        let mut tx_message = tx.into_transaction_message();
+        if submit_opts.add_memo {
+            tx_message.content = add_memo_to_serialized_tx(&tx_message.content, MEMO_MESSAGE)?;
+        }
+        signed_txs.push(sign_transaction(&tx_message, keypair, blockhash.to_string()).await?);
+    }
+
+    Ok(signed_txs)
+}
+
 fn sign_versioned_transaction(
     mut tx: VersionedTransaction,
     keypair: &Keypair,
@@ -96,3 +249,83 @@ fn sign_legacy_transaction(
     tx.try_partial_sign(&[keypair], blockhash)?;
     Ok(serialize(&tx)?)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outcomes_from_batch() {
+        let outcomes = SubmitOutcome::from_batch(
+            3,
+            vec![
+                api::PostSubmitBatchResponseEntry {
+                    signature: "first".to_string(),
+                    error: String::new(),
+                    submitted: true,
+                },
+                api::PostSubmitBatchResponseEntry {
+                    signature: "second".to_string(),
+                    error: "simulation failed".to_string(),
+                    submitted: false,
+                },
+            ],
+        );
+
+        assert_eq!(outcomes.len(), 3);
+        assert_eq!(
+            outcomes[0],
+            SubmitOutcome::submitted(0, "first".to_string())
+        );
+        assert_eq!(outcomes[1].index, 1);
+        assert_eq!(outcomes[1].error.as_deref(), Some("simulation failed"));
+        assert!(!outcomes[2].submitted);
+        assert_eq!(submitted_signatures(&outcomes), vec!["first".to_string()]);
+
+        let err = require_submitted(outcomes).unwrap_err();
+        assert!(err.to_string().contains("Transaction 1"));
+    }
+
+    #[tokio::test]
+    async fn test_single_rejection_is_an_outcome() {
+        let keypair = Keypair::new();
+        let ix = solana_sdk::system_instruction::transfer(&keypair.pubkey(), &keypair.pubkey(), 1);
+        let tx = Transaction::new_unsigned(solana_sdk::message::Message::new(
+            &[ix],
+            Some(&keypair.pubkey()),
+        ));
+        let message = api::TransactionMessage {
+            content: STANDARD.encode(serialize(&tx).unwrap()),
+            is_cleanup: false,
+        };
+        let blockhash = solana_sdk::hash::Hash::new_unique().to_string();
+        let signed = sign_transaction(&message, &keypair, blockhash)
+            .await
+            .unwrap();
+
+        let outcome = SubmitOutcome::from_single(&signed, Err(anyhow!("rejected")));
+        assert!(!outcome.submitted);
+        assert_eq!(outcome.error.as_deref(), Some("rejected"));
+        assert_eq!(outcome.signature, signed.signature().unwrap());
+        assert!(!outcome.signature.is_empty());
+
+        let outcome = SubmitOutcome::from_single(&signed, Ok("sig".to_string()));
+        assert_eq!(outcome, SubmitOutcome::submitted(0, "sig".to_string()));
+    }
+
+    #[test]
+    fn test_require_signer() {
+        assert_eq!(ClientCapability::of(None), ClientCapability::ReadOnly);
+        assert_eq!(require_signer(None).unwrap_err(), NoSignerError);
+
+        let keypair = Keypair::new();
+        assert_eq!(
+            ClientCapability::of(Some(&keypair)),
+            ClientCapability::Signing
+        );
+        assert_eq!(
+            require_signer(Some(&keypair)).unwrap().pubkey(),
+            keypair.pubkey()
+        );
+    }
+}
@@ -0,0 +1,317 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use solana_trader_proto::api;
+
+use super::amounts::SlippageBps;
+use super::quote::{best_quote, BestQuoteRequest, QuoteSource, Venue};
+use super::signing::SubmitParams;
+
+const DEFAULT_REBUILD_DELAY: Duration = Duration::from_millis(250);
+const MAX_REBUILD_DELAY: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum GuardAction {
+    Abort,
+    // Rebuilds the transaction against a fresh quote. The first quote stays the baseline, so a
+    // rebuild only proceeds once the price is back within the limit of it. Each rebuild waits
+    // `delay`, doubled after every attempt up to `MAX_REBUILD_DELAY`.
+    Rebuild { max_attempts: u32, delay: Duration },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SlippageGuard {
+    pub max_deterioration: SlippageBps,
+    // Baseline from the caller's original quote; re-quoted at the start of the flow when unset.
+    pub expected_out_amount: Option<f64>,
+    pub action: GuardAction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GuardDecision {
+    Proceed,
+    Rebuild { out_amount: f64, delay: Duration },
+}
+
+impl SlippageGuard {
+    pub fn abort(max_deterioration: SlippageBps) -> Self {
+        Self {
+            max_deterioration,
+            expected_out_amount: None,
+            action: GuardAction::Abort,
+        }
+    }
+
+    pub fn rebuild(max_deterioration: SlippageBps, max_attempts: u32) -> Self {
+        Self {
+            max_deterioration,
+            expected_out_amount: None,
+            action: GuardAction::Rebuild {
+                max_attempts,
+                delay: DEFAULT_REBUILD_DELAY,
+            },
+        }
+    }
+
+    // Sets the wait before the first rebuild; has no effect on an aborting guard.
+    pub fn with_rebuild_delay(mut self, rebuild_delay: Duration) -> Self {
+        if let GuardAction::Rebuild { delay, .. } = &mut self.action {
+            *delay = rebuild_delay;
+        }
+        self
+    }
+
+    pub fn with_expected_out_amount(mut self, expected_out_amount: f64) -> Self {
+        self.expected_out_amount = Some(expected_out_amount);
+        self
+    }
+
+    pub fn evaluate(&self, expected: f64, current: f64, attempt: u32) -> Result<GuardDecision> {
+        let deterioration = deterioration_percent(expected, current);
+        if deterioration <= self.max_deterioration.to_percent() {
+            return Ok(GuardDecision::Proceed);
+        }
+
+        match self.action {
+            GuardAction::Rebuild {
+                max_attempts,
+                delay,
+            } if attempt < max_attempts => Ok(GuardDecision::Rebuild {
+                out_amount: current,
+                delay: rebuild_delay(delay, attempt),
+            }),
+            _ => Err(anyhow!(
+                "Slippage guard tripped: expected output fell {:.4}% from {} to {} (limit {}%)",
+                deterioration,
+                expected,
+                current,
+                self.max_deterioration.to_percent()
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SlippageCheck {
+    pub guard: SlippageGuard,
+    pub quote: BestQuoteRequest,
+    pub baseline: Option<f64>,
+    pub attempt: u32,
+}
+
+impl SlippageCheck {
+    // The quote is only built when a guard is configured, so unsupported venues do not
+    // fail unguarded flows.
+    pub fn from_params<F>(submit_opts: &SubmitParams, quote: F) -> Result<Option<Self>>
+    where
+        F: FnOnce() -> Result<BestQuoteRequest>,
+    {
+        let Some(guard) = submit_opts.slippage_guard.clone() else {
+            return Ok(None);
+        };
+
+        Ok(Some(Self {
+            baseline: guard.expected_out_amount,
+            guard,
+            quote: quote()?,
+            attempt: 0,
+        }))
+    }
+
+    pub fn evaluate(&mut self, current: f64) -> Result<GuardDecision> {
+        let Some(expected) = self.baseline else {
+            self.baseline = Some(current);
+            return Ok(GuardDecision::Proceed);
+        };
+
+        let decision = self.guard.evaluate(expected, current, self.attempt)?;
+        if let GuardDecision::Rebuild { .. } = decision {
+            self.attempt += 1;
+        }
+        Ok(decision)
+    }
+}
+
+// Wraps a swap's build loop; every call is a no-op when no guard is configured:
+//
+//     guard.start(client).await?;
+//     loop {
+//         let txs = build().await?;
+//         if guard.rebuild(client).await? {
+//             continue;
+//         }
+//         return submit(txs).await;
+//     }
+#[derive(Debug, Clone)]
+pub struct SwapGuard {
+    check: Option<SlippageCheck>,
+}
+
+impl SwapGuard {
+    pub fn new<F>(submit_opts: &SubmitParams, quote: F) -> Result<Self>
+    where
+        F: FnOnce() -> Result<BestQuoteRequest>,
+    {
+        Ok(Self {
+            check: SlippageCheck::from_params(submit_opts, quote)?,
+        })
+    }
+
+    // Quotes the baseline before the first build unless the caller supplied one.
+    pub async fn start<S: QuoteSource + ?Sized>(&mut self, source: &S) -> Result<()> {
+        if let Some(check) = self.check.as_mut().filter(|c| c.baseline.is_none()) {
+            let out_amount = best_quote(source, &check.quote).await?.best.out_amount;
+            check.evaluate(out_amount)?;
+        }
+        Ok(())
+    }
+
+    // Re-quotes after a build; `true` means the transactions must be built again.
+    pub async fn rebuild<S: QuoteSource + ?Sized>(&mut self, source: &S) -> Result<bool> {
+        let Some(check) = self.check.as_mut() else {
+            return Ok(false);
+        };
+
+        let out_amount = best_quote(source, &check.quote).await?.best.out_amount;
+        match check.evaluate(out_amount)? {
+            GuardDecision::Proceed => Ok(false),
+            GuardDecision::Rebuild { delay, .. } => {
+                tokio::time::sleep(delay).await;
+                Ok(true)
+            }
+        }
+    }
+}
+
+// A delay configured above `MAX_REBUILD_DELAY` is kept as is rather than capped.
+fn rebuild_delay(delay: Duration, attempt: u32) -> Duration {
+    delay
+        .saturating_mul(2u32.saturating_pow(attempt))
+        .min(MAX_REBUILD_DELAY.max(delay))
+}
+
+pub fn deterioration_percent(expected: f64, current: f64) -> f64 {
+    if expected <= 0.0 {
+        return 0.0;
+    }
+    ((expected - current) / expected * 100.0).max(0.0)
+}
+
+pub fn guard_quote_request(
+    venue: Venue,
+    in_token: &str,
+    out_token: &str,
+    in_amount: f64,
+    slippage: f64,
+) -> Result<BestQuoteRequest> {
+    Ok(BestQuoteRequest {
+        venues: vec![venue],
+        ..BestQuoteRequest::from_proto(in_token, out_token, in_amount, slippage)?
+    })
+}
+
+pub fn trade_swap_venue(project: i32) -> Result<Venue> {
+    match api::Project::try_from(project) {
+        Ok(api::Project::PRaydium) => Ok(Venue::Raydium),
+        Ok(api::Project::PJupiter) => Ok(Venue::Jupiter),
+        _ => Err(anyhow!(
+            "Slippage guard does not support trade swaps for project {}",
+            project
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deterioration_percent() {
+        assert_eq!(deterioration_percent(100.0, 99.0), 1.0);
+        assert_eq!(deterioration_percent(100.0, 101.0), 0.0);
+        assert_eq!(deterioration_percent(0.0, 10.0), 0.0);
+    }
+
+    #[test]
+    fn test_guard_decisions() {
+        let abort = SlippageGuard::abort(SlippageBps::clamped(100));
+        assert_eq!(
+            abort.evaluate(100.0, 99.5, 0).unwrap(),
+            GuardDecision::Proceed
+        );
+        assert!(abort.evaluate(100.0, 98.0, 0).is_err());
+
+        let rebuild = SlippageGuard::rebuild(SlippageBps::clamped(100), 2)
+            .with_rebuild_delay(Duration::from_millis(100));
+        assert_eq!(
+            rebuild.evaluate(100.0, 98.0, 0).unwrap(),
+            GuardDecision::Rebuild {
+                out_amount: 98.0,
+                delay: Duration::from_millis(100)
+            }
+        );
+        assert_eq!(
+            rebuild.evaluate(100.0, 98.0, 1).unwrap(),
+            GuardDecision::Rebuild {
+                out_amount: 98.0,
+                delay: Duration::from_millis(200)
+            }
+        );
+        assert!(rebuild.evaluate(100.0, 98.0, 2).is_err());
+    }
+
+    #[test]
+    fn test_rebuild_delay_backs_off() {
+        let delay = Duration::from_secs(1);
+        assert_eq!(rebuild_delay(delay, 0), delay);
+        assert_eq!(rebuild_delay(delay, 2), Duration::from_secs(4));
+        assert_eq!(rebuild_delay(delay, 3), MAX_REBUILD_DELAY);
+        assert_eq!(rebuild_delay(delay, 64), MAX_REBUILD_DELAY);
+        assert_eq!(
+            rebuild_delay(Duration::from_secs(30), 1),
+            Duration::from_secs(30)
+        );
+    }
+
+    #[test]
+    fn test_check_keeps_first_quote_as_baseline() {
+        let submit_opts = SubmitParams {
+            slippage_guard: Some(SlippageGuard::rebuild(SlippageBps::clamped(100), 2)),
+            ..Default::default()
+        };
+        let mut check = SlippageCheck::from_params(&submit_opts, || {
+            guard_quote_request(Venue::Raydium, "SOL", "USDC", 1.0, 0.5)
+        })
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(check.evaluate(100.0).unwrap(), GuardDecision::Proceed);
+        assert!(matches!(
+            check.evaluate(95.0).unwrap(),
+            GuardDecision::Rebuild { out_amount, .. } if out_amount == 95.0
+        ));
+        // 94.5 is within 1% of the rebuilt quote but 5.5% below the first one.
+        assert!(matches!(
+            check.evaluate(94.5).unwrap(),
+            GuardDecision::Rebuild { out_amount, .. } if out_amount == 94.5
+        ));
+        assert!(check.evaluate(94.5).is_err());
+        assert_eq!(check.baseline, Some(100.0));
+
+        let mut recovered = SlippageCheck {
+            attempt: 0,
+            ..check.clone()
+        };
+        assert!(matches!(
+            recovered.evaluate(95.0).unwrap(),
+            GuardDecision::Rebuild { .. }
+        ));
+        assert_eq!(recovered.evaluate(99.5).unwrap(), GuardDecision::Proceed);
+
+        let unguarded =
+            SlippageCheck::from_params(&SubmitParams::default(), || Err(anyhow!("not built")))
+                .unwrap();
+        assert!(unguarded.is_none());
+    }
+}
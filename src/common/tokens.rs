@@ -0,0 +1,363 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use super::constants::{TOKEN_2022_PROGRAM, TOKEN_PROGRAM, USDC, USDT, WRAPPED_SOL};
+
+// Base layout shared by SPL Token and Token-2022 mints; extensions follow it.
+const MINT_LEN: usize = 82;
+const MINT_DECIMALS_OFFSET: usize = 44;
+const MINT_INITIALIZED_OFFSET: usize = 45;
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum TokenError {
+    #[error("Unknown token mint {0}")]
+    UnknownMint(String),
+
+    #[error("Invalid token amount {0:?}")]
+    InvalidAmount(String),
+
+    #[error("Token amount {amount} has more than {decimals} decimals")]
+    TooPrecise { amount: String, decimals: u8 },
+
+    #[error("Token amount {0} does not fit in a u64")]
+    Overflow(String),
+
+    #[error("Account owned by {0} is not a token program")]
+    UnsupportedProgram(Pubkey),
+
+    #[error("Invalid mint account {mint}: {reason}")]
+    InvalidMintAccount { mint: String, reason: &'static str },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenProgram {
+    Spl,
+    Token2022,
+}
+
+impl TokenProgram {
+    pub fn from_owner(owner: &Pubkey) -> Result<Self, TokenError> {
+        if *owner == TOKEN_PROGRAM {
+            Ok(TokenProgram::Spl)
+        } else if *owner == TOKEN_2022_PROGRAM {
+            Ok(TokenProgram::Token2022)
+        } else {
+            Err(TokenError::UnsupportedProgram(*owner))
+        }
+    }
+
+    pub fn id(&self) -> Pubkey {
+        match self {
+            TokenProgram::Spl => TOKEN_PROGRAM,
+            TokenProgram::Token2022 => TOKEN_2022_PROGRAM,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenInfo {
+    pub mint: String,
+    pub symbol: Option<String>,
+    pub decimals: u8,
+    pub program: TokenProgram,
+}
+
+impl TokenInfo {
+    pub fn new(mint: &str, symbol: &str, decimals: u8, program: TokenProgram) -> Self {
+        Self {
+            mint: mint.to_string(),
+            symbol: Some(symbol.to_string()),
+            decimals,
+            program,
+        }
+    }
+
+    // `owner` and `data` as returned by `getAccountInfo` for the mint.
+    pub fn from_mint_account(mint: &str, owner: &Pubkey, data: &[u8]) -> Result<Self, TokenError> {
+        let program = TokenProgram::from_owner(owner)?;
+        let invalid = |reason| TokenError::InvalidMintAccount {
+            mint: mint.to_string(),
+            reason,
+        };
+
+        if data.len() < MINT_LEN {
+            return Err(invalid("account data is too short"));
+        }
+        if data[MINT_INITIALIZED_OFFSET] != 1 {
+            return Err(invalid("mint is not initialized"));
+        }
+
+        Ok(Self {
+            mint: mint.to_string(),
+            symbol: None,
+            decimals: data[MINT_DECIMALS_OFFSET],
+            program,
+        })
+    }
+
+    pub fn format(&self, raw: u64) -> String {
+        format_ui_amount(raw, self.decimals)
+    }
+
+    pub fn parse(&self, amount: &str) -> Result<u64, TokenError> {
+        parse_ui_amount(amount, self.decimals)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TokenRegistry {
+    tokens: HashMap<String, TokenInfo>,
+}
+
+impl TokenRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Registry pre-filled with well-known mainnet tokens.
+    pub fn bundled() -> Self {
+        let mut registry = Self::new();
+        for token in [
+            TokenInfo::new(WRAPPED_SOL, "SOL", 9, TokenProgram::Spl),
+            TokenInfo::new(USDC, "USDC", 6, TokenProgram::Spl),
+            TokenInfo::new(USDT, "USDT", 6, TokenProgram::Spl),
+            TokenInfo::new(
+                "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263",
+                "BONK",
+                5,
+                TokenProgram::Spl,
+            ),
+            TokenInfo::new(
+                "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN",
+                "JUP",
+                6,
+                TokenProgram::Spl,
+            ),
+        ] {
+            registry.insert(token);
+        }
+        registry
+    }
+
+    pub fn insert(&mut self, token: TokenInfo) -> Option<TokenInfo> {
+        self.tokens.insert(token.mint.clone(), token)
+    }
+
+    // Resolves a mint from its account data, keeping any symbol already registered.
+    pub fn register_mint_account(
+        &mut self,
+        mint: &str,
+        owner: &Pubkey,
+        data: &[u8],
+    ) -> Result<&TokenInfo, TokenError> {
+        let mut token = TokenInfo::from_mint_account(mint, owner, data)?;
+        token.symbol = self.tokens.get(mint).and_then(|t| t.symbol.clone());
+        self.insert(token);
+        Ok(&self.tokens[mint])
+    }
+
+    pub fn get(&self, mint: &str) -> Option<&TokenInfo> {
+        self.tokens.get(mint)
+    }
+
+    pub fn by_symbol(&self, symbol: &str) -> Option<&TokenInfo> {
+        self.tokens.values().find(|t| {
+            t.symbol
+                .as_deref()
+                .is_some_and(|s| s.eq_ignore_ascii_case(symbol))
+        })
+    }
+
+    pub fn resolve(&self, mint: &str) -> Result<&TokenInfo, TokenError> {
+        self.get(mint)
+            .ok_or_else(|| TokenError::UnknownMint(mint.to_string()))
+    }
+
+    pub fn format(&self, mint: &str, raw: u64) -> Result<String, TokenError> {
+        Ok(self.resolve(mint)?.format(raw))
+    }
+
+    pub fn parse(&self, mint: &str, amount: &str) -> Result<u64, TokenError> {
+        self.resolve(mint)?.parse(amount)
+    }
+
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+}
+
+// Exact decimal rendering of a raw amount, without trailing zeros.
+pub fn format_ui_amount(raw: u64, decimals: u8) -> String {
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return raw.to_string();
+    }
+
+    let padded = format!("{:0>width$}", raw, width = decimals + 1);
+    let (int, frac) = padded.split_at(padded.len() - decimals);
+    let frac = frac.trim_end_matches('0');
+    if frac.is_empty() {
+        int.to_string()
+    } else {
+        format!("{}.{}", int, frac)
+    }
+}
+
+// Exact inverse of `format_ui_amount`; fails rather than rounds when the amount has more
+// decimals than the token.
+pub fn parse_ui_amount(amount: &str, decimals: u8) -> Result<u64, TokenError> {
+    let (int, frac) = split_amount(amount)?;
+    if frac.len() > decimals as usize && frac[decimals as usize..].bytes().any(|b| b != b'0') {
+        return Err(TokenError::TooPrecise {
+            amount: amount.to_string(),
+            decimals,
+        });
+    }
+    scale(amount, int, frac, decimals, false)
+}
+
+// Rounds to the nearest raw unit, for amounts that start out as floats. Goes through the
+// shortest decimal representation of `amount`, so 0.1 SOL is exactly 100_000_000 lamports.
+pub fn ui_to_raw(amount: f64, decimals: u8) -> Result<u64, TokenError> {
+    if !amount.is_finite() || amount < 0.0 {
+        return Err(TokenError::InvalidAmount(amount.to_string()));
+    }
+
+    let text = amount.to_string();
+    let (int, frac) = split_amount(&text)?;
+    let round_up = frac
+        .as_bytes()
+        .get(decimals as usize)
+        .is_some_and(|&b| b >= b'5');
+    scale(&text, int, frac, decimals, round_up)
+}
+
+// Nearest f64 to the exact amount, for display and comparisons that tolerate it.
+pub fn raw_to_ui(raw: u64, decimals: u8) -> f64 {
+    format_ui_amount(raw, decimals)
+        .parse()
+        .unwrap_or(f64::INFINITY)
+}
+
+fn split_amount(amount: &str) -> Result<(&str, &str), TokenError> {
+    let invalid = || TokenError::InvalidAmount(amount.to_string());
+    let trimmed = amount.trim();
+    let (int, frac) = trimmed.split_once('.').unwrap_or((trimmed, ""));
+
+    if int.is_empty() && frac.is_empty() {
+        return Err(invalid());
+    }
+    if !int.bytes().chain(frac.bytes()).all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    Ok((int, frac))
+}
+
+fn scale(
+    amount: &str,
+    int: &str,
+    frac: &str,
+    decimals: u8,
+    round_up: bool,
+) -> Result<u64, TokenError> {
+    let overflow = || TokenError::Overflow(amount.to_string());
+    let frac_digits = frac.bytes().chain(std::iter::repeat(b'0'));
+
+    let mut raw: u64 = 0;
+    for digit in int.bytes().chain(frac_digits.take(decimals as usize)) {
+        raw = raw
+            .checked_mul(10)
+            .and_then(|r| r.checked_add((digit - b'0') as u64))
+            .ok_or_else(overflow)?;
+    }
+    if round_up {
+        raw = raw.checked_add(1).ok_or_else(overflow)?;
+    }
+    Ok(raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_and_parse_are_exact() {
+        assert_eq!(format_ui_amount(100_000_000, 9), "0.1");
+        assert_eq!(format_ui_amount(1_500_000, 6), "1.5");
+        assert_eq!(format_ui_amount(7, 6), "0.000007");
+        assert_eq!(format_ui_amount(42, 0), "42");
+        assert_eq!(format_ui_amount(u64::MAX, 9), "18446744073.709551615");
+
+        assert_eq!(parse_ui_amount("0.1", 9), Ok(100_000_000));
+        assert_eq!(parse_ui_amount("1.50", 6), Ok(1_500_000));
+        assert_eq!(parse_ui_amount(".5", 1), Ok(5));
+        assert_eq!(parse_ui_amount("2.", 2), Ok(200));
+        assert_eq!(parse_ui_amount("18446744073.709551615", 9), Ok(u64::MAX));
+        assert!(matches!(
+            parse_ui_amount("18446744073.709551616", 9),
+            Err(TokenError::Overflow(_))
+        ));
+        assert!(matches!(
+            parse_ui_amount("0.0000001", 6),
+            Err(TokenError::TooPrecise { .. })
+        ));
+        for invalid in ["", ".", "-1", "1e3", "1.2.3"] {
+            assert!(matches!(
+                parse_ui_amount(invalid, 6),
+                Err(TokenError::InvalidAmount(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_float_conversions() {
+        assert_eq!(ui_to_raw(0.1, 9), Ok(100_000_000));
+        assert_eq!(ui_to_raw(1.1, 6), Ok(1_100_000));
+        assert_eq!(ui_to_raw(0.0000015, 6), Ok(2));
+        assert!(ui_to_raw(-1.0, 6).is_err());
+        assert!(ui_to_raw(f64::NAN, 6).is_err());
+
+        assert_eq!(raw_to_ui(100_000_000, 9), 0.1);
+        assert_eq!(raw_to_ui(1_100_000, 6), 1.1);
+    }
+
+    #[test]
+    fn test_registry_resolves_mint_accounts() {
+        let mut registry = TokenRegistry::bundled();
+        assert_eq!(registry.by_symbol("usdc").unwrap().mint, USDC);
+        assert_eq!(registry.format(WRAPPED_SOL, 1_500_000_000).unwrap(), "1.5");
+        assert_eq!(registry.parse(USDC, "0.25"), Ok(250_000));
+        assert!(matches!(
+            registry.parse("unknown", "1"),
+            Err(TokenError::UnknownMint(_))
+        ));
+
+        let mut data = vec![0u8; MINT_LEN + 10];
+        data[MINT_DECIMALS_OFFSET] = 8;
+        data[MINT_INITIALIZED_OFFSET] = 1;
+        let token = registry
+            .register_mint_account("mint", &TOKEN_2022_PROGRAM, &data)
+            .unwrap();
+        assert_eq!(token.decimals, 8);
+        assert_eq!(token.program, TokenProgram::Token2022);
+        assert_eq!(token.symbol, None);
+
+        let token = registry
+            .register_mint_account(USDC, &TOKEN_PROGRAM, &data)
+            .unwrap();
+        assert_eq!(token.symbol.as_deref(), Some("USDC"));
+
+        assert!(matches!(
+            TokenInfo::from_mint_account("mint", &Pubkey::new_unique(), &data),
+            Err(TokenError::UnsupportedProgram(_))
+        ));
+        assert!(TokenInfo::from_mint_account("mint", &TOKEN_PROGRAM, &data[..40]).is_err());
+    }
+}
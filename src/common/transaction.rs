@@ -0,0 +1,278 @@
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use solana_sdk::{
+    address_lookup_table::AddressLookupTableAccount,
+    compute_budget::ComputeBudgetInstruction,
+    hash::Hash,
+    instruction::Instruction,
+    message::{v0, Message, VersionedMessage},
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    system_instruction,
+    transaction::VersionedTransaction,
+};
+use solana_trader_proto::api;
+
+use crate::common::lookup_table::select_lookup_tables;
+use crate::common::memo::memo_instruction;
+use crate::provider::utils::{convert_instructions, ApiInstruction};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TransactionFormat {
+    Legacy,
+    V0,
+    // v0 when at least one of the provided lookup tables is worth including, legacy otherwise.
+    #[default]
+    Auto,
+}
+
+#[derive(Debug, Clone)]
+pub struct TransactionBuilder {
+    payer: Pubkey,
+    instructions: Vec<Instruction>,
+    compute_unit_limit: Option<u32>,
+    compute_unit_price: Option<u64>,
+    memo: Option<String>,
+    tip: Option<(Pubkey, u64)>,
+    lookup_tables: Vec<AddressLookupTableAccount>,
+    format: TransactionFormat,
+}
+
+impl TransactionBuilder {
+    pub fn new(payer: Pubkey) -> Self {
+        Self {
+            payer,
+            instructions: Vec::new(),
+            compute_unit_limit: None,
+            compute_unit_price: None,
+            memo: None,
+            tip: None,
+            lookup_tables: Vec::new(),
+            format: TransactionFormat::default(),
+        }
+    }
+
+    pub fn instruction(mut self, instruction: Instruction) -> Self {
+        self.instructions.push(instruction);
+        self
+    }
+
+    pub fn extend(mut self, instructions: impl IntoIterator<Item = Instruction>) -> Self {
+        self.instructions.extend(instructions);
+        self
+    }
+
+    // Accepts instructions straight from a Raydium or Jupiter swap-instructions response.
+    pub fn instructions<I: ApiInstruction>(mut self, instructions: &[I]) -> Result<Self> {
+        self.instructions
+            .extend(convert_instructions(instructions)?);
+        Ok(self)
+    }
+
+    pub fn compute_unit_limit(mut self, units: u32) -> Self {
+        self.compute_unit_limit = Some(units);
+        self
+    }
+
+    pub fn compute_unit_price(mut self, micro_lamports: u64) -> Self {
+        self.compute_unit_price = Some(micro_lamports);
+        self
+    }
+
+    pub fn memo(mut self, memo: impl Into<String>) -> Self {
+        self.memo = Some(memo.into());
+        self
+    }
+
+    pub fn tip(mut self, receiver: Pubkey, lamports: u64) -> Self {
+        self.tip = Some((receiver, lamports));
+        self
+    }
+
+    pub fn lookup_tables(
+        mut self,
+        tables: impl IntoIterator<Item = AddressLookupTableAccount>,
+    ) -> Self {
+        self.lookup_tables.extend(tables);
+        self
+    }
+
+    pub fn format(mut self, format: TransactionFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    // Compute budget instructions go first, followed by the caller's instructions, the tip
+    // transfer and finally the memo.
+    pub fn all_instructions(&self) -> Vec<Instruction> {
+        let mut instructions = Vec::with_capacity(self.instructions.len() + 4);
+
+        if let Some(units) = self.compute_unit_limit {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(units));
+        }
+        if let Some(price) = self.compute_unit_price {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_price(price));
+        }
+        instructions.extend(self.instructions.iter().cloned());
+        if let Some((receiver, lamports)) = self.tip {
+            instructions.push(system_instruction::transfer(
+                &self.payer,
+                &receiver,
+                lamports,
+            ));
+        }
+        if let Some(memo) = &self.memo {
+            instructions.push(memo_instruction(memo));
+        }
+
+        instructions
+    }
+
+    pub fn compile(&self, recent_blockhash: Hash) -> Result<VersionedMessage> {
+        let instructions = self.all_instructions();
+        if instructions.is_empty() {
+            return Err(anyhow!("Transaction has no instructions"));
+        }
+
+        let tables = match self.format {
+            TransactionFormat::Legacy => Vec::new(),
+            TransactionFormat::V0 => self.lookup_tables.clone(),
+            TransactionFormat::Auto => {
+                select_lookup_tables(&self.payer, &instructions, &self.lookup_tables)
+            }
+        };
+
+        if self.format != TransactionFormat::V0 && tables.is_empty() {
+            return Ok(VersionedMessage::Legacy(Message::new_with_blockhash(
+                &instructions,
+                Some(&self.payer),
+                &recent_blockhash,
+            )));
+        }
+
+        let message =
+            v0::Message::try_compile(&self.payer, &instructions, &tables, recent_blockhash)?;
+        Ok(VersionedMessage::V0(message))
+    }
+
+    // Unsigned transaction with empty signature slots, ready for `sign_and_submit`.
+    pub fn build(&self, recent_blockhash: &str) -> Result<api::TransactionMessage> {
+        let message = self.compile(recent_blockhash.parse()?)?;
+        let signatures =
+            vec![Signature::default(); message.header().num_required_signatures as usize];
+
+        encode(VersionedTransaction {
+            signatures,
+            message,
+        })
+    }
+
+    pub fn build_signed(
+        &self,
+        signers: &[&Keypair],
+        recent_blockhash: &str,
+    ) -> Result<api::TransactionMessage> {
+        let message = self.compile(recent_blockhash.parse()?)?;
+        encode(VersionedTransaction::try_new(message, signers)?)
+    }
+}
+
+fn encode(tx: VersionedTransaction) -> Result<api::TransactionMessage> {
+    Ok(api::TransactionMessage {
+        content: STANDARD.encode(bincode::serialize(&tx)?),
+        is_cleanup: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::constants::TRADER_API_MEMO_PROGRAM;
+    use solana_sdk::{compute_budget, instruction::AccountMeta, signer::Signer};
+
+    #[test]
+    fn test_build_sets_payer_and_order() {
+        let keypair = Keypair::new();
+        let payer = keypair.pubkey();
+        let receiver = Pubkey::new_unique();
+        let tip_wallet = Pubkey::new_unique();
+        let blockhash = Hash::new_unique();
+
+        let builder = TransactionBuilder::new(payer)
+            .instruction(system_instruction::transfer(&payer, &receiver, 10))
+            .compute_unit_limit(200_000)
+            .compute_unit_price(1_000)
+            .tip(tip_wallet, 5)
+            .memo("hello");
+
+        let instructions = builder.all_instructions();
+        assert_eq!(instructions.len(), 5);
+        assert_eq!(instructions[0].program_id, compute_budget::id());
+        assert_eq!(instructions[1].program_id, compute_budget::id());
+        assert_eq!(instructions[3].accounts[1].pubkey, tip_wallet);
+        assert_eq!(instructions[4].program_id, TRADER_API_MEMO_PROGRAM);
+
+        let message = builder.compile(blockhash).unwrap();
+        assert!(matches!(message, VersionedMessage::Legacy(_)));
+        assert_eq!(message.static_account_keys()[0], payer);
+        assert_eq!(*message.recent_blockhash(), blockhash);
+
+        let signed = builder
+            .build_signed(&[&keypair], &blockhash.to_string())
+            .unwrap();
+        let rawbytes = STANDARD.decode(signed.content).unwrap();
+        let tx: VersionedTransaction = bincode::deserialize(&rawbytes).unwrap();
+        assert!(tx.verify_with_results().iter().all(|ok| *ok));
+    }
+
+    #[test]
+    fn test_format_selection() {
+        let payer = Pubkey::new_unique();
+        let program = Pubkey::new_unique();
+        let keys: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let ix = Instruction {
+            program_id: program,
+            accounts: keys
+                .iter()
+                .map(|key| AccountMeta::new(*key, false))
+                .collect(),
+            data: vec![],
+        };
+        let table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: keys.clone(),
+        };
+
+        let auto = TransactionBuilder::new(payer)
+            .instruction(ix.clone())
+            .lookup_tables([table.clone()]);
+        assert!(matches!(
+            auto.compile(Hash::new_unique()).unwrap(),
+            VersionedMessage::V0(_)
+        ));
+
+        let legacy = auto.clone().format(TransactionFormat::Legacy);
+        assert!(matches!(
+            legacy.compile(Hash::new_unique()).unwrap(),
+            VersionedMessage::Legacy(_)
+        ));
+
+        // Without applicable tables, Auto falls back to legacy but V0 stays v0.
+        let no_tables = TransactionBuilder::new(payer).instruction(ix);
+        assert!(matches!(
+            no_tables.compile(Hash::new_unique()).unwrap(),
+            VersionedMessage::Legacy(_)
+        ));
+        assert!(matches!(
+            no_tables
+                .format(TransactionFormat::V0)
+                .compile(Hash::new_unique())
+                .unwrap(),
+            VersionedMessage::V0(_)
+        ));
+
+        assert!(TransactionBuilder::new(payer)
+            .compile(Hash::new_unique())
+            .is_err());
+    }
+}
@@ -0,0 +1,423 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{Arc, Mutex, OnceLock},
+    time::Duration,
+};
+
+use anyhow::{anyhow, Result};
+
+use crate::connections::telemetry::ErrorClass;
+
+// Upper bounds in seconds, matching the Prometheus client defaults.
+pub const LATENCY_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+pub trait MetricsRecorder: Send + Sync {
+    fn record_request(
+        &self,
+        transport: &str,
+        method: &str,
+        latency: Duration,
+        error: Option<ErrorClass>,
+    );
+
+    // Called for every update delivered to a subscription, with the number of updates
+    // waiting in its buffer afterwards. The subscription id only identifies the buffer
+    // until `record_stream_closed` is called for it.
+    fn record_stream_message(
+        &self,
+        transport: &str,
+        method: &str,
+        subscription_id: &str,
+        buffered: usize,
+        capacity: usize,
+    );
+
+    fn record_stream_closed(&self, transport: &str, method: &str, subscription_id: &str);
+
+    fn record_reconnect(&self, transport: &str, endpoint: &str);
+}
+
+static RECORDER: OnceLock<Arc<dyn MetricsRecorder>> = OnceLock::new();
+
+// Installs the process-wide recorder. It can only be set once, before or after clients
+// are created; calls made before that are not recorded.
+pub fn set_recorder(recorder: Arc<dyn MetricsRecorder>) -> Result<()> {
+    RECORDER
+        .set(recorder)
+        .map_err(|_| anyhow!("Metrics recorder is already set"))
+}
+
+pub fn recorder() -> Option<&'static Arc<dyn MetricsRecorder>> {
+    RECORDER.get()
+}
+
+pub(crate) fn record_request(
+    transport: &str,
+    method: &str,
+    latency: Duration,
+    error: Option<ErrorClass>,
+) {
+    if let Some(recorder) = recorder() {
+        recorder.record_request(transport, method, latency, error);
+    }
+}
+
+pub(crate) fn record_stream_message(
+    transport: &str,
+    method: &str,
+    subscription_id: &str,
+    buffered: usize,
+    capacity: usize,
+) {
+    if let Some(recorder) = recorder() {
+        recorder.record_stream_message(transport, method, subscription_id, buffered, capacity);
+    }
+}
+
+pub(crate) fn record_stream_closed(transport: &str, method: &str, subscription_id: &str) {
+    if let Some(recorder) = recorder() {
+        recorder.record_stream_closed(transport, method, subscription_id);
+    }
+}
+
+pub(crate) fn record_reconnect(transport: &str, endpoint: &str) {
+    if let Some(recorder) = recorder() {
+        recorder.record_reconnect(transport, endpoint);
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Histogram {
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, value: f64) {
+        for (bucket, bound) in self.buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            if value <= bound {
+                *bucket += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct BufferStats {
+    buffered: usize,
+    capacity: usize,
+}
+
+type MethodKey = (String, String);
+type StreamKey = (String, String, String);
+
+#[derive(Debug, Default)]
+struct Registry {
+    latency: BTreeMap<MethodKey, Histogram>,
+    errors: BTreeMap<(String, String, ErrorClass), u64>,
+    messages: BTreeMap<MethodKey, u64>,
+    // Only open subscriptions; rendered summed per method so ids never become labels.
+    buffers: BTreeMap<StreamKey, BufferStats>,
+    reconnects: BTreeMap<MethodKey, u64>,
+}
+
+// Keeps everything in memory and renders it in the Prometheus text exposition format,
+// e.g. for serving from a `/metrics` endpoint.
+#[derive(Debug, Default)]
+pub struct PrometheusRecorder {
+    registry: Mutex<Registry>,
+}
+
+impl PrometheusRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn render(&self) -> String {
+        let registry = self.registry.lock().unwrap_or_else(|e| e.into_inner());
+        let mut out = String::new();
+
+        header(
+            &mut out,
+            "trader_api_request_duration_seconds",
+            "histogram",
+            "Trader API request latency by transport and method.",
+        );
+        for ((transport, method), histogram) in &registry.latency {
+            let labels = labels(&[("transport", transport), ("method", method)]);
+            for (count, bound) in histogram.buckets.iter().zip(LATENCY_BUCKETS) {
+                let _ = writeln!(
+                    out,
+                    "trader_api_request_duration_seconds_bucket{{{},le=\"{}\"}} {}",
+                    labels, bound, count
+                );
+            }
+            let _ = writeln!(
+                out,
+                "trader_api_request_duration_seconds_bucket{{{},le=\"+Inf\"}} {}",
+                labels, histogram.count
+            );
+            let _ = writeln!(
+                out,
+                "trader_api_request_duration_seconds_sum{{{}}} {}",
+                labels, histogram.sum
+            );
+            let _ = writeln!(
+                out,
+                "trader_api_request_duration_seconds_count{{{}}} {}",
+                labels, histogram.count
+            );
+        }
+
+        header(
+            &mut out,
+            "trader_api_request_errors_total",
+            "counter",
+            "Failed Trader API requests by error class.",
+        );
+        for ((transport, method, class), count) in &registry.errors {
+            let labels = labels(&[
+                ("transport", transport),
+                ("method", method),
+                ("class", class.as_str()),
+            ]);
+            let _ = writeln!(
+                out,
+                "trader_api_request_errors_total{{{}}} {}",
+                labels, count
+            );
+        }
+
+        header(
+            &mut out,
+            "trader_api_stream_messages_total",
+            "counter",
+            "Updates delivered by transport and method.",
+        );
+        for ((transport, method), count) in &registry.messages {
+            let labels = labels(&[("transport", transport), ("method", method)]);
+            let _ = writeln!(
+                out,
+                "trader_api_stream_messages_total{{{}}} {}",
+                labels, count
+            );
+        }
+
+        let mut buffers = BTreeMap::<MethodKey, BufferStats>::new();
+        for ((transport, method, _), stats) in &registry.buffers {
+            let total = buffers
+                .entry((transport.clone(), method.clone()))
+                .or_default();
+            total.buffered += stats.buffered;
+            total.capacity += stats.capacity;
+        }
+
+        header(
+            &mut out,
+            "trader_api_stream_buffered_messages",
+            "gauge",
+            "Updates waiting in open subscription buffers.",
+        );
+        for ((transport, method), stats) in &buffers {
+            let labels = labels(&[("transport", transport), ("method", method)]);
+            let _ = writeln!(
+                out,
+                "trader_api_stream_buffered_messages{{{}}} {}",
+                labels, stats.buffered
+            );
+        }
+
+        header(
+            &mut out,
+            "trader_api_stream_buffer_capacity",
+            "gauge",
+            "Total size of open subscription buffers.",
+        );
+        for ((transport, method), stats) in &buffers {
+            let labels = labels(&[("transport", transport), ("method", method)]);
+            let _ = writeln!(
+                out,
+                "trader_api_stream_buffer_capacity{{{}}} {}",
+                labels, stats.capacity
+            );
+        }
+
+        header(
+            &mut out,
+            "trader_api_reconnects_total",
+            "counter",
+            "Connection and stream retries by transport and endpoint.",
+        );
+        for ((transport, endpoint), count) in &registry.reconnects {
+            let labels = labels(&[("transport", transport), ("endpoint", endpoint)]);
+            let _ = writeln!(out, "trader_api_reconnects_total{{{}}} {}", labels, count);
+        }
+
+        out
+    }
+}
+
+impl MetricsRecorder for PrometheusRecorder {
+    fn record_request(
+        &self,
+        transport: &str,
+        method: &str,
+        latency: Duration,
+        error: Option<ErrorClass>,
+    ) {
+        let mut registry = self.registry.lock().unwrap_or_else(|e| e.into_inner());
+        registry
+            .latency
+            .entry((transport.to_string(), method.to_string()))
+            .or_default()
+            .observe(latency.as_secs_f64());
+
+        if let Some(class) = error {
+            *registry
+                .errors
+                .entry((transport.to_string(), method.to_string(), class))
+                .or_default() += 1;
+        }
+    }
+
+    fn record_stream_message(
+        &self,
+        transport: &str,
+        method: &str,
+        subscription_id: &str,
+        buffered: usize,
+        capacity: usize,
+    ) {
+        let mut registry = self.registry.lock().unwrap_or_else(|e| e.into_inner());
+        *registry
+            .messages
+            .entry((transport.to_string(), method.to_string()))
+            .or_default() += 1;
+        registry.buffers.insert(
+            (
+                transport.to_string(),
+                method.to_string(),
+                subscription_id.to_string(),
+            ),
+            BufferStats { buffered, capacity },
+        );
+    }
+
+    fn record_stream_closed(&self, transport: &str, method: &str, subscription_id: &str) {
+        let mut registry = self.registry.lock().unwrap_or_else(|e| e.into_inner());
+        registry.buffers.remove(&(
+            transport.to_string(),
+            method.to_string(),
+            subscription_id.to_string(),
+        ));
+    }
+
+    fn record_reconnect(&self, transport: &str, endpoint: &str) {
+        let mut registry = self.registry.lock().unwrap_or_else(|e| e.into_inner());
+        *registry
+            .reconnects
+            .entry((transport.to_string(), endpoint.to_string()))
+            .or_default() += 1;
+    }
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+fn labels(pairs: &[(&str, &str)]) -> String {
+    pairs
+        .iter()
+        .map(|(name, value)| format!("{}=\"{}\"", name, escape(value)))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_requests() {
+        let recorder = PrometheusRecorder::new();
+        recorder.record_request("grpc", "GetRaydiumQuotes", Duration::from_millis(20), None);
+        recorder.record_request(
+            "grpc",
+            "GetRaydiumQuotes",
+            Duration::from_millis(300),
+            Some(ErrorClass::Status),
+        );
+
+        let out = recorder.render();
+        let labels = "transport=\"grpc\",method=\"GetRaydiumQuotes\"";
+        assert!(out.contains("# TYPE trader_api_request_duration_seconds histogram"));
+        assert!(out.contains(&format!(
+            "trader_api_request_duration_seconds_bucket{{{},le=\"0.025\"}} 1",
+            labels
+        )));
+        assert!(out.contains(&format!(
+            "trader_api_request_duration_seconds_bucket{{{},le=\"0.5\"}} 2",
+            labels
+        )));
+        assert!(out.contains(&format!(
+            "trader_api_request_duration_seconds_count{{{}}} 2",
+            labels
+        )));
+        assert!(out.contains(&format!(
+            "trader_api_request_errors_total{{{},class=\"status\"}} 1",
+            labels
+        )));
+    }
+
+    #[test]
+    fn test_render_streams_and_reconnects() {
+        let recorder = PrometheusRecorder::new();
+        let method = "GetPrioritizationFeesStream";
+        for (id, buffered) in [("a", 1), ("a", 3), ("b", 2)] {
+            recorder.record_stream_message("ws", method, id, buffered, 1000);
+        }
+        recorder.record_reconnect("ws", "wss://uk.example.com/ws");
+
+        let out = recorder.render();
+        let labels = "transport=\"ws\",method=\"GetPrioritizationFeesStream\"";
+        assert!(!out.contains("subscription_id"));
+        assert!(out.contains(&format!("trader_api_stream_messages_total{{{}}} 3", labels)));
+        assert!(out.contains(&format!(
+            "trader_api_stream_buffered_messages{{{}}} 5",
+            labels
+        )));
+        assert!(out.contains(&format!(
+            "trader_api_stream_buffer_capacity{{{}}} 2000",
+            labels
+        )));
+
+        recorder.record_stream_closed("ws", method, "a");
+        let out = recorder.render();
+        assert!(out.contains(&format!("trader_api_stream_messages_total{{{}}} 3", labels)));
+        assert!(out.contains(&format!(
+            "trader_api_stream_buffered_messages{{{}}} 2",
+            labels
+        )));
+        assert!(out.contains(&format!(
+            "trader_api_stream_buffer_capacity{{{}}} 1000",
+            labels
+        )));
+        assert!(out.contains(
+            "trader_api_reconnects_total{transport=\"ws\",endpoint=\"wss://uk.example.com/ws\"} 1"
+        ));
+    }
+}
@@ -1 +1,4 @@
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod telemetry;
 pub mod ws;
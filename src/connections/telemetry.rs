@@ -0,0 +1,350 @@
+use std::{
+    fmt::Display,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::Instant,
+};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use reqwest::{RequestBuilder, Response};
+use tokio_stream::Stream;
+use tonic::codegen::http;
+use tower::Service;
+use tracing::{debug, field, info_span, warn, Instrument, Span};
+
+pub const TRANSPORT_GRPC: &str = "grpc";
+pub const TRANSPORT_HTTP: &str = "http";
+pub const TRANSPORT_WS: &str = "ws";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ErrorClass {
+    // Connection, TLS or channel failures before a response arrived.
+    Transport,
+    Timeout,
+    // Non-success HTTP or gRPC status.
+    Status,
+    // Error object returned in a JSON-RPC response.
+    Rpc,
+    Decode,
+    Other,
+}
+
+impl ErrorClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorClass::Transport => "transport",
+            ErrorClass::Timeout => "timeout",
+            ErrorClass::Status => "status",
+            ErrorClass::Rpc => "rpc",
+            ErrorClass::Decode => "decode",
+            ErrorClass::Other => "other",
+        }
+    }
+
+    pub fn of(error: &anyhow::Error) -> Self {
+        if let Some(e) = error.downcast_ref::<ClassifiedError>() {
+            return e.class;
+        }
+        if let Some(e) = error.downcast_ref::<reqwest::Error>() {
+            return Self::of_reqwest(e);
+        }
+        if error.is::<tonic::Status>() {
+            return ErrorClass::Status;
+        }
+        if error.is::<tokio::time::error::Elapsed>() {
+            return ErrorClass::Timeout;
+        }
+        if error.is::<serde_json::Error>() || error.is::<prost::DecodeError>() {
+            return ErrorClass::Decode;
+        }
+        ErrorClass::Other
+    }
+
+    fn of_reqwest(error: &reqwest::Error) -> Self {
+        if error.is_timeout() {
+            ErrorClass::Timeout
+        } else if error.is_decode() {
+            ErrorClass::Decode
+        } else if error.is_status() {
+            ErrorClass::Status
+        } else {
+            ErrorClass::Transport
+        }
+    }
+}
+
+// Carries an explicit class for errors that would otherwise only be a message, while
+// displaying exactly that message.
+#[derive(Debug, thiserror::Error)]
+#[error("{message}")]
+pub struct ClassifiedError {
+    pub class: ErrorClass,
+    message: String,
+}
+
+pub fn classified(class: ErrorClass, message: impl Display) -> anyhow::Error {
+    anyhow::Error::new(ClassifiedError {
+        class,
+        message: message.to_string(),
+    })
+}
+
+// Every Trader API call runs inside one of these spans. Latency and outcome are recorded
+// once the call completes; request and subscription ids are filled in where they exist.
+pub fn request_span(transport: &'static str, method: &str, endpoint: &str) -> Span {
+    info_span!(
+        "trader_api_request",
+        transport,
+        method,
+        endpoint,
+        request_id = field::Empty,
+        subscription_id = field::Empty,
+        latency_ms = field::Empty,
+        outcome = field::Empty,
+        error_class = field::Empty,
+    )
+}
+
+// A call in flight: its span plus what the outcome is reported against.
+pub struct RequestTrace {
+    pub span: Span,
+    #[cfg_attr(not(feature = "metrics"), allow(dead_code))]
+    transport: &'static str,
+    #[cfg_attr(not(feature = "metrics"), allow(dead_code))]
+    method: String,
+    started: Instant,
+}
+
+impl RequestTrace {
+    pub fn start(transport: &'static str, method: &str, endpoint: &str) -> Self {
+        Self {
+            span: request_span(transport, method, endpoint),
+            transport,
+            method: method.to_string(),
+            started: Instant::now(),
+        }
+    }
+
+    pub fn finish(&self, error: Option<(ErrorClass, &dyn Display)>) {
+        let latency = self.started.elapsed();
+        self.span.record("latency_ms", latency.as_millis() as u64);
+        match error {
+            None => {
+                self.span.record("outcome", "ok");
+                debug!(parent: &self.span, "request completed");
+            }
+            Some((class, error)) => {
+                self.span.record("outcome", "error");
+                self.span.record("error_class", class.as_str());
+                warn!(parent: &self.span, error = %error, "request failed");
+            }
+        }
+
+        #[cfg(feature = "metrics")]
+        crate::connections::metrics::record_request(
+            self.transport,
+            &self.method,
+            latency,
+            error.map(|(class, _)| class),
+        );
+    }
+}
+
+pub async fn traced<T, F>(trace: RequestTrace, future: F) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+{
+    let result = future.instrument(trace.span.clone()).await;
+    match &result {
+        Ok(_) => trace.finish(None),
+        Err(e) => trace.finish(Some((ErrorClass::of(e), e))),
+    }
+    result
+}
+
+#[async_trait]
+pub trait TracedSend {
+    async fn send_traced(self) -> reqwest::Result<Response>;
+}
+
+#[async_trait]
+impl TracedSend for RequestBuilder {
+    async fn send_traced(self) -> reqwest::Result<Response> {
+        let (client, request) = self.build_split();
+        let request = request?;
+
+        let method = format!("{} {}", request.method(), request.url().path());
+        let endpoint = request.url().host_str().unwrap_or_default().to_string();
+        let trace = RequestTrace::start(TRANSPORT_HTTP, &method, &endpoint);
+
+        let result = client.execute(request).instrument(trace.span.clone()).await;
+        match &result {
+            Ok(response) if !response.status().is_success() => {
+                trace.finish(Some((ErrorClass::Status, &response.status())))
+            }
+            Ok(_) => trace.finish(None),
+            Err(e) => trace.finish(Some((ErrorClass::of_reqwest(e), e))),
+        }
+        result
+    }
+}
+
+// Wraps the gRPC channel so every call is traced with the RPC name taken from the request
+// path. Latency covers the time until response headers arrive.
+#[derive(Debug, Clone)]
+pub struct TracedService<S> {
+    inner: S,
+    endpoint: Arc<str>,
+}
+
+impl<S> TracedService<S> {
+    pub fn new(inner: S, endpoint: &str) -> Self {
+        Self {
+            inner,
+            endpoint: Arc::from(endpoint),
+        }
+    }
+}
+
+impl<S, ReqBody, ResBody> Service<http::Request<ReqBody>> for TracedService<S>
+where
+    S: Service<http::Request<ReqBody>, Response = http::Response<ResBody>>,
+    S::Future: Send + 'static,
+    S::Error: Display,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<ReqBody>) -> Self::Future {
+        let method = request.uri().path().rsplit('/').next().unwrap_or_default();
+        let trace = RequestTrace::start(TRANSPORT_GRPC, method, &self.endpoint);
+        let future = trace.span.in_scope(|| self.inner.call(request));
+
+        Box::pin(async move {
+            let result = future.await;
+            match &result {
+                Ok(response) => match grpc_error(response.headers()) {
+                    Some(error) => trace.finish(Some((ErrorClass::Status, &error))),
+                    None => trace.finish(None),
+                },
+                Err(e) => trace.finish(Some((ErrorClass::Transport, e))),
+            }
+            result
+        })
+    }
+}
+
+// Counts updates on a gRPC server stream like WebSocket subscriptions are counted. There
+// is no client-side buffer to report, so the buffer gauges stay at zero.
+pub struct TracedStream<S> {
+    inner: S,
+    #[cfg_attr(not(feature = "metrics"), allow(dead_code))]
+    method: &'static str,
+    #[cfg_attr(not(feature = "metrics"), allow(dead_code))]
+    id: String,
+}
+
+impl<S> TracedStream<S> {
+    pub fn new(inner: S, method: &'static str) -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+        Self {
+            inner,
+            method,
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed).to_string(),
+        }
+    }
+}
+
+impl<S: Stream + Unpin> Stream for TracedStream<S> {
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let item = Pin::new(&mut self.inner).poll_next(cx);
+
+        #[cfg(feature = "metrics")]
+        if let Poll::Ready(Some(_)) = &item {
+            crate::connections::metrics::record_stream_message(
+                TRANSPORT_GRPC,
+                self.method,
+                &self.id,
+                0,
+                0,
+            );
+        }
+
+        item
+    }
+}
+
+impl<S> Drop for TracedStream<S> {
+    fn drop(&mut self) {
+        #[cfg(feature = "metrics")]
+        crate::connections::metrics::record_stream_closed(TRANSPORT_GRPC, self.method, &self.id);
+    }
+}
+
+// Failed unary calls usually come back as trailers-only responses with the status in the
+// headers; successful calls carry it in the trailers instead.
+fn grpc_error(headers: &http::HeaderMap) -> Option<String> {
+    let status = headers.get("grpc-status")?.to_str().ok()?;
+    if status == "0" {
+        return None;
+    }
+
+    let message = headers
+        .get("grpc-message")
+        .and_then(|m| m.to_str().ok())
+        .unwrap_or_default();
+    Some(format!("grpc-status {}: {}", status, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grpc_error_from_headers() {
+        let mut headers = http::HeaderMap::new();
+        assert_eq!(grpc_error(&headers), None);
+
+        headers.insert("grpc-status", http::HeaderValue::from_static("0"));
+        assert_eq!(grpc_error(&headers), None);
+
+        headers.insert("grpc-status", http::HeaderValue::from_static("16"));
+        headers.insert(
+            "grpc-message",
+            http::HeaderValue::from_static("unauthenticated"),
+        );
+        assert_eq!(
+            grpc_error(&headers).as_deref(),
+            Some("grpc-status 16: unauthenticated")
+        );
+    }
+
+    #[test]
+    fn test_error_class() {
+        let timeout = classified(ErrorClass::Timeout, "Response timeout");
+        assert_eq!(ErrorClass::of(&timeout), ErrorClass::Timeout);
+        assert_eq!(timeout.to_string(), "Response timeout");
+
+        let decode = anyhow::Error::new(serde_json::from_str::<u64>("x").unwrap_err());
+        assert_eq!(ErrorClass::of(&decode), ErrorClass::Decode);
+
+        let status = anyhow::Error::new(tonic::Status::unavailable("down"));
+        assert_eq!(ErrorClass::of(&status), ErrorClass::Status);
+
+        assert_eq!(ErrorClass::of(&anyhow::anyhow!("boom")), ErrorClass::Other);
+    }
+}
@@ -21,9 +21,13 @@ use tokio_tungstenite::tungstenite::handshake::client::Request;
 use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
 use tokio_tungstenite::{connect_async_tls_with_config, Connector};
 use tokio_tungstenite::{tungstenite::protocol::Message, WebSocketStream};
+use tracing::{debug, trace, warn, Instrument};
 use url::Url;
 
-use crate::common::{get_base_url_from_env, ws_endpoint, BaseConfig};
+use crate::common::{get_base_url_from_env, ws_endpoint};
+use crate::connections::telemetry::{
+    classified, request_span, traced, ErrorClass, RequestTrace, TRANSPORT_WS,
+};
 use crate::provider::utils::convert_string_enums;
 
 const CONNECTION_RETRY_TIMEOUT: Duration = Duration::from_secs(15);
@@ -33,6 +37,8 @@ const PING_INTERVAL: Duration = Duration::from_secs(30);
 
 #[derive(Debug)]
 pub struct Subscription {
+    #[cfg_attr(not(feature = "metrics"), allow(dead_code))]
+    method: String,
     sender: mpsc::Sender<Value>,
 }
 
@@ -46,6 +52,7 @@ struct RequestTracker {
 }
 
 pub struct WS {
+    endpoint: String,
     stream: Arc<Mutex<WebSocketStream<tokio_tungstenite::MaybeTlsStream<TcpStream>>>>,
     write_tx: Sender<Message>,
     shutdown_tx: broadcast::Sender<()>,
@@ -55,25 +62,26 @@ pub struct WS {
 }
 
 impl WS {
-    pub async fn new(endpoint: Option<String>) -> Result<Self> {
-        let base = BaseConfig::try_from_env()?;
+    // Takes the auth header from the caller, which has already loaded its configuration.
+    pub async fn new(endpoint: Option<String>, auth_header: &str) -> Result<Self> {
         let (base_url, secure) = get_base_url_from_env();
         let endpoint = endpoint.unwrap_or_else(|| ws_endpoint(&base_url, secure));
 
-        if base.auth_header.is_empty() {
+        if auth_header.is_empty() {
             return Err(anyhow::anyhow!("AUTH_HEADER is empty"));
         }
 
         let url =
             Url::parse(&endpoint).map_err(|e| anyhow::anyhow!("Invalid WebSocket URL: {}", e))?;
 
-        let stream = Self::connect(&url, &base.auth_header).await?;
+        let stream = Self::connect(&url, auth_header).await?;
         let stream = Arc::new(Mutex::new(stream));
 
         let (write_tx, write_rx) = mpsc::channel(100);
         let (shutdown_tx, _) = broadcast::channel(1);
 
         let ws = Self {
+            endpoint,
             stream: stream.clone(),
             write_tx,
             shutdown_tx,
@@ -106,10 +114,16 @@ impl WS {
             .await
             {
                 Ok((stream, _)) => {
-                    println!("Connected to: {}", url);
+                    debug!(endpoint = %url, "WebSocket connected");
                     return Ok(stream);
                 }
                 Err(e) => {
+                    trace!(
+                        endpoint = %url,
+                        attempt = retry_count,
+                        error = %e,
+                        "WebSocket connect failed"
+                    );
                     if retry_count >= max_retries {
                         return Err(anyhow::anyhow!(
                             "WebSocket connection failed after {} retries: {}",
@@ -118,6 +132,8 @@ impl WS {
                         ));
                     }
                     retry_count += 1;
+                    #[cfg(feature = "metrics")]
+                    crate::connections::metrics::record_reconnect(TRANSPORT_WS, url.as_str());
                     tokio::time::sleep(CONNECTION_RETRY_INTERVAL).await;
                 }
             }
@@ -182,6 +198,16 @@ impl WS {
         T: DeserializeOwned,
     {
         let request_id = self.request_id.fetch_add(1, Ordering::SeqCst);
+        let trace = RequestTrace::start(TRANSPORT_WS, method, &self.endpoint);
+        trace.span.record("request_id", request_id);
+
+        traced(trace, self.send_request(request_id, method, params)).await
+    }
+
+    async fn send_request<T>(&self, request_id: u64, method: &str, params: Value) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
         let request_json = json!({
             "jsonrpc": "2.0",
             "id": request_id,
@@ -198,29 +224,39 @@ impl WS {
         let msg = Message::Text(request_json.to_string());
         timeout(Duration::from_secs(5), self.write_tx.send(msg))
             .await
-            .map_err(|_| anyhow::anyhow!("Request send timeout"))?
-            .map_err(|e| anyhow::anyhow!("Failed to send request: {}", e))?;
+            .map_err(|_| classified(ErrorClass::Timeout, "Request send timeout"))?
+            .map_err(|e| {
+                classified(
+                    ErrorClass::Transport,
+                    format!("Failed to send request: {}", e),
+                )
+            })?;
 
         let response = timeout(Duration::from_secs(10), rx.recv())
             .await
-            .map_err(|_| anyhow::anyhow!("Response timeout"))?
-            .ok_or_else(|| anyhow::anyhow!("Channel closed unexpectedly"))?;
+            .map_err(|_| classified(ErrorClass::Timeout, "Response timeout"))?
+            .ok_or_else(|| classified(ErrorClass::Transport, "Channel closed unexpectedly"))?;
 
-        let json_response: Value = serde_json::from_str(&response.response)
-            .map_err(|e| anyhow::anyhow!("Failed to parse response: {}", e))?;
+        let json_response: Value = serde_json::from_str(&response.response).map_err(|e| {
+            classified(
+                ErrorClass::Decode,
+                format!("Failed to parse response: {}", e),
+            )
+        })?;
 
         if let Some(error) = json_response.get("error") {
-            return Err(anyhow::anyhow!("RPC error: {}", error));
+            return Err(classified(ErrorClass::Rpc, format!("RPC error: {}", error)));
         }
 
         let result = json_response
             .get("result")
-            .ok_or_else(|| anyhow::anyhow!("Missing result field in response"))?;
+            .ok_or_else(|| classified(ErrorClass::Decode, "Missing result field in response"))?;
 
         let mut res = result.clone();
         convert_string_enums(&mut res);
 
-        serde_json::from_value(res).map_err(|e| anyhow::anyhow!("Failed to parse result: {}", e))
+        serde_json::from_value(res)
+            .map_err(|e| classified(ErrorClass::Decode, format!("Failed to parse result: {}", e)))
     }
 
     pub async fn stream_proto<Req, Resp>(
@@ -236,11 +272,23 @@ impl WS {
 
         let params = serde_json::to_value(request)?;
         let params_array = json!([method, params]);
-        let subscription_id: String = self.request("subscribe", params_array).await?;
+        let span = request_span(TRANSPORT_WS, method, &self.endpoint);
+        let subscription_id: String = self
+            .request("subscribe", params_array)
+            .instrument(span.clone())
+            .await?;
+        span.record("subscription_id", subscription_id.as_str());
+        debug!(parent: &span, "subscription started");
 
         {
             let mut subs = self.subscriptions.lock().await;
-            subs.insert(subscription_id, Subscription { sender: tx });
+            subs.insert(
+                subscription_id,
+                Subscription {
+                    method: method.to_string(),
+                    sender: tx,
+                },
+            );
         }
 
         Ok(ReceiverStream::new(rx).map(|value: Value| {
@@ -269,12 +317,12 @@ impl WS {
 
         let mut stream = self.stream.lock().await;
         if let Err(e) = stream.close(None).await {
-            eprintln!("Error during WebSocket close: {}", e);
+            warn!(endpoint = %self.endpoint, error = %e, "Error during WebSocket close");
         }
         drop(stream);
 
         tokio::time::sleep(Duration::from_millis(100)).await;
-        println!("WebSocket shutdown complete");
+        debug!(endpoint = %self.endpoint, "WebSocket shutdown complete");
         Ok(())
     }
 }
@@ -286,7 +334,7 @@ async fn write_loop(
     while let Some(msg) = write_rx.recv().await {
         let mut stream = stream.lock().await;
         if let Err(e) = stream.send(msg).await {
-            eprintln!("Write error: {}", e);
+            warn!(error = %e, "WebSocket write failed");
             break;
         }
     }
@@ -325,7 +373,7 @@ async fn ping_loop(
             _ = interval.tick() => {
                 let mut stream = stream.lock().await;
                 if let Err(e) = stream.send(Message::Ping(vec![])).await {
-                    eprintln!("Ping error: {}", e);
+                    warn!(error = %e, "WebSocket ping failed");
                     break;
                 }
             }
@@ -371,7 +419,26 @@ async fn handle_subscription(
         return;
     };
 
-    if let Some(sub) = subscriptions.lock().await.get(id) {
-        let _ = sub.sender.send(result.clone()).await;
+    let mut subscriptions = subscriptions.lock().await;
+    match subscriptions.get(id) {
+        Some(sub) => {
+            // The receiver is gone once the caller drops the stream.
+            if sub.sender.send(result.clone()).await.is_err() {
+                #[cfg(feature = "metrics")]
+                crate::connections::metrics::record_stream_closed(TRANSPORT_WS, &sub.method, id);
+                subscriptions.remove(id);
+                return;
+            }
+
+            #[cfg(feature = "metrics")]
+            crate::connections::metrics::record_stream_message(
+                TRANSPORT_WS,
+                &sub.method,
+                id,
+                sub.sender.max_capacity() - sub.sender.capacity(),
+                sub.sender.max_capacity(),
+            );
+        }
+        None => trace!(subscription_id = id, "Update for unknown subscription"),
     }
 }
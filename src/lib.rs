@@ -1,3 +1,4 @@
 pub mod common;
 pub mod connections;
 pub mod provider;
+pub mod strategy;
@@ -0,0 +1,489 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use solana_sdk::signature::Keypair;
+use solana_trader_proto::api;
+use tokio::sync::{mpsc, Mutex};
+use tokio::time::timeout;
+
+use crate::common::signing::{sign_transactions, SignedTransaction, SubmitParams};
+use crate::connections::telemetry::TracedSend;
+
+use super::grpc::GrpcClient;
+use super::http::HTTPClient;
+use super::utils::IntoTransactionMessage;
+use super::ws::WebSocketClient;
+
+const BROADCAST_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub enum BroadcastEndpoint {
+    Grpc(GrpcClient),
+    Http(HTTPClient),
+    Ws(WebSocketClient),
+    SolanaRpc(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct BroadcastOutcome {
+    pub target: String,
+    pub signature: Option<String>,
+    pub error: Option<String>,
+    pub latency: Duration,
+}
+
+impl BroadcastOutcome {
+    pub fn accepted(&self) -> bool {
+        self.signature.is_some()
+    }
+}
+
+// Returned as soon as one target accepts, or once every target has answered. Targets that
+// were still pending keep running in the background; `wait_all` collects their outcomes.
+#[derive(Debug)]
+pub struct BroadcastReport {
+    pub first_accepted: Option<BroadcastOutcome>,
+    // Outcomes received so far, in completion order.
+    pub outcomes: Vec<BroadcastOutcome>,
+    pending: mpsc::Receiver<BroadcastOutcome>,
+}
+
+impl BroadcastReport {
+    // Waits for the remaining targets; each is bounded by the broadcast timeout.
+    pub async fn wait_all(&mut self) -> &[BroadcastOutcome] {
+        while let Some(outcome) = self.pending.recv().await {
+            self.outcomes.push(outcome);
+        }
+        &self.outcomes
+    }
+}
+
+// What `broadcast` sends to; split out from `BroadcastEndpoint` so it can be faked in tests.
+#[async_trait]
+trait BroadcastTarget: Send {
+    async fn block_hash(&mut self) -> Result<String>;
+
+    async fn submit(
+        &mut self,
+        signed_tx: &SignedTransaction,
+        submit_opts: &SubmitParams,
+    ) -> Result<String>;
+}
+
+struct EndpointTarget {
+    endpoint: BroadcastEndpoint,
+    rpc_client: reqwest::Client,
+}
+
+#[async_trait]
+impl BroadcastTarget for EndpointTarget {
+    async fn block_hash(&mut self) -> Result<String> {
+        let request = api::GetRecentBlockHashRequestV2 { offset: 0 };
+        match &mut self.endpoint {
+            BroadcastEndpoint::Grpc(client) => client
+                .get_recent_block_hash_v2(request)
+                .await
+                .map(|r| r.block_hash),
+            BroadcastEndpoint::Http(client) => client
+                .get_recent_block_hash_v2(&request)
+                .await
+                .map(|r| r.block_hash),
+            BroadcastEndpoint::Ws(client) => client
+                .get_recent_block_hash_v2(&request)
+                .await
+                .map(|r| r.block_hash),
+            BroadcastEndpoint::SolanaRpc(url) => get_rpc_block_hash(&self.rpc_client, url).await,
+        }
+    }
+
+    async fn submit(
+        &mut self,
+        signed_tx: &SignedTransaction,
+        submit_opts: &SubmitParams,
+    ) -> Result<String> {
+        match &mut self.endpoint {
+            BroadcastEndpoint::Grpc(client) => {
+                client
+                    .submit_signed_transaction(signed_tx, submit_opts)
+                    .await
+            }
+            BroadcastEndpoint::Http(client) => {
+                client
+                    .submit_signed_transaction(signed_tx, submit_opts)
+                    .await
+            }
+            BroadcastEndpoint::Ws(client) => {
+                client
+                    .submit_signed_transaction(signed_tx, submit_opts)
+                    .await
+            }
+            BroadcastEndpoint::SolanaRpc(url) => {
+                send_rpc_transaction(&self.rpc_client, url, signed_tx, submit_opts).await
+            }
+        }
+    }
+}
+
+type Target = (String, Arc<Mutex<dyn BroadcastTarget>>);
+
+pub struct Broadcaster {
+    targets: Vec<Target>,
+    rpc_client: reqwest::Client,
+    timeout: Duration,
+}
+
+impl Default for Broadcaster {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Broadcaster {
+    pub fn new() -> Self {
+        Self {
+            targets: Vec::new(),
+            rpc_client: reqwest::Client::new(),
+            timeout: BROADCAST_TIMEOUT,
+        }
+    }
+
+    // Targets only relay transactions signed by the caller, so they are built read-only.
+    pub fn http_regions(base_urls: &[&str]) -> Result<Self> {
+        let mut broadcaster = Self::new();
+        for base_url in base_urls {
+            let client = HTTPClient::new_read_only(Some(base_url.to_string()))?;
+            broadcaster = broadcaster.with_target(*base_url, BroadcastEndpoint::Http(client));
+        }
+        Ok(broadcaster)
+    }
+
+    pub fn with_target(self, name: impl Into<String>, endpoint: BroadcastEndpoint) -> Self {
+        let rpc_client = self.rpc_client.clone();
+        self.with_broadcast_target(
+            name,
+            EndpointTarget {
+                endpoint,
+                rpc_client,
+            },
+        )
+    }
+
+    fn with_broadcast_target(
+        mut self,
+        name: impl Into<String>,
+        target: impl BroadcastTarget + 'static,
+    ) -> Self {
+        self.targets
+            .push((name.into(), Arc::new(Mutex::new(target))));
+        self
+    }
+
+    pub fn with_solana_rpc(self, rpc_url: impl Into<String>) -> Self {
+        let rpc_url = rpc_url.into();
+        self.with_target(rpc_url.clone(), BroadcastEndpoint::SolanaRpc(rpc_url))
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub async fn sign_and_broadcast<T: IntoTransactionMessage + Clone>(
+        &self,
+        tx: &T,
+        keypair: &Keypair,
+        submit_opts: SubmitParams,
+    ) -> Result<BroadcastReport> {
+        let block_hash = self.get_block_hash().await?;
+        // Signed like every other submit path, so options such as `add_memo` apply here too.
+        let signed_tx = sign_transactions(vec![tx.clone()], keypair, &block_hash, &submit_opts)
+            .await?
+            .pop()
+            .ok_or_else(|| anyhow!("No transaction was signed"))?;
+
+        self.broadcast(&signed_tx, submit_opts).await
+    }
+
+    pub async fn broadcast(
+        &self,
+        signed_tx: &SignedTransaction,
+        submit_opts: SubmitParams,
+    ) -> Result<BroadcastReport> {
+        if self.targets.is_empty() {
+            return Err(anyhow!("No broadcast targets configured"));
+        }
+
+        let target_count = self.targets.len();
+        let (tx, mut rx) = mpsc::channel(target_count);
+
+        for (name, endpoint) in &self.targets {
+            let tx = tx.clone();
+            let name = name.clone();
+            let endpoint = endpoint.clone();
+            let signed_tx = signed_tx.clone();
+            let submit_opts = submit_opts.clone();
+            let deadline = self.timeout;
+
+            tokio::spawn(async move {
+                let started = Instant::now();
+                let result = timeout(deadline, async {
+                    endpoint.lock().await.submit(&signed_tx, &submit_opts).await
+                })
+                .await
+                .unwrap_or_else(|_| Err(anyhow!("Broadcast timed out after {:?}", deadline)));
+
+                let (signature, error) = match result {
+                    Ok(signature) => (Some(signature), None),
+                    Err(e) => (None, Some(e.to_string())),
+                };
+
+                let _ = tx
+                    .send(BroadcastOutcome {
+                        target: name,
+                        signature,
+                        error,
+                        latency: started.elapsed(),
+                    })
+                    .await;
+            });
+        }
+        drop(tx);
+
+        let mut outcomes = Vec::with_capacity(target_count);
+        let mut first_accepted = None;
+        while let Some(outcome) = rx.recv().await {
+            let accepted = outcome.accepted();
+            outcomes.push(outcome);
+            if accepted {
+                first_accepted = outcomes.last().cloned();
+                break;
+            }
+        }
+
+        Ok(BroadcastReport {
+            first_accepted,
+            outcomes,
+            pending: rx,
+        })
+    }
+
+    async fn get_block_hash(&self) -> Result<String> {
+        let mut last_err = None;
+
+        for (name, endpoint) in &self.targets {
+            let result = endpoint.lock().await.block_hash().await;
+            match result {
+                Ok(block_hash) => return Ok(block_hash),
+                Err(e) => last_err = Some(e.context(format!("target {}", name))),
+            }
+        }
+
+        Err(last_err.unwrap_or_else(|| anyhow!("No broadcast targets configured")))
+    }
+}
+
+async fn send_rpc_transaction(
+    client: &reqwest::Client,
+    url: &str,
+    signed_tx: &SignedTransaction,
+    submit_opts: &SubmitParams,
+) -> Result<String> {
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "sendTransaction",
+        "params": [
+            signed_tx.content,
+            {
+                "encoding": "base64",
+                "skipPreflight": submit_opts.skip_pre_flight
+            }
+        ]
+    });
+
+    let result = rpc_call(client, url, &request).await?;
+    result
+        .as_str()
+        .map(String::from)
+        .ok_or_else(|| anyhow!("Missing signature in RPC response"))
+}
+
+async fn get_rpc_block_hash(client: &reqwest::Client, url: &str) -> Result<String> {
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "getLatestBlockhash",
+        "params": [{ "commitment": "finalized" }]
+    });
+
+    let result = rpc_call(client, url, &request).await?;
+    result["value"]["blockhash"]
+        .as_str()
+        .map(String::from)
+        .ok_or_else(|| anyhow!("Missing blockhash in RPC response"))
+}
+
+async fn rpc_call(client: &reqwest::Client, url: &str, request: &Value) -> Result<Value> {
+    let response: Value = client
+        .post(url)
+        .json(request)
+        .send_traced()
+        .await
+        .map_err(|e| anyhow!("RPC request failed: {}", e))?
+        .json()
+        .await
+        .map_err(|e| anyhow!("Failed to parse RPC response: {}", e))?;
+
+    if let Some(error) = response.get("error") {
+        return Err(anyhow!("RPC error: {}", error));
+    }
+
+    response
+        .get("result")
+        .cloned()
+        .ok_or_else(|| anyhow!("Missing result field in RPC response"))
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use solana_sdk::signer::Signer;
+    use solana_sdk::transaction::Transaction;
+
+    use super::*;
+    use crate::common::memo::MEMO_MESSAGE;
+
+    // Answers after `delay`; a `None` signature rejects the transaction.
+    struct FakeTarget {
+        delay: Duration,
+        signature: Option<&'static str>,
+        received: Arc<std::sync::Mutex<Vec<SignedTransaction>>>,
+    }
+
+    impl FakeTarget {
+        fn new(delay_ms: u64, signature: Option<&'static str>) -> Self {
+            Self {
+                delay: Duration::from_millis(delay_ms),
+                signature,
+                received: Arc::default(),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl BroadcastTarget for FakeTarget {
+        async fn block_hash(&mut self) -> Result<String> {
+            Ok(solana_sdk::hash::Hash::new_unique().to_string())
+        }
+
+        async fn submit(
+            &mut self,
+            signed_tx: &SignedTransaction,
+            _: &SubmitParams,
+        ) -> Result<String> {
+            self.received.lock().unwrap().push(signed_tx.clone());
+            tokio::time::sleep(self.delay).await;
+            self.signature
+                .map(String::from)
+                .ok_or_else(|| anyhow!("rejected"))
+        }
+    }
+
+    fn signed_tx() -> SignedTransaction {
+        SignedTransaction {
+            content: String::new(),
+            is_cleanup: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_first_acceptance_returns_before_slow_targets() {
+        let broadcaster = Broadcaster::new()
+            .with_broadcast_target("rejects", FakeTarget::new(0, None))
+            .with_broadcast_target("fast", FakeTarget::new(10, Some("fast-sig")))
+            .with_broadcast_target("slow", FakeTarget::new(200, Some("slow-sig")))
+            .with_broadcast_target("stuck", FakeTarget::new(10_000, Some("stuck-sig")))
+            .with_timeout(Duration::from_millis(500));
+
+        let mut report = broadcaster
+            .broadcast(&signed_tx(), SubmitParams::default())
+            .await
+            .unwrap();
+        let first = report.first_accepted.clone().unwrap();
+        assert_eq!(first.target, "fast");
+        assert_eq!(first.signature.as_deref(), Some("fast-sig"));
+        assert_eq!(
+            report
+                .outcomes
+                .iter()
+                .map(|o| o.target.as_str())
+                .collect::<Vec<_>>(),
+            vec!["rejects", "fast"]
+        );
+
+        let outcomes = report.wait_all().await;
+        assert_eq!(outcomes.len(), 4);
+        let slow = outcomes.iter().find(|o| o.target == "slow").unwrap();
+        assert_eq!(slow.signature.as_deref(), Some("slow-sig"));
+        let stuck = outcomes.iter().find(|o| o.target == "stuck").unwrap();
+        assert!(!stuck.accepted());
+        assert!(stuck.error.as_deref().unwrap().contains("timed out"));
+    }
+
+    #[tokio::test]
+    async fn test_no_acceptance_waits_for_every_target() {
+        let broadcaster = Broadcaster::new()
+            .with_broadcast_target("a", FakeTarget::new(0, None))
+            .with_broadcast_target("b", FakeTarget::new(20, None));
+
+        let mut report = broadcaster
+            .broadcast(&signed_tx(), SubmitParams::default())
+            .await
+            .unwrap();
+        assert!(report.first_accepted.is_none());
+        assert_eq!(report.outcomes.len(), 2);
+        assert_eq!(report.wait_all().await.len(), 2);
+
+        assert!(Broadcaster::new()
+            .broadcast(&signed_tx(), SubmitParams::default())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sign_and_broadcast_applies_submit_options() {
+        let keypair = Keypair::new();
+        let ix = solana_sdk::system_instruction::transfer(&keypair.pubkey(), &keypair.pubkey(), 1);
+        let tx = Transaction::new_unsigned(solana_sdk::message::Message::new(
+            &[ix],
+            Some(&keypair.pubkey()),
+        ));
+        let message = api::TransactionMessage {
+            content: STANDARD.encode(bincode::serialize(&tx).unwrap()),
+            is_cleanup: false,
+        };
+
+        let target = FakeTarget::new(0, Some("sig"));
+        let received = target.received.clone();
+        let broadcaster = Broadcaster::new().with_broadcast_target("fake", target);
+        let submit_opts = SubmitParams {
+            add_memo: true,
+            ..Default::default()
+        };
+        let report = broadcaster
+            .sign_and_broadcast(&message, &keypair, submit_opts)
+            .await
+            .unwrap();
+        assert!(report.first_accepted.is_some());
+
+        let received = received.lock().unwrap();
+        let signed: Transaction =
+            bincode::deserialize(&STANDARD.decode(&received[0].content).unwrap()).unwrap();
+        assert_eq!(signed.message.instructions.len(), 2);
+        let memo = &signed.message.instructions[1];
+        assert_eq!(memo.data, MEMO_MESSAGE.as_bytes());
+        assert!(signed.is_signed());
+    }
+}
@@ -0,0 +1,599 @@
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use futures_util::future::{join_all, BoxFuture};
+use futures_util::stream::BoxStream;
+use futures_util::StreamExt;
+use solana_trader_proto::api;
+use tokio::sync::{mpsc, Mutex, RwLock};
+use tokio::time::timeout;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
+
+use crate::common::constants::{MAINNET_NY, MAINNET_PUMP_NY, MAINNET_PUMP_UK, MAINNET_UK};
+use crate::connections::telemetry::{TRANSPORT_GRPC, TRANSPORT_HTTP, TRANSPORT_WS};
+
+use super::grpc::GrpcClient;
+use super::http::HTTPClient;
+use super::ws::WebSocketClient;
+
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+const STREAM_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+// A healthy stream only moves to a region that is faster by this much, and not before it has
+// stayed on its current region for `STREAM_MIN_DWELL`.
+const STREAM_SWITCH_MARGIN: Duration = Duration::from_millis(20);
+const STREAM_MIN_DWELL: Duration = Duration::from_secs(30);
+const STREAM_BUFFER: usize = 1000;
+const MAX_CONSECUTIVE_FAILURES: u32 = 3;
+
+#[async_trait]
+pub trait RegionalClient: Send + Sized + 'static {
+    const TRANSPORT: &'static str;
+
+    async fn connect(base_url: String) -> Result<Self>;
+    async fn health_check(&mut self) -> Result<()>;
+}
+
+#[async_trait]
+impl RegionalClient for GrpcClient {
+    const TRANSPORT: &'static str = TRANSPORT_GRPC;
+
+    async fn connect(base_url: String) -> Result<Self> {
+        GrpcClient::new(Some(base_url)).await
+    }
+
+    async fn health_check(&mut self) -> Result<()> {
+        self.get_rate_limit(&api::GetRateLimitRequest {})
+            .await
+            .map(|_| ())
+    }
+}
+
+#[async_trait]
+impl RegionalClient for HTTPClient {
+    const TRANSPORT: &'static str = TRANSPORT_HTTP;
+
+    async fn connect(base_url: String) -> Result<Self> {
+        HTTPClient::new(Some(base_url))
+    }
+
+    async fn health_check(&mut self) -> Result<()> {
+        self.get_rate_limit().await.map(|_| ())
+    }
+}
+
+#[async_trait]
+impl RegionalClient for WebSocketClient {
+    const TRANSPORT: &'static str = TRANSPORT_WS;
+
+    async fn connect(base_url: String) -> Result<Self> {
+        WebSocketClient::new(Some(base_url)).await
+    }
+
+    async fn health_check(&mut self) -> Result<()> {
+        self.get_rate_limit(api::GetRateLimitRequest {})
+            .await
+            .map(|_| ())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RegionHealth {
+    pub connected: bool,
+    pub latency: Option<Duration>,
+    pub consecutive_failures: u32,
+    pub last_checked: Option<Instant>,
+}
+
+impl RegionHealth {
+    pub fn is_healthy(&self) -> bool {
+        self.connected && self.consecutive_failures < MAX_CONSECUTIVE_FAILURES
+    }
+}
+
+struct Region<C> {
+    base_url: String,
+    client: Mutex<Option<C>>,
+    health: RwLock<RegionHealth>,
+}
+
+impl<C: RegionalClient> Region<C> {
+    async fn connect(base_url: String) -> Self {
+        let client = timeout(HEALTH_CHECK_TIMEOUT, C::connect(base_url.clone()))
+            .await
+            .ok()
+            .and_then(|res| res.ok());
+
+        let health = RegionHealth {
+            connected: client.is_some(),
+            last_checked: Some(Instant::now()),
+            ..Default::default()
+        };
+
+        Self {
+            base_url,
+            client: Mutex::new(client),
+            health: RwLock::new(health),
+        }
+    }
+
+    async fn check(&self) {
+        let mut client = self.client.lock().await;
+
+        if client.is_none() {
+            match timeout(HEALTH_CHECK_TIMEOUT, C::connect(self.base_url.clone())).await {
+                Ok(Ok(c)) => {
+                    #[cfg(feature = "metrics")]
+                    crate::connections::metrics::record_reconnect(C::TRANSPORT, &self.base_url);
+                    *client = Some(c);
+                }
+                _ => {
+                    self.mark_degraded().await;
+                    return;
+                }
+            }
+        }
+
+        let Some(client) = client.as_mut() else {
+            return;
+        };
+
+        let started = Instant::now();
+        match timeout(HEALTH_CHECK_TIMEOUT, client.health_check()).await {
+            Ok(Ok(())) => self.record_success(started.elapsed()).await,
+            _ => self.mark_degraded().await,
+        }
+    }
+
+    async fn record_success(&self, latency: Duration) {
+        let mut health = self.health.write().await;
+        health.connected = true;
+        health.latency = Some(latency);
+        health.consecutive_failures = 0;
+        health.last_checked = Some(Instant::now());
+    }
+
+    // Call latency depends on the call, so only health checks update `latency`.
+    async fn record_call_success(&self) {
+        let mut health = self.health.write().await;
+        health.connected = true;
+        health.consecutive_failures = 0;
+    }
+
+    async fn record_failure(&self) {
+        let mut health = self.health.write().await;
+        health.consecutive_failures = health.consecutive_failures.saturating_add(1);
+        health.last_checked = Some(Instant::now());
+    }
+
+    // A dropped stream or failed health check takes the region out of rotation
+    // until the next successful health check.
+    async fn mark_degraded(&self) {
+        let mut health = self.health.write().await;
+        health.consecutive_failures = health.consecutive_failures.max(MAX_CONSECUTIVE_FAILURES);
+        health.last_checked = Some(Instant::now());
+    }
+
+    async fn is_healthy(&self) -> bool {
+        self.health.read().await.is_healthy()
+    }
+}
+
+pub struct FailoverClient<C: RegionalClient> {
+    regions: Arc<Vec<Region<C>>>,
+}
+
+impl FailoverClient<GrpcClient> {
+    pub async fn mainnet() -> Result<Self> {
+        Self::new(vec![MAINNET_NY.to_string(), MAINNET_UK.to_string()]).await
+    }
+
+    pub async fn mainnet_pump() -> Result<Self> {
+        Self::new(vec![
+            MAINNET_PUMP_NY.to_string(),
+            MAINNET_PUMP_UK.to_string(),
+        ])
+        .await
+    }
+}
+
+impl<C: RegionalClient> FailoverClient<C> {
+    pub async fn new(base_urls: Vec<String>) -> Result<Self> {
+        Self::with_health_check_interval(base_urls, HEALTH_CHECK_INTERVAL).await
+    }
+
+    pub async fn with_health_check_interval(
+        base_urls: Vec<String>,
+        interval: Duration,
+    ) -> Result<Self> {
+        if base_urls.is_empty() {
+            return Err(anyhow!("At least one regional endpoint is required"));
+        }
+
+        let regions = join_all(base_urls.into_iter().map(Region::<C>::connect)).await;
+
+        let mut connected = false;
+        for region in &regions {
+            connected |= region.is_healthy().await;
+        }
+        if !connected {
+            return Err(anyhow!("Failed to connect to any regional endpoint"));
+        }
+
+        let regions = Arc::new(regions);
+        tokio::spawn(health_loop(Arc::downgrade(&regions), interval));
+
+        Ok(Self { regions })
+    }
+
+    pub async fn check_health(&self) {
+        join_all(self.regions.iter().map(|region| region.check())).await;
+    }
+
+    pub async fn health(&self) -> Vec<(String, RegionHealth)> {
+        let mut result = Vec::with_capacity(self.regions.len());
+        for region in self.regions.iter() {
+            result.push((region.base_url.clone(), region.health.read().await.clone()));
+        }
+        result
+    }
+
+    pub async fn active_region(&self) -> Option<String> {
+        let order = ranked(&self.regions).await;
+        order.first().map(|&idx| self.regions[idx].base_url.clone())
+    }
+
+    pub async fn execute<T, F>(&self, mut call: F) -> Result<T>
+    where
+        F: for<'c> FnMut(&'c mut C) -> BoxFuture<'c, Result<T>>,
+    {
+        let mut last_err = None;
+
+        for idx in ranked(&self.regions).await {
+            let region = &self.regions[idx];
+            let mut client = region.client.lock().await;
+            let Some(client) = client.as_mut() else {
+                continue;
+            };
+
+            match call(client).await {
+                Ok(value) => {
+                    region.record_call_success().await;
+                    return Ok(value);
+                }
+                Err(e) => {
+                    region.record_failure().await;
+                    last_err = Some(e.context(format!("region {}", region.base_url)));
+                }
+            }
+        }
+
+        Err(last_err.unwrap_or_else(|| anyhow!("No regional endpoint available")))
+    }
+
+    pub async fn stream<T, F>(&self, mut open: F) -> Result<impl Stream<Item = Result<T>> + Unpin>
+    where
+        T: Send + 'static,
+        F: for<'c> FnMut(&'c mut C) -> BoxFuture<'c, Result<BoxStream<'static, Result<T>>>>
+            + Send
+            + 'static,
+    {
+        let regions = self.regions.clone();
+        let (mut idx, mut inner) = open_stream(&regions, &mut open).await?;
+        let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+
+        tokio::spawn(async move {
+            let mut check = tokio::time::interval(STREAM_CHECK_INTERVAL);
+            let mut opened_at = Instant::now();
+
+            loop {
+                let reopen = tokio::select! {
+                    item = inner.next() => match item {
+                        Some(Ok(value)) => {
+                            if tx.send(Ok(value)).await.is_err() {
+                                return;
+                            }
+                            false
+                        }
+                        Some(Err(_)) | None => {
+                            regions[idx].mark_degraded().await;
+                            true
+                        }
+                    },
+                    _ = check.tick() => {
+                        let order = ranked(&regions).await;
+                        match order.first() {
+                            Some(&best) if best != idx => {
+                                let current = regions[idx].health.read().await.clone();
+                                let candidate = regions[best].health.read().await.clone();
+                                should_switch(&current, &candidate, opened_at.elapsed())
+                            }
+                            _ => false,
+                        }
+                    }
+                };
+
+                if !reopen {
+                    continue;
+                }
+
+                match open_stream(&regions, &mut open).await {
+                    Ok((next_idx, next_stream)) => {
+                        #[cfg(feature = "metrics")]
+                        crate::connections::metrics::record_reconnect(
+                            C::TRANSPORT,
+                            &regions[next_idx].base_url,
+                        );
+                        idx = next_idx;
+                        inner = next_stream;
+                        opened_at = Instant::now();
+                    }
+                    Err(e) => {
+                        let _ = tx.send(Err(e)).await;
+                        return;
+                    }
+                }
+            }
+        });
+
+        Ok(ReceiverStream::new(rx))
+    }
+}
+
+async fn open_stream<C, T, F>(
+    regions: &[Region<C>],
+    open: &mut F,
+) -> Result<(usize, BoxStream<'static, Result<T>>)>
+where
+    C: RegionalClient,
+    F: for<'c> FnMut(&'c mut C) -> BoxFuture<'c, Result<BoxStream<'static, Result<T>>>>,
+{
+    let mut last_err = None;
+
+    for idx in ranked(regions).await {
+        let region = &regions[idx];
+        let mut client = region.client.lock().await;
+        let Some(client) = client.as_mut() else {
+            continue;
+        };
+
+        match open(client).await {
+            Ok(stream) => return Ok((idx, stream)),
+            Err(e) => {
+                region.mark_degraded().await;
+                last_err = Some(e.context(format!("region {}", region.base_url)));
+            }
+        }
+    }
+
+    Err(last_err.unwrap_or_else(|| anyhow!("No regional endpoint available for stream")))
+}
+
+async fn health_loop<C: RegionalClient>(regions: Weak<Vec<Region<C>>>, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.tick().await;
+
+    loop {
+        ticker.tick().await;
+        let Some(regions) = regions.upgrade() else {
+            return;
+        };
+        join_all(regions.iter().map(|region| region.check())).await;
+    }
+}
+
+async fn ranked<C>(regions: &[Region<C>]) -> Vec<usize> {
+    let mut health = Vec::with_capacity(regions.len());
+    for region in regions {
+        health.push(region.health.read().await.clone());
+    }
+    rank(&health)
+}
+
+fn rank(health: &[RegionHealth]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..health.len()).collect();
+    order.sort_by_key(|&i| {
+        (
+            !health[i].is_healthy(),
+            health[i].latency.unwrap_or(Duration::MAX),
+            i,
+        )
+    });
+    order
+}
+
+// Leaves an unhealthy region right away; a healthy one only for a clearly faster region after
+// the minimum dwell, so streams don't flap between regions with similar latency.
+fn should_switch(current: &RegionHealth, candidate: &RegionHealth, dwell: Duration) -> bool {
+    if !candidate.is_healthy() {
+        return false;
+    }
+    if !current.is_healthy() {
+        return true;
+    }
+    if dwell < STREAM_MIN_DWELL {
+        return false;
+    }
+
+    match (current.latency, candidate.latency) {
+        (Some(current), Some(candidate)) => candidate + STREAM_SWITCH_MARGIN < current,
+        (None, Some(_)) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(connected: bool, latency_ms: Option<u64>, failures: u32) -> RegionHealth {
+        RegionHealth {
+            connected,
+            latency: latency_ms.map(Duration::from_millis),
+            consecutive_failures: failures,
+            last_checked: None,
+        }
+    }
+
+    #[test]
+    fn test_rank_prefers_healthy_low_latency() {
+        let health = vec![
+            region(true, Some(80), 0),
+            region(true, Some(20), MAX_CONSECUTIVE_FAILURES),
+            region(true, Some(40), 1),
+            region(false, None, 0),
+            region(true, None, 0),
+        ];
+
+        assert_eq!(rank(&health), vec![2, 0, 4, 1, 3]);
+    }
+
+    // Stub endpoint whose URL is `name/failures`: calls fail `failures` times before they
+    // succeed, and while failures are left its streams drop after the first item.
+    struct StubClient {
+        name: String,
+        failures: u32,
+    }
+
+    impl StubClient {
+        fn call(&mut self) -> Result<String> {
+            if self.failures > 0 {
+                self.failures -= 1;
+                return Err(anyhow!("{} unavailable", self.name));
+            }
+            Ok(self.name.clone())
+        }
+
+        fn open(&mut self) -> BoxStream<'static, Result<String>> {
+            let first = futures_util::stream::iter([Ok(format!("{}-1", self.name))]);
+            if self.failures > 0 {
+                return first.boxed();
+            }
+            let rest = futures_util::stream::iter([Ok(format!("{}-2", self.name))]);
+            first
+                .chain(rest)
+                .chain(futures_util::stream::pending())
+                .boxed()
+        }
+    }
+
+    #[async_trait]
+    impl RegionalClient for StubClient {
+        const TRANSPORT: &'static str = "stub";
+
+        async fn connect(base_url: String) -> Result<Self> {
+            let (name, failures) = base_url
+                .split_once('/')
+                .ok_or_else(|| anyhow!("bad stub url {}", base_url))?;
+            Ok(Self {
+                name: name.to_string(),
+                failures: failures.parse()?,
+            })
+        }
+
+        async fn health_check(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    async fn failures(client: &FailoverClient<StubClient>, region: usize) -> u32 {
+        client.health().await[region].1.consecutive_failures
+    }
+
+    #[tokio::test]
+    async fn test_execute_retries_until_the_region_recovers() {
+        let client = FailoverClient::<StubClient>::new(vec!["ny/2".to_string()])
+            .await
+            .unwrap();
+
+        for attempt in 1..=2 {
+            let err = client
+                .execute(|c| Box::pin(async move { c.call() }))
+                .await
+                .unwrap_err();
+            assert!(format!("{:#}", err).contains("region ny/2"));
+            assert_eq!(failures(&client, 0).await, attempt);
+        }
+
+        let name = client
+            .execute(|c| Box::pin(async move { c.call() }))
+            .await
+            .unwrap();
+        assert_eq!(name, "ny");
+        assert_eq!(failures(&client, 0).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_execute_switches_away_from_a_failing_region() {
+        let client =
+            FailoverClient::<StubClient>::new(vec!["ny/3".to_string(), "uk/0".to_string()])
+                .await
+                .unwrap();
+
+        for _ in 0..MAX_CONSECUTIVE_FAILURES {
+            let name = client
+                .execute(|c| Box::pin(async move { c.call() }))
+                .await
+                .unwrap();
+            assert_eq!(name, "uk");
+        }
+        assert_eq!(client.active_region().await.as_deref(), Some("uk/0"));
+
+        // ny would succeed now, but it is out of rotation until a health check passes.
+        let name = client
+            .execute(|c| Box::pin(async move { c.call() }))
+            .await
+            .unwrap();
+        assert_eq!(name, "uk");
+        assert_eq!(failures(&client, 0).await, MAX_CONSECUTIVE_FAILURES);
+
+        client.check_health().await;
+        assert_eq!(failures(&client, 0).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_stream_reopens_on_another_region_when_it_drops() {
+        let client =
+            FailoverClient::<StubClient>::new(vec!["ny/1".to_string(), "uk/0".to_string()])
+                .await
+                .unwrap();
+
+        let stream = client
+            .stream(|c| Box::pin(async move { Ok(c.open()) }))
+            .await
+            .unwrap();
+        let items: Vec<String> =
+            timeout(Duration::from_secs(5), stream.take(3).collect::<Vec<_>>())
+                .await
+                .unwrap()
+                .into_iter()
+                .collect::<Result<_>>()
+                .unwrap();
+
+        assert_eq!(items, vec!["ny-1", "uk-1", "uk-2"]);
+        assert!(!client.health().await[0].1.is_healthy());
+        assert_eq!(client.active_region().await.as_deref(), Some("uk/0"));
+    }
+
+    #[test]
+    fn test_stream_switch_needs_margin_and_dwell() {
+        let current = region(true, Some(50), 0);
+        let slightly_faster = region(true, Some(45), 0);
+        let much_faster = region(true, Some(10), 0);
+
+        assert!(!should_switch(&current, &slightly_faster, STREAM_MIN_DWELL));
+        assert!(!should_switch(
+            &current,
+            &much_faster,
+            Duration::from_secs(1)
+        ));
+        assert!(should_switch(&current, &much_faster, STREAM_MIN_DWELL));
+
+        let unhealthy = region(true, Some(50), MAX_CONSECUTIVE_FAILURES);
+        assert!(should_switch(&unhealthy, &slightly_faster, Duration::ZERO));
+        assert!(!should_switch(&current, &unhealthy, STREAM_MIN_DWELL));
+    }
+}
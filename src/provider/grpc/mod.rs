@@ -1,4 +1,7 @@
 pub mod general;
+pub mod openbook;
+pub mod phoenix;
+pub mod pools;
 pub mod quote;
 pub mod stream;
 pub mod swap;
@@ -15,9 +18,15 @@ use tonic::{
     metadata::MetadataValue, service::interceptor::InterceptedService, transport::Channel,
 };
 
-use crate::common::signing::{sign_transaction, SubmitParams};
+use crate::common::bundle::{Bundle, BundleResult};
+use crate::common::signing::{
+    require_signer, sign_transactions, ClientCapability, SignedTransaction, SubmitOutcome,
+    SubmitParams,
+};
 use crate::common::{get_base_url_from_env, grpc_endpoint, BaseConfig};
+use crate::connections::telemetry::TracedService;
 use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
 use solana_trader_proto::api::{
     GetRecentBlockHashRequestV2, PostSubmitRequest, TransactionMessage,
 };
@@ -59,20 +68,45 @@ impl Interceptor for AuthInterceptor {
     }
 }
 
+type ApiClient =
+    api::api_client::ApiClient<TracedService<InterceptedService<Channel, AuthInterceptor>>>;
+
 #[derive(Debug)]
 pub struct GrpcClient {
-    client: api::api_client::ApiClient<InterceptedService<Channel, AuthInterceptor>>,
+    client: ApiClient,
     keypair: Option<Keypair>,
     pub public_key: Option<Pubkey>,
 }
 
 impl GrpcClient {
     pub fn get_keypair(&self) -> Result<&Keypair> {
-        Ok(self.keypair.as_ref().unwrap())
+        Ok(require_signer(self.keypair.as_ref())?)
+    }
+
+    pub fn require_signer(&self) -> Result<()> {
+        self.get_keypair().map(|_| ())
+    }
+
+    pub fn capability(&self) -> ClientCapability {
+        ClientCapability::of(self.keypair.as_ref())
+    }
+
+    pub fn with_keypair(mut self, keypair: Keypair) -> Self {
+        self.public_key = Some(keypair.pubkey());
+        self.keypair = Some(keypair);
+        self
     }
 
     pub async fn new(endpoint: Option<String>) -> Result<Self> {
-        let base = BaseConfig::try_from_env()?;
+        Self::from_config(endpoint, BaseConfig::try_from_env_async().await?).await
+    }
+
+    // Connects without loading any keypair; signing methods return `NoSignerError`.
+    pub async fn new_read_only(endpoint: Option<String>) -> Result<Self> {
+        Self::from_config(endpoint, BaseConfig::read_only_from_env()?).await
+    }
+
+    async fn from_config(endpoint: Option<String>, base: BaseConfig) -> Result<Self> {
         let (default_base_url, secure) = get_base_url_from_env();
         let final_base_url = endpoint.unwrap_or(default_base_url);
         let endpoint = grpc_endpoint(&final_base_url, secure);
@@ -92,7 +126,8 @@ impl GrpcClient {
             .map_err(|e| anyhow::anyhow!("Connection error: {}", e))?;
 
         let interceptor = AuthInterceptor::new(base.auth_header, true);
-        let client = api::api_client::ApiClient::with_interceptor(channel, interceptor);
+        let service = InterceptedService::new(channel, interceptor);
+        let client = api::api_client::ApiClient::new(TracedService::new(service, &endpoint));
 
         Ok(Self {
             client,
@@ -101,61 +136,97 @@ impl GrpcClient {
         })
     }
 
+    pub async fn submit_signed_transaction(
+        &mut self,
+        signed_tx: &SignedTransaction,
+        submit_opts: &SubmitParams,
+    ) -> Result<String> {
+        let req = PostSubmitRequest {
+            transaction: Some(TransactionMessage {
+                content: signed_tx.content.clone(),
+                is_cleanup: signed_tx.is_cleanup,
+            }),
+            skip_pre_flight: submit_opts.skip_pre_flight,
+            front_running_protection: Some(submit_opts.front_running_protection),
+            use_staked_rp_cs: Some(submit_opts.use_staked_rpcs),
+            fast_best_effort: Some(submit_opts.fast_best_effort),
+            tip: None,
+            allow_back_run: submit_opts.allow_back_run,
+            revenue_address: submit_opts.revenue_address.clone(),
+        };
+
+        let signature = self
+            .client
+            .post_submit_v2(req)
+            .await?
+            .into_inner()
+            .signature;
+
+        Ok(signature)
+    }
+
     pub async fn sign_and_submit<T: IntoTransactionMessage + Clone>(
         &mut self,
         txs: Vec<T>,
         submit_opts: SubmitParams,
         use_bundle: bool,
-    ) -> Result<Vec<String>> {
-        let block_hash = self
-            .client
-            .get_recent_block_hash_v2(GetRecentBlockHashRequestV2 { offset: 0 })
-            .await?
-            .into_inner()
-            .block_hash;
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
 
+        let block_hash = self.latest_block_hash().await?;
         let keypair = self.get_keypair()?;
 
-        if txs.len() == 1 {
-            let signed_tx = sign_transaction(&txs[0], keypair, block_hash).await?;
+        let mut signed_txs = sign_transactions(txs, keypair, &block_hash, &submit_opts).await?;
 
-            let req = PostSubmitRequest {
-                transaction: Some(TransactionMessage {
-                    content: signed_tx.content,
-                    is_cleanup: signed_tx.is_cleanup,
-                }),
-                skip_pre_flight: submit_opts.skip_pre_flight,
-                front_running_protection: Some(submit_opts.front_running_protection),
-                use_staked_rp_cs: Some(submit_opts.use_staked_rpcs),
-                fast_best_effort: Some(submit_opts.fast_best_effort),
-                tip: None,
-                allow_back_run: submit_opts.allow_back_run,
-                revenue_address: submit_opts.revenue_address,
-            };
-
-            let signature = self
-                .client
-                .post_submit_v2(req)
-                .await?
-                .into_inner()
-                .signature;
-
-            return Ok(vec![signature]);
+        if signed_txs.len() == 1 {
+            let signed_tx = signed_txs.remove(0);
+            let result = self
+                .submit_signed_transaction(&signed_tx, &submit_opts)
+                .await;
+
+            return Ok(vec![SubmitOutcome::from_single(&signed_tx, result)]);
         }
 
-        let mut entries = Vec::with_capacity(txs.len());
-        for tx in txs {
-            let signed_tx = sign_transaction(&tx, keypair, block_hash.clone()).await?;
+        let count = signed_txs.len();
+        let response = self
+            .submit_signed_batch(signed_txs, &submit_opts, use_bundle)
+            .await?;
+
+        Ok(SubmitOutcome::from_batch(count, response))
+    }
+
+    pub async fn submit_bundle(
+        &mut self,
+        bundle: &Bundle,
+        submit_opts: SubmitParams,
+    ) -> Result<BundleResult> {
+        self.require_signer()?;
 
-            let entry = api::PostSubmitRequestEntry {
+        let block_hash = self.latest_block_hash().await?;
+        let signed_txs = bundle.sign(self.get_keypair()?, &block_hash, &submit_opts).await?;
+
+        let response = self
+            .submit_signed_batch(signed_txs, &submit_opts, true)
+            .await?;
+        Ok(bundle.result(response))
+    }
+
+    async fn submit_signed_batch(
+        &mut self,
+        signed_txs: Vec<SignedTransaction>,
+        submit_opts: &SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<api::PostSubmitBatchResponseEntry>> {
+        let entries = signed_txs
+            .into_iter()
+            .map(|signed_tx| api::PostSubmitRequestEntry {
                 transaction: Some(TransactionMessage {
                     content: signed_tx.content,
                     is_cleanup: signed_tx.is_cleanup,
                 }),
                 skip_pre_flight: submit_opts.skip_pre_flight,
-            };
-            entries.push(entry);
-        }
+            })
+            .collect();
 
         let batch_request = api::PostSubmitBatchRequest {
             entries,
@@ -170,13 +241,15 @@ impl GrpcClient {
             .await?
             .into_inner();
 
-        let signatures = response
-            .transactions
-            .into_iter()
-            .filter(|entry| entry.submitted)
-            .map(|entry| entry.signature)
-            .collect();
+        Ok(response.transactions)
+    }
 
-        Ok(signatures)
+    async fn latest_block_hash(&mut self) -> Result<String> {
+        Ok(self
+            .client
+            .get_recent_block_hash_v2(GetRecentBlockHashRequestV2 { offset: 0 })
+            .await?
+            .into_inner()
+            .block_hash)
     }
 }
@@ -0,0 +1,177 @@
+use anyhow::Result;
+use solana_trader_proto::api;
+use tonic::Request;
+
+use crate::common::{
+    clob::{order_transaction, require_order_id, require_transactions, ProjectRequest},
+    signing::{SubmitOutcome, SubmitParams},
+};
+
+use super::GrpcClient;
+
+impl GrpcClient {
+    pub async fn post_order(
+        &mut self,
+        request: &api::PostOrderRequestV2,
+    ) -> Result<api::PostOrderResponse> {
+        let response = self
+            .client
+            .post_order_v2(Request::new(request.clone()))
+            .await
+            .map_err(|e| anyhow::anyhow!("PostOrderV2 error: {}", e))?;
+
+        Ok(response.into_inner())
+    }
+
+    pub async fn submit_order(
+        &mut self,
+        request: api::PostOrderRequestV2,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_order(&request).await?;
+        let txs = order_transaction("PostOrderV2", response)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    pub async fn post_cancel_order(
+        &mut self,
+        request: &api::PostCancelOrderRequestV2,
+    ) -> Result<api::PostCancelOrderResponseV2> {
+        require_order_id(request)?;
+
+        let response = self
+            .client
+            .post_cancel_order_v2(Request::new(request.clone()))
+            .await
+            .map_err(|e| anyhow::anyhow!("PostCancelOrderV2 error: {}", e))?;
+
+        Ok(response.into_inner())
+    }
+
+    pub async fn submit_cancel_order(
+        &mut self,
+        request: api::PostCancelOrderRequestV2,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_cancel_order(&request).await?;
+        let txs = require_transactions("PostCancelOrderV2", response.transactions)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    // Cancels every open order the owner has on the market; there is no v2 variant.
+    pub async fn post_cancel_all_orders(
+        &mut self,
+        request: &api::PostCancelAllRequest,
+    ) -> Result<api::PostCancelAllResponse> {
+        let request = request.for_project(api::Project::POpenbook);
+        let response = self
+            .client
+            .post_cancel_all(Request::new(request))
+            .await
+            .map_err(|e| anyhow::anyhow!("PostCancelAll error: {}", e))?;
+
+        Ok(response.into_inner())
+    }
+
+    pub async fn submit_cancel_all_orders(
+        &mut self,
+        request: api::PostCancelAllRequest,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_cancel_all_orders(&request).await?;
+        let txs = require_transactions("PostCancelAll", response.transactions)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    pub async fn post_replace_order(
+        &mut self,
+        request: &api::PostReplaceOrderRequestV2,
+    ) -> Result<api::PostOrderResponse> {
+        let response = self
+            .client
+            .post_replace_order_v2(Request::new(request.clone()))
+            .await
+            .map_err(|e| anyhow::anyhow!("PostReplaceOrderV2 error: {}", e))?;
+
+        Ok(response.into_inner())
+    }
+
+    pub async fn submit_replace_order(
+        &mut self,
+        request: api::PostReplaceOrderRequestV2,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_replace_order(&request).await?;
+        let txs = order_transaction("PostReplaceOrderV2", response)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    pub async fn post_settle(
+        &mut self,
+        request: &api::PostSettleRequestV2,
+    ) -> Result<api::PostSettleResponse> {
+        let response = self
+            .client
+            .post_settle_v2(Request::new(request.clone()))
+            .await
+            .map_err(|e| anyhow::anyhow!("PostSettleV2 error: {}", e))?;
+
+        Ok(response.into_inner())
+    }
+
+    pub async fn submit_settle(
+        &mut self,
+        request: api::PostSettleRequestV2,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_settle(&request).await?;
+        let txs = require_transactions("PostSettleV2", response.transactions)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    pub async fn get_open_orders(
+        &mut self,
+        request: &api::GetOpenOrdersRequestV2,
+    ) -> Result<api::GetOpenOrdersResponseV2> {
+        let response = self
+            .client
+            .get_open_orders_v2(Request::new(request.clone()))
+            .await
+            .map_err(|e| anyhow::anyhow!("GetOpenOrdersV2 error: {}", e))?;
+
+        Ok(response.into_inner())
+    }
+
+    pub async fn get_unsettled(
+        &mut self,
+        request: &api::GetUnsettledRequestV2,
+    ) -> Result<api::GetUnsettledResponse> {
+        let response = self
+            .client
+            .get_unsettled_v2(Request::new(request.clone()))
+            .await
+            .map_err(|e| anyhow::anyhow!("GetUnsettledV2 error: {}", e))?;
+
+        Ok(response.into_inner())
+    }
+}
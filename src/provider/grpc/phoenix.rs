@@ -0,0 +1,182 @@
+use anyhow::Result;
+use solana_trader_proto::api;
+use tonic::Request;
+
+use crate::common::{
+    clob::{order_transaction, require_transactions, ProjectRequest},
+    signing::{SubmitOutcome, SubmitParams},
+};
+
+use super::GrpcClient;
+
+// Phoenix is only served by the v1 trade endpoints; every request is sent with the
+// Phoenix project set.
+impl GrpcClient {
+    pub async fn post_phoenix_order(
+        &mut self,
+        request: &api::PostOrderRequest,
+    ) -> Result<api::PostOrderResponse> {
+        let request = request.for_project(api::Project::PPhoenix);
+        let response = self
+            .client
+            .post_order(Request::new(request))
+            .await
+            .map_err(|e| anyhow::anyhow!("PostOrder error: {}", e))?;
+
+        Ok(response.into_inner())
+    }
+
+    pub async fn submit_phoenix_order(
+        &mut self,
+        request: api::PostOrderRequest,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_phoenix_order(&request).await?;
+        let txs = order_transaction("PostOrder", response)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    pub async fn post_phoenix_cancel_order(
+        &mut self,
+        request: &api::PostCancelOrderRequest,
+    ) -> Result<api::PostCancelOrderResponse> {
+        let request = request.for_project(api::Project::PPhoenix);
+        let response = self
+            .client
+            .post_cancel_order(Request::new(request))
+            .await
+            .map_err(|e| anyhow::anyhow!("PostCancelOrder error: {}", e))?;
+
+        Ok(response.into_inner())
+    }
+
+    pub async fn submit_phoenix_cancel_order(
+        &mut self,
+        request: api::PostCancelOrderRequest,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_phoenix_cancel_order(&request).await?;
+        let txs = require_transactions("PostCancelOrder", response.transactions)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    pub async fn post_phoenix_cancel_all_orders(
+        &mut self,
+        request: &api::PostCancelAllRequest,
+    ) -> Result<api::PostCancelAllResponse> {
+        let request = request.for_project(api::Project::PPhoenix);
+        let response = self
+            .client
+            .post_cancel_all(Request::new(request))
+            .await
+            .map_err(|e| anyhow::anyhow!("PostCancelAll error: {}", e))?;
+
+        Ok(response.into_inner())
+    }
+
+    pub async fn submit_phoenix_cancel_all_orders(
+        &mut self,
+        request: api::PostCancelAllRequest,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_phoenix_cancel_all_orders(&request).await?;
+        let txs = require_transactions("PostCancelAll", response.transactions)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    pub async fn post_phoenix_replace_order(
+        &mut self,
+        request: &api::PostReplaceOrderRequest,
+    ) -> Result<api::PostOrderResponse> {
+        let request = request.for_project(api::Project::PPhoenix);
+        let response = self
+            .client
+            .post_replace_order(Request::new(request))
+            .await
+            .map_err(|e| anyhow::anyhow!("PostReplaceOrder error: {}", e))?;
+
+        Ok(response.into_inner())
+    }
+
+    pub async fn submit_phoenix_replace_order(
+        &mut self,
+        request: api::PostReplaceOrderRequest,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_phoenix_replace_order(&request).await?;
+        let txs = order_transaction("PostReplaceOrder", response)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    pub async fn post_phoenix_settle(
+        &mut self,
+        request: &api::PostSettleRequest,
+    ) -> Result<api::PostSettleResponse> {
+        let request = request.for_project(api::Project::PPhoenix);
+        let response = self
+            .client
+            .post_settle(Request::new(request))
+            .await
+            .map_err(|e| anyhow::anyhow!("PostSettle error: {}", e))?;
+
+        Ok(response.into_inner())
+    }
+
+    pub async fn submit_phoenix_settle(
+        &mut self,
+        request: api::PostSettleRequest,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_phoenix_settle(&request).await?;
+        let txs = require_transactions("PostSettle", response.transactions)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    pub async fn get_phoenix_open_orders(
+        &mut self,
+        request: &api::GetOpenOrdersRequest,
+    ) -> Result<api::GetOpenOrdersResponse> {
+        let request = request.for_project(api::Project::PPhoenix);
+        let response = self
+            .client
+            .get_open_orders(Request::new(request))
+            .await
+            .map_err(|e| anyhow::anyhow!("GetOpenOrders error: {}", e))?;
+
+        Ok(response.into_inner())
+    }
+
+    pub async fn get_phoenix_unsettled(
+        &mut self,
+        request: &api::GetUnsettledRequest,
+    ) -> Result<api::GetUnsettledResponse> {
+        let request = request.for_project(api::Project::PPhoenix);
+        let response = self
+            .client
+            .get_unsettled(Request::new(request))
+            .await
+            .map_err(|e| anyhow::anyhow!("GetUnsettled error: {}", e))?;
+
+        Ok(response.into_inner())
+    }
+}
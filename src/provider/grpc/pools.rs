@@ -0,0 +1,87 @@
+use anyhow::Result;
+use solana_trader_proto::api;
+use tonic::Request;
+
+use crate::common::pools::{PoolCache, PoolKind};
+
+use super::GrpcClient;
+
+impl GrpcClient {
+    pub async fn get_markets(
+        &mut self,
+        request: &api::GetMarketsRequestV2,
+    ) -> Result<api::GetMarketsResponseV2> {
+        let response = self
+            .client
+            .get_markets_v2(Request::new(request.clone()))
+            .await
+            .map_err(|e| anyhow::anyhow!("GetMarketsV2 error: {}", e))?;
+
+        Ok(response.into_inner())
+    }
+
+    pub async fn get_raydium_pools(
+        &mut self,
+        request: &api::GetRaydiumPoolsRequest,
+    ) -> Result<api::GetRaydiumPoolsResponse> {
+        let response = self
+            .client
+            .get_raydium_pools(Request::new(request.clone()))
+            .await
+            .map_err(|e| anyhow::anyhow!("GetRaydiumPools error: {}", e))?;
+
+        Ok(response.into_inner())
+    }
+
+    pub async fn get_raydium_cpmm_pools(
+        &mut self,
+        request: &api::GetRaydiumCpmmPoolsRequest,
+    ) -> Result<api::GetRaydiumCpmmPoolsResponse> {
+        let response = self
+            .client
+            .get_raydium_cpmm_pools(Request::new(request.clone()))
+            .await
+            .map_err(|e| anyhow::anyhow!("GetRaydiumCPMMPools error: {}", e))?;
+
+        Ok(response.into_inner())
+    }
+
+    pub async fn get_raydium_clmm_pools(
+        &mut self,
+        request: &api::GetRaydiumClmmPoolsRequest,
+    ) -> Result<api::GetRaydiumClmmPoolsResponse> {
+        let response = self
+            .client
+            .get_raydium_clmm_pools(Request::new(request.clone()))
+            .await
+            .map_err(|e| anyhow::anyhow!("GetRaydiumCLMMPools error: {}", e))?;
+
+        Ok(response.into_inner())
+    }
+
+    pub async fn get_raydium_pool_reserve(
+        &mut self,
+        request: &api::GetRaydiumPoolReserveRequest,
+    ) -> Result<api::GetRaydiumPoolReserveResponse> {
+        let response = self
+            .client
+            .get_raydium_pool_reserve(Request::new(request.clone()))
+            .await
+            .map_err(|e| anyhow::anyhow!("GetRaydiumPoolReserve error: {}", e))?;
+
+        Ok(response.into_inner())
+    }
+
+    pub async fn load_pool_cache(&mut self) -> Result<PoolCache> {
+        let mut cache = PoolCache::new();
+
+        let amm = self.get_raydium_pools(&Default::default()).await?;
+        cache.extend(PoolKind::Amm, &amm.pools);
+        let cpmm = self.get_raydium_cpmm_pools(&Default::default()).await?;
+        cache.extend(PoolKind::Cpmm, &cpmm.pools);
+        let clmm = self.get_raydium_clmm_pools(&Default::default()).await?;
+        cache.extend(PoolKind::Clmm, &clmm.pools);
+
+        Ok(cache)
+    }
+}
@@ -1,7 +1,10 @@
 use anyhow::Result;
+use async_trait::async_trait;
 use solana_trader_proto::api;
 use tonic::Request;
 
+use crate::common::quote::{self, BestQuote, BestQuoteRequest, QuoteSource};
+
 use super::GrpcClient;
 
 impl GrpcClient {
@@ -115,4 +118,82 @@ impl GrpcClient {
 
         Ok(response.into_inner())
     }
+
+    pub async fn best_quote(&self, request: &BestQuoteRequest) -> Result<BestQuote> {
+        quote::best_quote(self, request).await
+    }
+}
+
+// Quotes go through a clone of the channel, so `best_quote` can fan out from `&self`.
+#[async_trait]
+impl QuoteSource for GrpcClient {
+    async fn raydium_quotes(
+        &self,
+        request: &api::GetRaydiumQuotesRequest,
+    ) -> Result<api::GetRaydiumQuotesResponse> {
+        let response = self
+            .client
+            .clone()
+            .get_raydium_quotes(Request::new(request.clone()))
+            .await
+            .map_err(|e| anyhow::anyhow!("GetRaydiumQuotes error: {}", e))?;
+
+        Ok(response.into_inner())
+    }
+
+    async fn raydium_cpmm_quotes(
+        &self,
+        request: &api::GetRaydiumCpmmQuotesRequest,
+    ) -> Result<api::GetRaydiumCpmmQuotesResponse> {
+        let response = self
+            .client
+            .clone()
+            .get_raydium_cpmm_quotes(Request::new(request.clone()))
+            .await
+            .map_err(|e| anyhow::anyhow!("GetRaydiumCPMMQuotes error: {}", e))?;
+
+        Ok(response.into_inner())
+    }
+
+    async fn raydium_clmm_quotes(
+        &self,
+        request: &api::GetRaydiumClmmQuotesRequest,
+    ) -> Result<api::GetRaydiumClmmQuotesResponse> {
+        let response = self
+            .client
+            .clone()
+            .get_raydium_clmm_quotes(Request::new(request.clone()))
+            .await
+            .map_err(|e| anyhow::anyhow!("GetRaydiumCLMMQuotes error: {}", e))?;
+
+        Ok(response.into_inner())
+    }
+
+    async fn jupiter_quotes(
+        &self,
+        request: &api::GetJupiterQuotesRequest,
+    ) -> Result<api::GetJupiterQuotesResponse> {
+        let response = self
+            .client
+            .clone()
+            .get_jupiter_quotes(Request::new(request.clone()))
+            .await
+            .map_err(|e| anyhow::anyhow!("GetJupiterQuotes error: {}", e))?;
+
+        Ok(response.into_inner())
+    }
+
+    async fn pump_fun_quotes(
+        &self,
+        request: &api::GetPumpFunQuotesRequest,
+    ) -> Result<api::GetPumpFunQuotesResponse> {
+        let response = self
+            .client
+            .clone()
+            .get_pump_fun_quotes(Request::new(request.clone()))
+            .await
+            .map_err(|e| anyhow::anyhow!("GetPumpFunQuotes error: {}", e))?;
+
+        Ok(response.into_inner())
+    }
 }
@@ -4,13 +4,16 @@ use tonic::Request;
 use tonic::Streaming;
 
 use super::GrpcClient;
+use crate::connections::telemetry::TracedStream;
+
+pub type GrpcStream<T> = TracedStream<Streaming<T>>;
 
 impl GrpcClient {
     pub async fn get_prices_stream(
         &mut self,
         projects: Vec<api::Project>,
         tokens: Vec<String>,
-    ) -> Result<Streaming<api::GetPricesStreamResponse>> {
+    ) -> Result<GrpcStream<api::GetPricesStreamResponse>> {
         let request = Request::new(api::GetPricesStreamRequest {
             projects: projects.iter().map(|&p| p as i32).collect(),
             tokens,
@@ -22,10 +25,10 @@ impl GrpcClient {
             .await
             .map_err(|e| anyhow::anyhow!("GetPricesStream error: {}", e))?;
 
-        Ok(response.into_inner())
+        Ok(TracedStream::new(response.into_inner(), "GetPricesStream"))
     }
 
-    pub async fn get_block_stream(&mut self) -> Result<Streaming<api::GetBlockStreamResponse>> {
+    pub async fn get_block_stream(&mut self) -> Result<GrpcStream<api::GetBlockStreamResponse>> {
         let request = Request::new(api::GetBlockStreamRequest {});
 
         let response = self
@@ -34,7 +37,7 @@ impl GrpcClient {
             .await
             .map_err(|e| anyhow::anyhow!("GetBlockStream error: {}", e))?;
 
-        Ok(response.into_inner())
+        Ok(TracedStream::new(response.into_inner(), "GetBlockStream"))
     }
 
     pub async fn get_orderbook_stream(
@@ -42,7 +45,7 @@ impl GrpcClient {
         markets: Vec<String>,
         limit: u32,
         project: api::Project,
-    ) -> Result<Streaming<api::GetOrderbooksStreamResponse>> {
+    ) -> Result<GrpcStream<api::GetOrderbooksStreamResponse>> {
         let request = Request::new(api::GetOrderbooksRequest {
             markets,
             limit,
@@ -55,7 +58,10 @@ impl GrpcClient {
             .await
             .map_err(|e| anyhow::anyhow!("GetOrderbooksStream error: {}", e))?;
 
-        Ok(response.into_inner())
+        Ok(TracedStream::new(
+            response.into_inner(),
+            "GetOrderbooksStream",
+        ))
     }
 
     pub async fn get_market_depths_stream(
@@ -63,7 +69,7 @@ impl GrpcClient {
         markets: Vec<String>,
         limit: u32,
         project: api::Project,
-    ) -> Result<Streaming<api::GetMarketDepthsStreamResponse>> {
+    ) -> Result<GrpcStream<api::GetMarketDepthsStreamResponse>> {
         let request = Request::new(api::GetMarketDepthsRequest {
             markets,
             limit,
@@ -76,14 +82,17 @@ impl GrpcClient {
             .await
             .map_err(|e| anyhow::anyhow!("GetMarketDepthsStream error: {}", e))?;
 
-        Ok(response.into_inner())
+        Ok(TracedStream::new(
+            response.into_inner(),
+            "GetMarketDepthsStream",
+        ))
     }
 
     pub async fn get_ticker_stream(
         &mut self,
         markets: Vec<String>,
         project: api::Project,
-    ) -> Result<Streaming<api::GetTickersStreamResponse>> {
+    ) -> Result<GrpcStream<api::GetTickersStreamResponse>> {
         let request = Request::new(api::GetTickersStreamRequest {
             markets,
             project: project as i32,
@@ -95,7 +104,7 @@ impl GrpcClient {
             .await
             .map_err(|e| anyhow::anyhow!("GetTickersStream error: {}", e))?;
 
-        Ok(response.into_inner())
+        Ok(TracedStream::new(response.into_inner(), "GetTickersStream"))
     }
 
     pub async fn get_trades_stream(
@@ -103,7 +112,7 @@ impl GrpcClient {
         market: String,
         limit: u32,
         project: api::Project,
-    ) -> Result<Streaming<api::GetTradesStreamResponse>> {
+    ) -> Result<GrpcStream<api::GetTradesStreamResponse>> {
         let request = Request::new(api::GetTradesRequest {
             market,
             limit,
@@ -116,7 +125,7 @@ impl GrpcClient {
             .await
             .map_err(|e| anyhow::anyhow!("GetTradesStream error: {}", e))?;
 
-        Ok(response.into_inner())
+        Ok(TracedStream::new(response.into_inner(), "GetTradesStream"))
     }
 
     pub async fn get_swaps_stream(
@@ -124,7 +133,7 @@ impl GrpcClient {
         projects: Vec<api::Project>,
         pools: Vec<String>,
         include_failed: bool,
-    ) -> Result<Streaming<api::GetSwapsStreamResponse>> {
+    ) -> Result<GrpcStream<api::GetSwapsStreamResponse>> {
         let request = Request::new(api::GetSwapsStreamRequest {
             projects: projects.iter().map(|&p| p as i32).collect(),
             pools,
@@ -137,13 +146,13 @@ impl GrpcClient {
             .await
             .map_err(|e| anyhow::anyhow!("GetSwapsStream error: {}", e))?;
 
-        Ok(response.into_inner())
+        Ok(TracedStream::new(response.into_inner(), "GetSwapsStream"))
     }
 
     pub async fn get_new_raydium_pools_stream(
         &mut self,
         include_cpmm: bool,
-    ) -> Result<Streaming<api::GetNewRaydiumPoolsResponse>> {
+    ) -> Result<GrpcStream<api::GetNewRaydiumPoolsResponse>> {
         let request = Request::new(api::GetNewRaydiumPoolsRequest {
             include_cpmm: Some(include_cpmm),
         });
@@ -154,12 +163,15 @@ impl GrpcClient {
             .await
             .map_err(|e| anyhow::anyhow!("GetNewRaydiumPoolsStream error: {}", e))?;
 
-        Ok(response.into_inner())
+        Ok(TracedStream::new(
+            response.into_inner(),
+            "GetNewRaydiumPoolsStream",
+        ))
     }
 
     pub async fn get_new_raydium_pools_by_transaction_stream(
         &mut self,
-    ) -> Result<Streaming<api::GetNewRaydiumPoolsByTransactionResponse>> {
+    ) -> Result<GrpcStream<api::GetNewRaydiumPoolsByTransactionResponse>> {
         let request = Request::new(api::GetNewRaydiumPoolsByTransactionRequest {});
 
         let response = self
@@ -168,12 +180,15 @@ impl GrpcClient {
             .await
             .map_err(|e| anyhow::anyhow!("GetNewRaydiumPoolsByTransactionStream error: {}", e))?;
 
-        Ok(response.into_inner())
+        Ok(TracedStream::new(
+            response.into_inner(),
+            "GetNewRaydiumPoolsByTransactionStream",
+        ))
     }
 
     pub async fn get_recent_block_hash_stream(
         &mut self,
-    ) -> Result<Streaming<api::GetRecentBlockHashResponse>> {
+    ) -> Result<GrpcStream<api::GetRecentBlockHashResponse>> {
         let request = Request::new(api::GetRecentBlockHashRequest {});
 
         let response = self
@@ -182,14 +197,17 @@ impl GrpcClient {
             .await
             .map_err(|e| anyhow::anyhow!("GetRecentBlockHashStream error: {}", e))?;
 
-        Ok(response.into_inner())
+        Ok(TracedStream::new(
+            response.into_inner(),
+            "GetRecentBlockHashStream",
+        ))
     }
 
     pub async fn get_pool_reserves_stream(
         &mut self,
         projects: Vec<api::Project>,
         pools: Vec<String>,
-    ) -> Result<Streaming<api::GetPoolReservesStreamResponse>> {
+    ) -> Result<GrpcStream<api::GetPoolReservesStreamResponse>> {
         let request = Request::new(api::GetPoolReservesStreamRequest {
             projects: projects.iter().map(|&p| p as i32).collect(),
             pools,
@@ -201,14 +219,17 @@ impl GrpcClient {
             .await
             .map_err(|e| anyhow::anyhow!("GetPoolReservesStream error: {}", e))?;
 
-        Ok(response.into_inner())
+        Ok(TracedStream::new(
+            response.into_inner(),
+            "GetPoolReservesStream",
+        ))
     }
 
     pub async fn get_priority_fee_stream(
         &mut self,
         project: api::Project,
         percentile: Option<f64>,
-    ) -> Result<Streaming<api::GetPriorityFeeResponse>> {
+    ) -> Result<GrpcStream<api::GetPriorityFeeResponse>> {
         let request = Request::new(api::GetPriorityFeeRequest {
             project: project as i32,
             percentile,
@@ -220,10 +241,13 @@ impl GrpcClient {
             .await
             .map_err(|e| anyhow::anyhow!("GetPriorityFeeStream error: {}", e))?;
 
-        Ok(response.into_inner())
+        Ok(TracedStream::new(
+            response.into_inner(),
+            "GetPriorityFeeStream",
+        ))
     }
 
-    pub async fn get_bundle_tip_stream(&mut self) -> Result<Streaming<api::GetBundleTipResponse>> {
+    pub async fn get_bundle_tip_stream(&mut self) -> Result<GrpcStream<api::GetBundleTipResponse>> {
         let request = Request::new(api::GetBundleTipRequest {});
 
         let response = self
@@ -232,12 +256,15 @@ impl GrpcClient {
             .await
             .map_err(|e| anyhow::anyhow!("GetBundleTipStream error: {}", e))?;
 
-        Ok(response.into_inner())
+        Ok(TracedStream::new(
+            response.into_inner(),
+            "GetBundleTipStream",
+        ))
     }
 
     pub async fn get_pump_fun_new_tokens_stream(
         &mut self,
-    ) -> Result<Streaming<api::GetPumpFunNewTokensStreamResponse>> {
+    ) -> Result<GrpcStream<api::GetPumpFunNewTokensStreamResponse>> {
         let request = Request::new(api::GetPumpFunNewTokensStreamRequest {});
 
         let response = self
@@ -246,13 +273,16 @@ impl GrpcClient {
             .await
             .map_err(|e| anyhow::anyhow!("GetPumpFunNewTokensStream error: {}", e))?;
 
-        Ok(response.into_inner())
+        Ok(TracedStream::new(
+            response.into_inner(),
+            "GetPumpFunNewTokensStream",
+        ))
     }
 
     pub async fn get_pump_fun_swaps_stream(
         &mut self,
         tokens: Vec<String>,
-    ) -> Result<Streaming<api::GetPumpFunSwapsStreamResponse>> {
+    ) -> Result<GrpcStream<api::GetPumpFunSwapsStreamResponse>> {
         let request = Request::new(api::GetPumpFunSwapsStreamRequest { tokens });
 
         let response = self
@@ -261,6 +291,9 @@ impl GrpcClient {
             .await
             .map_err(|e| anyhow::anyhow!("GetPumpFunSwapsStream error: {}", e))?;
 
-        Ok(response.into_inner())
+        Ok(TracedStream::new(
+            response.into_inner(),
+            "GetPumpFunSwapsStream",
+        ))
     }
 }
@@ -1,4 +1,8 @@
 pub mod general;
+pub mod openbook;
+pub mod phoenix;
+pub mod pools;
+pub mod query;
 pub mod quote;
 pub mod swap;
 
@@ -9,18 +13,24 @@ use reqwest::{
 };
 use serde::de::DeserializeOwned;
 use serde_json::json;
-use solana_sdk::{pubkey::Pubkey, signature::Keypair};
-use solana_trader_proto::api::GetRecentBlockHashResponseV2;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+use solana_trader_proto::api::{self, GetRecentBlockHashRequestV2, GetRecentBlockHashResponseV2};
 
 use crate::{
     common::{
+        bundle::{Bundle, BundleResult},
         get_base_url_from_env, http_endpoint,
-        signing::{sign_transaction, SubmitParams},
+        signing::{
+            require_signer, sign_transactions, ClientCapability, SignedTransaction, SubmitOutcome,
+            SubmitParams,
+        },
         BaseConfig,
     },
+    connections::telemetry::TracedSend,
     provider::utils::convert_string_enums,
 };
 
+use self::query::{Endpoint, HttpGet};
 use super::utils::IntoTransactionMessage;
 
 pub struct HTTPClient {
@@ -32,11 +42,33 @@ pub struct HTTPClient {
 
 impl HTTPClient {
     pub fn get_keypair(&self) -> Result<&Keypair> {
-        Ok(self.keypair.as_ref().unwrap())
+        Ok(require_signer(self.keypair.as_ref())?)
+    }
+
+    pub fn require_signer(&self) -> Result<()> {
+        self.get_keypair().map(|_| ())
+    }
+
+    pub fn capability(&self) -> ClientCapability {
+        ClientCapability::of(self.keypair.as_ref())
+    }
+
+    pub fn with_keypair(mut self, keypair: Keypair) -> Self {
+        self.public_key = Some(keypair.pubkey());
+        self.keypair = Some(keypair);
+        self
     }
 
     pub fn new(endpoint: Option<String>) -> Result<Self> {
-        let base = BaseConfig::try_from_env()?;
+        Self::from_config(endpoint, BaseConfig::try_from_env()?)
+    }
+
+    // Builds the client without loading any keypair; signing methods return `NoSignerError`.
+    pub fn new_read_only(endpoint: Option<String>) -> Result<Self> {
+        Self::from_config(endpoint, BaseConfig::read_only_from_env()?)
+    }
+
+    fn from_config(endpoint: Option<String>, base: BaseConfig) -> Result<Self> {
         let (default_base_url, secure) = get_base_url_from_env();
         let final_base_url = endpoint.unwrap_or(default_base_url);
         let endpoint = http_endpoint(&final_base_url, secure);
@@ -90,66 +122,111 @@ impl HTTPClient {
             .map_err(|e| anyhow::anyhow!("Failed to parse response into desired type: {}", e))
     }
 
+    async fn get<T: DeserializeOwned>(&self, endpoint: Endpoint) -> Result<T> {
+        let url = endpoint.url(&self.base_url)?;
+
+        let response = self
+            .client
+            .get(&url)
+            .send_traced()
+            .await
+            .map_err(|e| anyhow!("HTTP GET request failed: {}", e))?;
+
+        self.handle_response(response).await
+    }
+
+    pub async fn submit_signed_transaction(
+        &self,
+        signed_tx: &SignedTransaction,
+        submit_opts: &SubmitParams,
+    ) -> Result<String> {
+        let request_json = json!({
+            "transaction": { "content": signed_tx.content, "isCleanup": signed_tx.is_cleanup },
+            "skipPreFlight": submit_opts.skip_pre_flight,
+            "frontRunningProtection": submit_opts.front_running_protection,
+            "useStakedRPCs": submit_opts.use_staked_rpcs,
+            "fastBestEffort": submit_opts.fast_best_effort
+        });
+
+        let response = self
+            .client
+            .post(format!("{}/api/v2/submit", self.base_url))
+            .json(&request_json)
+            .send_traced()
+            .await?;
+
+        let result: serde_json::Value = self.handle_response(response).await?;
+        result
+            .get("signature")
+            .and_then(|s| s.as_str())
+            .map(String::from)
+            .ok_or_else(|| anyhow!("Missing signature in response"))
+    }
+
     pub async fn sign_and_submit<T: IntoTransactionMessage + Clone>(
         &self,
         txs: Vec<T>,
         submit_opts: SubmitParams,
         use_bundle: bool,
-    ) -> Result<Vec<String>> {
+    ) -> Result<Vec<SubmitOutcome>> {
         let keypair = self.get_keypair()?;
+        let block_hash = self.latest_block_hash().await?;
+
+        let mut signed_txs = sign_transactions(txs, keypair, &block_hash, &submit_opts).await?;
+
+        if signed_txs.len() == 1 {
+            let signed_tx = signed_txs.remove(0);
+            let result = self
+                .submit_signed_transaction(&signed_tx, &submit_opts)
+                .await;
 
-        // TODO: refactor once this endpoint is defined
+            return Ok(vec![SubmitOutcome::from_single(&signed_tx, result)]);
+        }
+
+        let count = signed_txs.len();
         let response = self
-            .client
-            .get(format!(
-                "{}/api/v2/system/blockhash?offset={}",
-                self.base_url, 0
-            ))
-            .send()
+            .submit_signed_batch(signed_txs, &submit_opts, use_bundle)
             .await?;
 
-        let res: GetRecentBlockHashResponseV2 = self.handle_response(response).await?;
-
-        if txs.len() == 1 {
-            let signed_tx = sign_transaction(&txs[0], keypair, res.block_hash).await?;
-
-            let request_json = json!({
-                "transaction": { "content": signed_tx.content, "isCleanup": signed_tx.is_cleanup },
-                "skipPreFlight": submit_opts.skip_pre_flight,
-                "frontRunningProtection": submit_opts.front_running_protection,
-                "useStakedRPCs": submit_opts.use_staked_rpcs,
-                "fastBestEffort": submit_opts.fast_best_effort
-            });
-
-            let response = self
-                .client
-                .post(format!("{}/api/v2/submit", self.base_url))
-                .json(&request_json)
-                .send()
-                .await?;
-
-            let result: serde_json::Value = self.handle_response(response).await?;
-            return Ok(vec![result
-                .get("signature")
-                .and_then(|s| s.as_str())
-                .map(String::from)
-                .ok_or_else(|| anyhow!("Missing signature in response"))?]);
-        }
+        Ok(SubmitOutcome::from_batch(count, response))
+    }
 
-        let mut entries = Vec::with_capacity(txs.len());
-        for tx in txs {
-            let signed_tx = sign_transaction(&tx, keypair, res.block_hash.clone()).await?;
-            entries.push(json!({
-                "transaction": {
-                    "content": signed_tx.content,
-                    "isCleanup": signed_tx.is_cleanup
-                },
-                "skipPreFlight": submit_opts.skip_pre_flight,
-                "frontRunningProtection": submit_opts.front_running_protection,
-                "useStakedRPCs": submit_opts.use_staked_rpcs,
-                "fastBestEffort": submit_opts.fast_best_effort
-            }));
-        }
+    pub async fn submit_bundle(
+        &self,
+        bundle: &Bundle,
+        submit_opts: SubmitParams,
+    ) -> Result<BundleResult> {
+        let keypair = self.get_keypair()?;
+        let block_hash = self.latest_block_hash().await?;
+        let signed_txs = bundle.sign(keypair, &block_hash, &submit_opts).await?;
+
+        let response = self
+            .submit_signed_batch(signed_txs, &submit_opts, true)
+            .await?;
+        Ok(bundle.result(response))
+    }
+
+    async fn submit_signed_batch(
+        &self,
+        signed_txs: Vec<SignedTransaction>,
+        submit_opts: &SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<api::PostSubmitBatchResponseEntry>> {
+        let entries: Vec<_> = signed_txs
+            .into_iter()
+            .map(|signed_tx| {
+                json!({
+                    "transaction": {
+                        "content": signed_tx.content,
+                        "isCleanup": signed_tx.is_cleanup
+                    },
+                    "skipPreFlight": submit_opts.skip_pre_flight,
+                    "frontRunningProtection": submit_opts.front_running_protection,
+                    "useStakedRPCs": submit_opts.use_staked_rpcs,
+                    "fastBestEffort": submit_opts.fast_best_effort
+                })
+            })
+            .collect();
 
         let request_json = json!({
             "entries": entries,
@@ -161,19 +238,17 @@ impl HTTPClient {
             .client
             .post(format!("{}/api/v2/submit/batch", self.base_url))
             .json(&request_json)
-            .send()
+            .send_traced()
             .await?;
 
-        let result: serde_json::Value = self.handle_response(response).await?;
-
-        let signatures = result["transactions"]
-            .as_array()
-            .ok_or_else(|| anyhow!("Invalid response format"))?
-            .iter()
-            .filter(|entry| entry["submitted"].as_bool().unwrap_or(false))
-            .filter_map(|entry| entry["signature"].as_str().map(String::from))
-            .collect();
+        let result: api::PostSubmitBatchResponse = self.handle_response(response).await?;
+        Ok(result.transactions)
+    }
 
-        Ok(signatures)
+    async fn latest_block_hash(&self) -> Result<String> {
+        let res: GetRecentBlockHashResponseV2 = self
+            .get(GetRecentBlockHashRequestV2 { offset: 0 }.endpoint())
+            .await?;
+        Ok(res.block_hash)
     }
 }
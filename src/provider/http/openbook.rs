@@ -0,0 +1,169 @@
+use anyhow::Result;
+use solana_trader_proto::api;
+
+use super::query::HttpGet;
+use super::HTTPClient;
+use crate::common::{
+    clob::{order_transaction, require_order_id, require_transactions, ProjectRequest},
+    signing::{SubmitOutcome, SubmitParams},
+};
+use crate::connections::telemetry::TracedSend;
+
+impl HTTPClient {
+    pub async fn post_order(
+        &self,
+        request: &api::PostOrderRequestV2,
+    ) -> Result<api::PostOrderResponse> {
+        let response = self
+            .client
+            .post(format!("{}/api/v2/openbook/place", self.base_url))
+            .json(&request)
+            .send_traced()
+            .await?;
+
+        self.handle_response(response).await
+    }
+
+    pub async fn submit_order(
+        &self,
+        request: api::PostOrderRequestV2,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_order(&request).await?;
+        let txs = order_transaction("PostOrderV2", response)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    pub async fn post_cancel_order(
+        &self,
+        request: &api::PostCancelOrderRequestV2,
+    ) -> Result<api::PostCancelOrderResponseV2> {
+        require_order_id(request)?;
+
+        let response = self
+            .client
+            .post(format!("{}/api/v2/openbook/cancel", self.base_url))
+            .json(&request)
+            .send_traced()
+            .await?;
+
+        self.handle_response(response).await
+    }
+
+    pub async fn submit_cancel_order(
+        &self,
+        request: api::PostCancelOrderRequestV2,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_cancel_order(&request).await?;
+        let txs = require_transactions("PostCancelOrderV2", response.transactions)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    // Cancels every open order the owner has on the market; there is no v2 variant.
+    pub async fn post_cancel_all_orders(
+        &self,
+        request: &api::PostCancelAllRequest,
+    ) -> Result<api::PostCancelAllResponse> {
+        let response = self
+            .client
+            .post(format!("{}/api/v1/trade/cancelall", self.base_url))
+            .json(&request.for_project(api::Project::POpenbook))
+            .send_traced()
+            .await?;
+
+        self.handle_response(response).await
+    }
+
+    pub async fn submit_cancel_all_orders(
+        &self,
+        request: api::PostCancelAllRequest,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_cancel_all_orders(&request).await?;
+        let txs = require_transactions("PostCancelAll", response.transactions)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    pub async fn post_replace_order(
+        &self,
+        request: &api::PostReplaceOrderRequestV2,
+    ) -> Result<api::PostOrderResponse> {
+        let response = self
+            .client
+            .post(format!("{}/api/v2/openbook/replace", self.base_url))
+            .json(&request)
+            .send_traced()
+            .await?;
+
+        self.handle_response(response).await
+    }
+
+    pub async fn submit_replace_order(
+        &self,
+        request: api::PostReplaceOrderRequestV2,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_replace_order(&request).await?;
+        let txs = order_transaction("PostReplaceOrderV2", response)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    pub async fn post_settle(
+        &self,
+        request: &api::PostSettleRequestV2,
+    ) -> Result<api::PostSettleResponse> {
+        let response = self
+            .client
+            .post(format!("{}/api/v2/openbook/settle", self.base_url))
+            .json(&request)
+            .send_traced()
+            .await?;
+
+        self.handle_response(response).await
+    }
+
+    pub async fn submit_settle(
+        &self,
+        request: api::PostSettleRequestV2,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_settle(&request).await?;
+        let txs = require_transactions("PostSettleV2", response.transactions)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    pub async fn get_open_orders(
+        &self,
+        request: &api::GetOpenOrdersRequestV2,
+    ) -> Result<api::GetOpenOrdersResponseV2> {
+        self.get(request.endpoint()).await
+    }
+
+    pub async fn get_unsettled(
+        &self,
+        request: &api::GetUnsettledRequestV2,
+    ) -> Result<api::GetUnsettledResponse> {
+        self.get(request.endpoint()).await
+    }
+}
@@ -0,0 +1,170 @@
+use anyhow::Result;
+use solana_trader_proto::api;
+
+use super::query::HttpGet;
+use super::HTTPClient;
+use crate::common::{
+    clob::{order_transaction, require_transactions, ProjectRequest},
+    signing::{SubmitOutcome, SubmitParams},
+};
+use crate::connections::telemetry::TracedSend;
+
+// Phoenix is only served by the v1 trade endpoints; every request is sent with the
+// Phoenix project set.
+impl HTTPClient {
+    pub async fn post_phoenix_order(
+        &self,
+        request: &api::PostOrderRequest,
+    ) -> Result<api::PostOrderResponse> {
+        let response = self
+            .client
+            .post(format!("{}/api/v1/trade/place", self.base_url))
+            .json(&request.for_project(api::Project::PPhoenix))
+            .send_traced()
+            .await?;
+
+        self.handle_response(response).await
+    }
+
+    pub async fn submit_phoenix_order(
+        &self,
+        request: api::PostOrderRequest,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_phoenix_order(&request).await?;
+        let txs = order_transaction("PostOrder", response)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    pub async fn post_phoenix_cancel_order(
+        &self,
+        request: &api::PostCancelOrderRequest,
+    ) -> Result<api::PostCancelOrderResponse> {
+        let response = self
+            .client
+            .post(format!("{}/api/v1/trade/cancel", self.base_url))
+            .json(&request.for_project(api::Project::PPhoenix))
+            .send_traced()
+            .await?;
+
+        self.handle_response(response).await
+    }
+
+    pub async fn submit_phoenix_cancel_order(
+        &self,
+        request: api::PostCancelOrderRequest,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_phoenix_cancel_order(&request).await?;
+        let txs = require_transactions("PostCancelOrder", response.transactions)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    pub async fn post_phoenix_cancel_all_orders(
+        &self,
+        request: &api::PostCancelAllRequest,
+    ) -> Result<api::PostCancelAllResponse> {
+        let response = self
+            .client
+            .post(format!("{}/api/v1/trade/cancelall", self.base_url))
+            .json(&request.for_project(api::Project::PPhoenix))
+            .send_traced()
+            .await?;
+
+        self.handle_response(response).await
+    }
+
+    pub async fn submit_phoenix_cancel_all_orders(
+        &self,
+        request: api::PostCancelAllRequest,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_phoenix_cancel_all_orders(&request).await?;
+        let txs = require_transactions("PostCancelAll", response.transactions)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    pub async fn post_phoenix_replace_order(
+        &self,
+        request: &api::PostReplaceOrderRequest,
+    ) -> Result<api::PostOrderResponse> {
+        let response = self
+            .client
+            .post(format!("{}/api/v1/trade/replace", self.base_url))
+            .json(&request.for_project(api::Project::PPhoenix))
+            .send_traced()
+            .await?;
+
+        self.handle_response(response).await
+    }
+
+    pub async fn submit_phoenix_replace_order(
+        &self,
+        request: api::PostReplaceOrderRequest,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_phoenix_replace_order(&request).await?;
+        let txs = order_transaction("PostReplaceOrder", response)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    pub async fn post_phoenix_settle(
+        &self,
+        request: &api::PostSettleRequest,
+    ) -> Result<api::PostSettleResponse> {
+        let response = self
+            .client
+            .post(format!("{}/api/v1/trade/settle", self.base_url))
+            .json(&request.for_project(api::Project::PPhoenix))
+            .send_traced()
+            .await?;
+
+        self.handle_response(response).await
+    }
+
+    pub async fn submit_phoenix_settle(
+        &self,
+        request: api::PostSettleRequest,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_phoenix_settle(&request).await?;
+        let txs = require_transactions("PostSettle", response.transactions)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    pub async fn get_phoenix_open_orders(
+        &self,
+        request: &api::GetOpenOrdersRequest,
+    ) -> Result<api::GetOpenOrdersResponse> {
+        self.get(request.for_project(api::Project::PPhoenix).endpoint())
+            .await
+    }
+
+    pub async fn get_phoenix_unsettled(
+        &self,
+        request: &api::GetUnsettledRequest,
+    ) -> Result<api::GetUnsettledResponse> {
+        self.get(request.for_project(api::Project::PPhoenix).endpoint())
+            .await
+    }
+}
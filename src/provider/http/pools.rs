@@ -0,0 +1,56 @@
+use anyhow::Result;
+use solana_trader_proto::api;
+
+use super::query::HttpGet;
+use super::HTTPClient;
+use crate::common::pools::{PoolCache, PoolKind};
+
+impl HTTPClient {
+    pub async fn get_markets(
+        &self,
+        request: &api::GetMarketsRequestV2,
+    ) -> Result<api::GetMarketsResponseV2> {
+        self.get(request.endpoint()).await
+    }
+
+    pub async fn get_raydium_pools(
+        &self,
+        request: &api::GetRaydiumPoolsRequest,
+    ) -> Result<api::GetRaydiumPoolsResponse> {
+        self.get(request.endpoint()).await
+    }
+
+    pub async fn get_raydium_cpmm_pools(
+        &self,
+        request: &api::GetRaydiumCpmmPoolsRequest,
+    ) -> Result<api::GetRaydiumCpmmPoolsResponse> {
+        self.get(request.endpoint()).await
+    }
+
+    pub async fn get_raydium_clmm_pools(
+        &self,
+        request: &api::GetRaydiumClmmPoolsRequest,
+    ) -> Result<api::GetRaydiumClmmPoolsResponse> {
+        self.get(request.endpoint()).await
+    }
+
+    pub async fn get_raydium_pool_reserve(
+        &self,
+        request: &api::GetRaydiumPoolReserveRequest,
+    ) -> Result<api::GetRaydiumPoolReserveResponse> {
+        self.get(request.endpoint()).await
+    }
+
+    pub async fn load_pool_cache(&self) -> Result<PoolCache> {
+        let mut cache = PoolCache::new();
+
+        let amm = self.get_raydium_pools(&Default::default()).await?;
+        cache.extend(PoolKind::Amm, &amm.pools);
+        let cpmm = self.get_raydium_cpmm_pools(&Default::default()).await?;
+        cache.extend(PoolKind::Cpmm, &cpmm.pools);
+        let clmm = self.get_raydium_clmm_pools(&Default::default()).await?;
+        cache.extend(PoolKind::Clmm, &clmm.pools);
+
+        Ok(cache)
+    }
+}
@@ -0,0 +1,485 @@
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use serde_json::Value;
+use solana_trader_proto::api;
+use url::Url;
+
+// Path and query parameters of a REST GET call. Parameters keep the order they were added
+// in and are percent-encoded when the URL is built; like reqwest's `RequestBuilder`, the
+// first serialization error is kept and returned from `url`.
+#[derive(Debug)]
+pub struct Endpoint {
+    segments: Vec<String>,
+    params: Vec<(&'static str, String)>,
+    error: Option<anyhow::Error>,
+}
+
+impl Endpoint {
+    pub fn new(path: &str) -> Self {
+        Self {
+            segments: path
+                .split('/')
+                .filter(|segment| !segment.is_empty())
+                .map(String::from)
+                .collect(),
+            params: Vec::new(),
+            error: None,
+        }
+    }
+
+    // Appended as a single path segment, so a `/` in e.g. a market name is encoded.
+    pub fn segment(mut self, segment: impl Into<String>) -> Self {
+        self.segments.push(segment.into());
+        self
+    }
+
+    // `None` is skipped and sequences repeat the key, e.g. `tokens=a&tokens=b`. Enums with a
+    // serde representation are sent by name; proto enums are `i32` and go out as numbers.
+    pub fn param<T: Serialize + ?Sized>(mut self, key: &'static str, value: &T) -> Self {
+        if self.error.is_none() {
+            if let Err(e) = self.push(key, value) {
+                self.error = Some(e);
+            }
+        }
+        self
+    }
+
+    // Proto3 scalars have no presence, so a default value means the filter is unset.
+    pub fn param_if_set<T: Serialize + Default + PartialEq>(
+        self,
+        key: &'static str,
+        value: &T,
+    ) -> Self {
+        if *value == T::default() {
+            return self;
+        }
+        self.param(key, value)
+    }
+
+    // serde_json serializes NaN and infinities as null, which `param` would skip as unset, so
+    // float parameters go through here and are rejected instead.
+    pub fn number(mut self, key: &'static str, value: impl Into<Option<f64>>) -> Self {
+        match value.into() {
+            Some(value) if !value.is_finite() => {
+                if self.error.is_none() {
+                    self.error = Some(anyhow!(
+                        "Query parameter {} must be a finite number, got {}",
+                        key,
+                        value
+                    ));
+                }
+                self
+            }
+            value => self.param(key, &value),
+        }
+    }
+
+    pub fn url(self, base_url: &str) -> Result<String> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+
+        let mut url =
+            Url::parse(base_url).map_err(|e| anyhow!("Invalid base URL {}: {}", base_url, e))?;
+        url.path_segments_mut()
+            .map_err(|_| anyhow!("Base URL {} cannot have a path", base_url))?
+            .pop_if_empty()
+            .extend(&self.segments);
+        if !self.params.is_empty() {
+            url.query_pairs_mut().extend_pairs(&self.params);
+        }
+
+        Ok(url.into())
+    }
+
+    fn push<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<()> {
+        match serde_json::to_value(value)? {
+            Value::Null => {}
+            Value::Array(items) => {
+                for item in items {
+                    self.params.push((key, scalar(key, item)?));
+                }
+            }
+            value => self.params.push((key, scalar(key, value)?)),
+        }
+        Ok(())
+    }
+}
+
+fn scalar(key: &str, value: Value) -> Result<String> {
+    match value {
+        Value::String(s) => Ok(s),
+        Value::Bool(b) => Ok(b.to_string()),
+        // Floats use `Display`, so 1.0 is sent as `1` and never in exponent form.
+        Value::Number(n) => Ok(match n.as_f64() {
+            Some(f) if n.is_f64() => f.to_string(),
+            _ => n.to_string(),
+        }),
+        other => Err(anyhow!(
+            "Query parameter {} must be a scalar or a list of scalars, got {}",
+            key,
+            other
+        )),
+    }
+}
+
+// Maps a proto request to its documented REST endpoint.
+pub trait HttpGet {
+    fn endpoint(&self) -> Endpoint;
+}
+
+impl HttpGet for api::GetTransactionRequest {
+    fn endpoint(&self) -> Endpoint {
+        Endpoint::new("/api/v2/transaction").param("signature", &self.signature)
+    }
+}
+
+impl HttpGet for api::GetRecentBlockHashRequestV2 {
+    fn endpoint(&self) -> Endpoint {
+        Endpoint::new("/api/v2/system/blockhash").param("offset", &self.offset)
+    }
+}
+
+impl HttpGet for api::GetAccountBalanceRequest {
+    fn endpoint(&self) -> Endpoint {
+        Endpoint::new("/api/v2/balance").param("ownerAddress", &self.owner_address)
+    }
+}
+
+impl HttpGet for api::GetTokenAccountsRequest {
+    fn endpoint(&self) -> Endpoint {
+        Endpoint::new("/api/v1/account/token-accounts").param("ownerAddress", &self.owner_address)
+    }
+}
+
+impl HttpGet for api::GetPriorityFeeRequest {
+    fn endpoint(&self) -> Endpoint {
+        Endpoint::new("/api/v2/system/priority-fee")
+            .param("project", &self.project)
+            .number("percentile", self.percentile)
+    }
+}
+
+impl HttpGet for api::GetPriorityFeeByProgramRequest {
+    fn endpoint(&self) -> Endpoint {
+        Endpoint::new("/api/v2/system/priority-fee-by-program").param("programs", &self.programs)
+    }
+}
+
+macro_rules! impl_quote_endpoint {
+    ($($request:ty => $path:literal),*) => {
+        $(
+            impl HttpGet for $request {
+                fn endpoint(&self) -> Endpoint {
+                    Endpoint::new($path)
+                        .param("inToken", &self.in_token)
+                        .param("outToken", &self.out_token)
+                        .number("inAmount", self.in_amount)
+                        .number("slippage", self.slippage)
+                }
+            }
+        )*
+    };
+}
+
+impl_quote_endpoint!(
+    api::GetRaydiumQuotesRequest => "/api/v2/raydium/quotes",
+    api::GetRaydiumCpmmQuotesRequest => "/api/v2/raydium/cpmm-quotes",
+    api::GetRaydiumClmmQuotesRequest => "/api/v2/raydium/clmm-quotes",
+    api::GetJupiterQuotesRequest => "/api/v2/jupiter/quotes"
+);
+
+impl HttpGet for api::GetPumpFunQuotesRequest {
+    fn endpoint(&self) -> Endpoint {
+        Endpoint::new("/api/v2/pumpfun/quotes")
+            .param("mintAddress", &self.mint_address)
+            .param("quoteType", &self.quote_type)
+            .number("amount", self.amount)
+            .param("bondingCurveAddress", &self.bonding_curve_address)
+    }
+}
+
+impl HttpGet for api::GetQuotesRequest {
+    fn endpoint(&self) -> Endpoint {
+        Endpoint::new("/api/v1/market/quote")
+            .param("inToken", &self.in_token)
+            .param("outToken", &self.out_token)
+            .number("inAmount", self.in_amount)
+            .number("slippage", self.slippage)
+            .param("limit", &self.limit)
+            .param("project", &self.projects)
+    }
+}
+
+impl HttpGet for api::GetRaydiumPricesRequest {
+    fn endpoint(&self) -> Endpoint {
+        Endpoint::new("/api/v2/raydium/prices").param("tokens", &self.tokens)
+    }
+}
+
+impl HttpGet for api::GetJupiterPricesRequest {
+    fn endpoint(&self) -> Endpoint {
+        Endpoint::new("/api/v2/jupiter/prices").param("tokens", &self.tokens)
+    }
+}
+
+impl HttpGet for api::GetMarketsRequestV2 {
+    fn endpoint(&self) -> Endpoint {
+        Endpoint::new("/api/v2/openbook/markets")
+    }
+}
+
+macro_rules! impl_pools_endpoint {
+    ($($request:ty => $path:literal),*) => {
+        $(
+            impl HttpGet for $request {
+                fn endpoint(&self) -> Endpoint {
+                    Endpoint::new($path).param_if_set("pairOrAddress", &self.pair_or_address)
+                }
+            }
+        )*
+    };
+}
+
+impl_pools_endpoint!(
+    api::GetRaydiumPoolsRequest => "/api/v2/raydium/pools",
+    api::GetRaydiumCpmmPoolsRequest => "/api/v2/raydium/cpmm-pools",
+    api::GetRaydiumClmmPoolsRequest => "/api/v2/raydium/clmm-pools"
+);
+
+impl HttpGet for api::GetRaydiumPoolReserveRequest {
+    fn endpoint(&self) -> Endpoint {
+        Endpoint::new("/api/v2/raydium/pool-reserves")
+            .param("pairsOrAddresses", &self.pairs_or_addresses)
+    }
+}
+
+impl HttpGet for api::GetOpenOrdersRequestV2 {
+    fn endpoint(&self) -> Endpoint {
+        Endpoint::new("/api/v2/openbook/open-orders")
+            .segment(self.market.as_str())
+            .param("address", &self.owner_address)
+            .param_if_set("openOrdersAddress", &self.open_orders_address)
+            .param_if_set("orderID", &self.order_id)
+            .param_if_set("clientOrderID", &self.client_order_id)
+            .param_if_set("limit", &self.limit)
+    }
+}
+
+impl HttpGet for api::GetUnsettledRequestV2 {
+    fn endpoint(&self) -> Endpoint {
+        Endpoint::new("/api/v2/openbook/unsettled")
+            .segment(self.market.as_str())
+            .param("ownerAddress", &self.owner_address)
+    }
+}
+
+impl HttpGet for api::GetOpenOrdersRequest {
+    fn endpoint(&self) -> Endpoint {
+        Endpoint::new("/api/v1/trade/orders")
+            .segment(self.market.as_str())
+            .param("address", &self.address)
+            .param_if_set("openOrdersAddress", &self.open_orders_address)
+            .param_if_set("orderID", &self.order_id)
+            .param_if_set("clientOrderID", &self.client_order_id)
+            .param_if_set("limit", &self.limit)
+            .param("project", &self.project)
+    }
+}
+
+impl HttpGet for api::GetUnsettledRequest {
+    fn endpoint(&self) -> Endpoint {
+        Endpoint::new("/api/v1/trade/unsettled")
+            .segment(self.market.as_str())
+            .param("ownerAddress", &self.owner_address)
+            .param("project", &self.project)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::clob::ProjectRequest;
+    use crate::common::constants::{USDC, WRAPPED_SOL};
+
+    const BASE_URL: &str = "https://ny.solana.dex.blxrbdn.com";
+
+    fn url(request: &impl HttpGet) -> String {
+        request.endpoint().url(BASE_URL).unwrap()
+    }
+
+    #[test]
+    fn test_quote_urls() {
+        assert_eq!(
+            url(&api::GetRaydiumQuotesRequest {
+                in_token: WRAPPED_SOL.to_string(),
+                out_token: USDC.to_string(),
+                in_amount: 0.1,
+                slippage: 1.0,
+            }),
+            format!(
+                "{}/api/v2/raydium/quotes?inToken={}&outToken={}&inAmount=0.1&slippage=1",
+                BASE_URL, WRAPPED_SOL, USDC
+            )
+        );
+        assert_eq!(
+            url(&api::GetPumpFunQuotesRequest {
+                mint_address: "mint".to_string(),
+                quote_type: "buy".to_string(),
+                amount: 0.000001,
+                bonding_curve_address: "curve".to_string(),
+            }),
+            format!(
+                "{}/api/v2/pumpfun/quotes?mintAddress=mint&quoteType=buy&amount=0.000001&bondingCurveAddress=curve",
+                BASE_URL
+            )
+        );
+        assert_eq!(
+            url(&api::GetQuotesRequest {
+                in_token: "SOL".to_string(),
+                out_token: "USDC".to_string(),
+                in_amount: 2.5,
+                slippage: 0.5,
+                limit: 3,
+                projects: vec![api::Project::PRaydium as i32, api::Project::PJupiter as i32],
+            }),
+            format!(
+                "{}/api/v1/market/quote?inToken=SOL&outToken=USDC&inAmount=2.5&slippage=0.5&limit=3&project={}&project={}",
+                BASE_URL,
+                api::Project::PRaydium as i32,
+                api::Project::PJupiter as i32
+            )
+        );
+        assert_eq!(
+            url(&api::GetRaydiumPricesRequest {
+                tokens: vec!["SOL".to_string(), "USDC".to_string()],
+            }),
+            format!("{}/api/v2/raydium/prices?tokens=SOL&tokens=USDC", BASE_URL)
+        );
+    }
+
+    #[test]
+    fn test_general_urls() {
+        assert_eq!(
+            url(&api::GetRecentBlockHashRequestV2 { offset: 0 }),
+            format!("{}/api/v2/system/blockhash?offset=0", BASE_URL)
+        );
+        assert_eq!(
+            url(&api::GetPriorityFeeRequest {
+                project: api::Project::PRaydium as i32,
+                percentile: None,
+            }),
+            format!(
+                "{}/api/v2/system/priority-fee?project={}",
+                BASE_URL,
+                api::Project::PRaydium as i32
+            )
+        );
+        assert_eq!(
+            url(&api::GetPriorityFeeRequest {
+                project: api::Project::PRaydium as i32,
+                percentile: Some(90.0),
+            }),
+            format!(
+                "{}/api/v2/system/priority-fee?project={}&percentile=90",
+                BASE_URL,
+                api::Project::PRaydium as i32
+            )
+        );
+        assert_eq!(
+            url(&api::GetPriorityFeeByProgramRequest {
+                programs: vec!["a".to_string(), "b".to_string()],
+            }),
+            format!(
+                "{}/api/v2/system/priority-fee-by-program?programs=a&programs=b",
+                BASE_URL
+            )
+        );
+        assert_eq!(
+            url(&api::GetMarketsRequestV2::default()),
+            format!("{}/api/v2/openbook/markets", BASE_URL)
+        );
+    }
+
+    #[test]
+    fn test_optional_and_encoded_params() {
+        assert_eq!(
+            url(&api::GetRaydiumPoolsRequest::default()),
+            format!("{}/api/v2/raydium/pools", BASE_URL)
+        );
+        assert_eq!(
+            url(&api::GetRaydiumCpmmPoolsRequest {
+                pair_or_address: "SOL/USDC".to_string(),
+            }),
+            format!(
+                "{}/api/v2/raydium/cpmm-pools?pairOrAddress=SOL%2FUSDC",
+                BASE_URL
+            )
+        );
+        assert_eq!(
+            url(&api::GetRaydiumPoolReserveRequest {
+                pairs_or_addresses: vec!["SOL/USDC".to_string(), "a&b=c d".to_string()],
+            }),
+            format!(
+                "{}/api/v2/raydium/pool-reserves?pairsOrAddresses=SOL%2FUSDC&pairsOrAddresses=a%26b%3Dc+d",
+                BASE_URL
+            )
+        );
+        assert_eq!(
+            url(&api::GetOpenOrdersRequestV2 {
+                market: "SOL/USDC".to_string(),
+                owner_address: "owner".to_string(),
+                limit: 10,
+                ..Default::default()
+            }),
+            format!(
+                "{}/api/v2/openbook/open-orders/SOL%2FUSDC?address=owner&limit=10",
+                BASE_URL
+            )
+        );
+        assert_eq!(
+            url(&api::GetUnsettledRequest {
+                market: "SOL/USDC".to_string(),
+                owner_address: "owner".to_string(),
+                ..Default::default()
+            }
+            .for_project(api::Project::PPhoenix)),
+            format!(
+                "{}/api/v1/trade/unsettled/SOL%2FUSDC?ownerAddress=owner&project={}",
+                BASE_URL,
+                api::Project::PPhoenix as i32
+            )
+        );
+
+        // A base URL with a path keeps it.
+        let endpoint = Endpoint::new("/api/v2/rate-limit");
+        assert_eq!(
+            endpoint.url("http://localhost:8080/trader/").unwrap(),
+            "http://localhost:8080/trader/api/v2/rate-limit"
+        );
+
+        let endpoint = Endpoint::new("/api/v2/balance").param("ownerAddress", &[[1]]);
+        assert!(endpoint.url(BASE_URL).is_err());
+    }
+
+    #[test]
+    fn test_non_finite_floats_are_rejected() {
+        let request = |in_amount: f64| api::GetRaydiumQuotesRequest {
+            in_token: WRAPPED_SOL.to_string(),
+            out_token: USDC.to_string(),
+            in_amount,
+            slippage: 1.0,
+        };
+
+        for in_amount in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let err = request(in_amount).endpoint().url(BASE_URL).unwrap_err();
+            assert!(err.to_string().contains("inAmount"), "{}", err);
+        }
+
+        let endpoint = Endpoint::new("/api/v2/raydium/quotes").number("slippage", f64::NAN);
+        assert!(endpoint.url(BASE_URL).is_err());
+        let endpoint =
+            Endpoint::new("/api/v2/system/priority-fee").number("percentile", Some(f64::INFINITY));
+        assert!(endpoint.url(BASE_URL).is_err());
+    }
+}
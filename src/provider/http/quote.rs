@@ -1,7 +1,9 @@
-use crate::provider::utils::convert_string_enums;
+use crate::common::quote::{self, BestQuote, BestQuoteRequest, QuoteSource};
 
+use super::query::HttpGet;
 use super::HTTPClient;
-use anyhow::{anyhow, Result};
+use anyhow::Result;
+use async_trait::async_trait;
 use solana_trader_proto::api;
 
 impl HTTPClient {
@@ -9,80 +11,28 @@ impl HTTPClient {
         &self,
         request: &api::GetRaydiumQuotesRequest,
     ) -> Result<api::GetRaydiumQuotesResponse> {
-        let url = format!(
-            "{}/api/v2/raydium/quotes?inToken={}&outToken={}&inAmount={}&slippage={}",
-            self.base_url, request.in_token, request.out_token, request.in_amount, request.slippage
-        );
-
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| anyhow!("HTTP GET request failed: {}", e))?;
-
-        self.handle_response(response).await
+        self.get(request.endpoint()).await
     }
 
     pub async fn get_raydium_cpmm_quotes(
         &self,
         request: &api::GetRaydiumCpmmQuotesRequest,
     ) -> Result<api::GetRaydiumCpmmQuotesResponse> {
-        let url = format!(
-            "{}/api/v2/raydium/cpmm-quotes?inToken={}&outToken={}&inAmount={}&slippage={}",
-            self.base_url, request.in_token, request.out_token, request.in_amount, request.slippage
-        );
-
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| anyhow::anyhow!("HTTP GET request failed: {}", e))?;
-
-        self.handle_response(response).await
+        self.get(request.endpoint()).await
     }
 
     pub async fn get_raydium_clmm_quotes(
         &self,
         request: &api::GetRaydiumClmmQuotesRequest,
     ) -> Result<api::GetRaydiumClmmQuotesResponse> {
-        let url = format!(
-            "{}/api/v2/raydium/clmm-quotes?inToken={}&outToken={}&inAmount={}&slippage={}",
-            self.base_url, request.in_token, request.out_token, request.in_amount, request.slippage
-        );
-
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| anyhow::anyhow!("HTTP GET request failed: {}", e))?;
-
-        self.handle_response(response).await
+        self.get(request.endpoint()).await
     }
 
     pub async fn get_pump_fun_quotes(
         &self,
         request: &api::GetPumpFunQuotesRequest,
     ) -> Result<api::GetPumpFunQuotesResponse> {
-        let url = format!(
-            "{}/api/v2/pumpfun/quotes?mintAddress={}&quoteType={}&amount={}&bondingCurveAddress={}",
-            self.base_url,
-            request.mint_address,
-            request.quote_type,
-            request.amount,
-            request.bonding_curve_address,
-        );
-
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| anyhow::anyhow!("HTTP GET request failed: {}", e))?;
-
-        self.handle_response(response).await
+        self.get(request.endpoint()).await
     }
 
     // NOTE: Fast mode is not used as of 11/1, breaks the endpoint.
@@ -90,19 +40,7 @@ impl HTTPClient {
         &self,
         request: &api::GetJupiterQuotesRequest,
     ) -> Result<api::GetJupiterQuotesResponse> {
-        let url = format!(
-            "{}/api/v2/jupiter/quotes?inToken={}&outToken={}&inAmount={}&slippage={}",
-            self.base_url, request.in_token, request.out_token, request.in_amount, request.slippage,
-        );
-
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| anyhow::anyhow!("HTTP GET request failed: {}", e))?;
-
-        self.handle_response(response).await
+        self.get(request.endpoint()).await
     }
 
     pub async fn get_quotes(
@@ -114,69 +52,73 @@ impl HTTPClient {
         limit: i32,
         projects: &[api::Project],
     ) -> Result<api::GetQuotesResponse> {
-        let project_params: Vec<String> = projects
-            .iter()
-            .map(|p| format!("&project={}", *p as i32))
-            .collect();
-
-        let url = format!(
-            "{}/api/v1/market/quote?inToken={}&outToken={}&inAmount={}&slippage={}&limit={}{}",
-            self.base_url,
-            in_token,
-            out_token,
+        let request = api::GetQuotesRequest {
+            in_token: in_token.to_string(),
+            out_token: out_token.to_string(),
             in_amount,
             slippage,
             limit,
-            project_params.join("")
-        );
-
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| anyhow::anyhow!("HTTP GET request failed: {}", e))?;
+            projects: projects.iter().map(|p| *p as i32).collect(),
+        };
 
-        let response_text = response.text().await?;
-
-        let mut value: serde_json::Value = serde_json::from_str(&response_text)
-            .map_err(|e| anyhow::anyhow!("Failed to parse response as JSON: {}", e))?;
-
-        convert_string_enums(&mut value);
-
-        serde_json::from_value(value)
-            .map_err(|e| anyhow::anyhow!("Failed to parse response into GetQuotesResponse: {}", e))
+        self.get(request.endpoint()).await
     }
 
     pub async fn get_raydium_prices(
         &self,
         tokens: Vec<String>,
     ) -> Result<api::GetRaydiumPricesResponse> {
-        let mut url = format!("{}/api/v2/raydium/prices?", self.base_url);
-        for (i, token) in tokens.iter().enumerate() {
-            if i > 0 {
-                url.push('&');
-            }
-            url.push_str(&format!("tokens={}", token));
-        }
-
-        let response = self.client.get(&url).send().await?;
-        self.handle_response(response).await
+        self.get(api::GetRaydiumPricesRequest { tokens }.endpoint())
+            .await
     }
 
     pub async fn get_jupiter_prices(
         &self,
         tokens: Vec<String>,
     ) -> Result<api::GetJupiterPricesResponse> {
-        let mut url = format!("{}/api/v2/jupiter/prices?", self.base_url);
-        for (i, token) in tokens.iter().enumerate() {
-            if i > 0 {
-                url.push('&');
-            }
-            url.push_str(&format!("tokens={}", token));
-        }
-
-        let response = self.client.get(&url).send().await?;
-        self.handle_response(response).await
+        self.get(api::GetJupiterPricesRequest { tokens }.endpoint())
+            .await
+    }
+
+    pub async fn best_quote(&self, request: &BestQuoteRequest) -> Result<BestQuote> {
+        quote::best_quote(self, request).await
+    }
+}
+
+#[async_trait]
+impl QuoteSource for HTTPClient {
+    async fn raydium_quotes(
+        &self,
+        request: &api::GetRaydiumQuotesRequest,
+    ) -> Result<api::GetRaydiumQuotesResponse> {
+        self.get_raydium_quotes(request).await
+    }
+
+    async fn raydium_cpmm_quotes(
+        &self,
+        request: &api::GetRaydiumCpmmQuotesRequest,
+    ) -> Result<api::GetRaydiumCpmmQuotesResponse> {
+        self.get_raydium_cpmm_quotes(request).await
+    }
+
+    async fn raydium_clmm_quotes(
+        &self,
+        request: &api::GetRaydiumClmmQuotesRequest,
+    ) -> Result<api::GetRaydiumClmmQuotesResponse> {
+        self.get_raydium_clmm_quotes(request).await
+    }
+
+    async fn jupiter_quotes(
+        &self,
+        request: &api::GetJupiterQuotesRequest,
+    ) -> Result<api::GetJupiterQuotesResponse> {
+        self.get_jupiter_quotes(request).await
+    }
+
+    async fn pump_fun_quotes(
+        &self,
+        request: &api::GetPumpFunQuotesRequest,
+    ) -> Result<api::GetPumpFunQuotesResponse> {
+        self.get_pump_fun_quotes(request).await
     }
 }
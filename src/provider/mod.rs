@@ -1,3 +1,5 @@
+pub mod broadcast;
+pub mod failover;
 pub mod grpc;
 pub mod http;
 pub mod utils;
@@ -1,16 +1,16 @@
 use std::{collections::HashMap, str::FromStr};
 
 use anyhow::Result;
-use base64::{engine::general_purpose, Engine};
 use serde_json::{json, Value};
 use solana_sdk::{
     address_lookup_table::AddressLookupTableAccount,
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
-    transaction::Transaction,
 };
 use solana_trader_proto::api::{self, Project, TransactionMessage, TransactionMessageV2};
 
+use crate::common::transaction::{TransactionBuilder, TransactionFormat};
+
 pub trait IntoTransactionMessage {
     fn into_transaction_message(self) -> TransactionMessage;
 }
@@ -54,82 +54,75 @@ pub fn convert_address_lookup_table(
     Ok(result)
 }
 
-pub fn convert_jupiter_instructions(
-    instructions: &[api::InstructionJupiter],
-) -> Result<Vec<Instruction>> {
-    let mut solana_instructions = Vec::new();
-
-    for inst in instructions {
-        let program_id = Pubkey::from_str(&inst.program_id)?;
+// Instructions as returned by the swap-instructions endpoints, which all share one shape.
+pub trait ApiInstruction {
+    fn to_instruction(&self) -> Result<Instruction>;
+}
 
-        let accounts: Vec<AccountMeta> = inst
-            .accounts
-            .iter()
-            .map(|acc| {
-                let pubkey = Pubkey::from_str(&acc.program_id)?;
-                Ok(AccountMeta {
-                    pubkey,
-                    is_signer: acc.is_signer,
-                    is_writable: acc.is_writable,
-                })
-            })
-            .collect::<Result<Vec<_>>>()?;
-
-        solana_instructions.push(Instruction {
-            program_id,
-            accounts,
-            data: inst.data.clone(),
-        });
+impl ApiInstruction for Instruction {
+    fn to_instruction(&self) -> Result<Instruction> {
+        Ok(self.clone())
     }
+}
 
-    Ok(solana_instructions)
+macro_rules! impl_api_instruction {
+    ($($instruction:ty),*) => {
+        $(
+            impl ApiInstruction for $instruction {
+                fn to_instruction(&self) -> Result<Instruction> {
+                    let accounts = self
+                        .accounts
+                        .iter()
+                        .map(|acc| {
+                            Ok(AccountMeta {
+                                pubkey: Pubkey::from_str(&acc.program_id)?,
+                                is_signer: acc.is_signer,
+                                is_writable: acc.is_writable,
+                            })
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+
+                    Ok(Instruction {
+                        program_id: Pubkey::from_str(&self.program_id)?,
+                        accounts,
+                        data: self.data.clone(),
+                    })
+                }
+            }
+        )*
+    };
 }
 
-pub fn convert_raydium_instructions(
-    instructions: &[api::InstructionRaydium],
-) -> Result<Vec<Instruction>> {
-    let mut solana_instructions = Vec::new();
+impl_api_instruction!(api::InstructionJupiter, api::InstructionRaydium);
 
-    for inst in instructions {
-        let program_id = Pubkey::from_str(&inst.program_id)?;
+pub fn convert_instructions<I: ApiInstruction>(instructions: &[I]) -> Result<Vec<Instruction>> {
+    instructions
+        .iter()
+        .map(ApiInstruction::to_instruction)
+        .collect()
+}
 
-        let accounts: Vec<AccountMeta> = inst
-            .accounts
-            .iter()
-            .map(|acc| {
-                let pubkey = Pubkey::from_str(&acc.program_id)?;
-                Ok(AccountMeta {
-                    pubkey,
-                    is_signer: acc.is_signer,
-                    is_writable: acc.is_writable,
-                })
-            })
-            .collect::<Result<Vec<_>>>()?;
-
-        solana_instructions.push(Instruction {
-            program_id,
-            accounts,
-            data: inst.data.clone(),
-        });
-    }
+pub fn convert_jupiter_instructions(
+    instructions: &[api::InstructionJupiter],
+) -> Result<Vec<Instruction>> {
+    convert_instructions(instructions)
+}
 
-    Ok(solana_instructions)
+pub fn convert_raydium_instructions(
+    instructions: &[api::InstructionRaydium],
+) -> Result<Vec<Instruction>> {
+    convert_instructions(instructions)
 }
 
 pub fn create_transaction_message(
     instructions: Vec<Instruction>,
+    payer: &Pubkey,
     recent_blockhash: &str,
 ) -> Result<api::TransactionMessage> {
-    let mut transaction = Transaction::new_with_payer(&instructions, None);
-    transaction.message.recent_blockhash = recent_blockhash.parse()?;
-
-    let serialized = bincode::serialize(&transaction)?;
-    let content = general_purpose::STANDARD.encode(serialized);
-
-    Ok(api::TransactionMessage {
-        content,
-        is_cleanup: false,
-    })
+    TransactionBuilder::new(*payer)
+        .extend(instructions)
+        .format(TransactionFormat::Legacy)
+        .build(recent_blockhash)
 }
 
 pub fn convert_string_enums(value: &mut Value) {
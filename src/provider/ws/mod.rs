@@ -1,4 +1,7 @@
 pub mod general;
+pub mod openbook;
+pub mod phoenix;
+pub mod pools;
 pub mod quote;
 pub mod stream;
 pub mod swap;
@@ -7,9 +10,14 @@ use anyhow::{anyhow, Result};
 use serde_json::json;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::Keypair;
-use solana_trader_proto::api::GetRecentBlockHashResponseV2;
-
-use crate::common::signing::{sign_transaction, SubmitParams};
+use solana_sdk::signer::Signer;
+use solana_trader_proto::api::{self, GetRecentBlockHashResponseV2};
+
+use crate::common::bundle::{Bundle, BundleResult};
+use crate::common::signing::{
+    require_signer, sign_transactions, ClientCapability, SignedTransaction, SubmitOutcome,
+    SubmitParams,
+};
 use crate::common::{get_base_url_from_env, ws_endpoint, BaseConfig};
 use crate::connections::ws::WS;
 
@@ -31,11 +39,33 @@ pub struct WebSocketClient {
 
 impl WebSocketClient {
     pub fn get_keypair(&self) -> Result<&Keypair> {
-        Ok(self.keypair.as_ref().unwrap())
+        Ok(require_signer(self.keypair.as_ref())?)
+    }
+
+    pub fn require_signer(&self) -> Result<()> {
+        self.get_keypair().map(|_| ())
+    }
+
+    pub fn capability(&self) -> ClientCapability {
+        ClientCapability::of(self.keypair.as_ref())
+    }
+
+    pub fn with_keypair(mut self, keypair: Keypair) -> Self {
+        self.public_key = Some(keypair.pubkey());
+        self.keypair = Some(keypair);
+        self
     }
 
     pub async fn new(endpoint: Option<String>) -> Result<Self> {
-        let base = BaseConfig::try_from_env()?;
+        Self::from_config(endpoint, BaseConfig::try_from_env_async().await?).await
+    }
+
+    // Connects without loading any keypair; signing methods return `NoSignerError`.
+    pub async fn new_read_only(endpoint: Option<String>) -> Result<Self> {
+        Self::from_config(endpoint, BaseConfig::read_only_from_env()?).await
+    }
+
+    async fn from_config(endpoint: Option<String>, base: BaseConfig) -> Result<Self> {
         let (default_base_url, secure) = get_base_url_from_env();
         let final_base_url = endpoint.unwrap_or(default_base_url);
         let endpoint = ws_endpoint(&final_base_url, secure);
@@ -44,7 +74,7 @@ impl WebSocketClient {
             return Err(anyhow::anyhow!("AUTH_HEADER is empty"));
         }
 
-        let conn = WS::new(Some(endpoint))
+        let conn = WS::new(Some(endpoint), &base.auth_header)
             .await
             .map_err(|e| anyhow::anyhow!("Connection timeout: {}", e))?;
 
@@ -59,71 +89,110 @@ impl WebSocketClient {
         self.conn.close().await
     }
 
+    pub async fn submit_signed_transaction(
+        &self,
+        signed_tx: &SignedTransaction,
+        submit_opts: &SubmitParams,
+    ) -> Result<String> {
+        let request = json!({
+            "transaction": {
+                "content": signed_tx.content,
+                "isCleanup": signed_tx.is_cleanup
+            },
+            "skipPreFlight": submit_opts.skip_pre_flight,
+            "frontRunningProtection": submit_opts.front_running_protection,
+            "useStakedRPCs": submit_opts.use_staked_rpcs,
+            "fastBestEffort": submit_opts.fast_best_effort
+        });
+
+        let response: serde_json::Value = self.conn.request("PostSubmitV2", request).await?;
+
+        response
+            .get("signature")
+            .and_then(|s| s.as_str())
+            .map(String::from)
+            .ok_or_else(|| anyhow!("Missing signature in response"))
+    }
+
     pub async fn sign_and_submit<T: IntoTransactionMessage + Clone>(
         &self,
         txs: Vec<T>,
         submit_opts: SubmitParams,
         use_bundle: bool,
-    ) -> Result<Vec<String>> {
+    ) -> Result<Vec<SubmitOutcome>> {
         let keypair = self.get_keypair()?;
+        let block_hash = self.latest_block_hash().await?;
 
-        let hash_res: GetRecentBlockHashResponseV2 =
-            self.conn.request("GetRecentBlockHashV2", json!({})).await?;
+        let mut signed_txs = sign_transactions(txs, keypair, &block_hash, &submit_opts).await?;
 
-        if txs.len() == 1 {
-            let signed_tx = sign_transaction(&txs[0], keypair, hash_res.block_hash).await?;
-
-            let request = json!({
-                "transaction": {
-                    "content": signed_tx.content,
-                    "isCleanup": signed_tx.is_cleanup
-                },
-                "skipPreFlight": submit_opts.skip_pre_flight,
-                "frontRunningProtection": submit_opts.front_running_protection,
-                "useStakedRPCs": submit_opts.use_staked_rpcs,
-                "fastBestEffort": submit_opts.fast_best_effort
-            });
-
-            let response: serde_json::Value = self.conn.request("PostSubmitV2", request).await?;
-
-            return Ok(vec![response
-                .get("signature")
-                .and_then(|s| s.as_str())
-                .map(String::from)
-                .ok_or_else(|| anyhow!("Missing signature in response"))?]);
-        }
+        if signed_txs.len() == 1 {
+            let signed_tx = signed_txs.remove(0);
+            let result = self
+                .submit_signed_transaction(&signed_tx, &submit_opts)
+                .await;
 
-        let mut entries = Vec::with_capacity(txs.len());
-        for tx in txs {
-            let signed_tx = sign_transaction(&tx, keypair, hash_res.block_hash.clone()).await?;
-            entries.push(json!({
-                "transaction": {
-                    "content": signed_tx.content,
-                    "isCleanup": signed_tx.is_cleanup
-                },
-                "skipPreFlight": submit_opts.skip_pre_flight,
-                "frontRunningProtection": submit_opts.front_running_protection,
-                "useStakedRPCs": submit_opts.use_staked_rpcs,
-                "fastBestEffort": submit_opts.fast_best_effort
-            }));
+            return Ok(vec![SubmitOutcome::from_single(&signed_tx, result)]);
         }
 
+        let count = signed_txs.len();
+        let response = self
+            .submit_signed_batch(signed_txs, &submit_opts, use_bundle)
+            .await?;
+
+        Ok(SubmitOutcome::from_batch(count, response))
+    }
+
+    pub async fn submit_bundle(
+        &self,
+        bundle: &Bundle,
+        submit_opts: SubmitParams,
+    ) -> Result<BundleResult> {
+        let keypair = self.get_keypair()?;
+        let block_hash = self.latest_block_hash().await?;
+        let signed_txs = bundle.sign(keypair, &block_hash, &submit_opts).await?;
+
+        let response = self
+            .submit_signed_batch(signed_txs, &submit_opts, true)
+            .await?;
+        Ok(bundle.result(response))
+    }
+
+    async fn submit_signed_batch(
+        &self,
+        signed_txs: Vec<SignedTransaction>,
+        submit_opts: &SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<api::PostSubmitBatchResponseEntry>> {
+        let entries: Vec<_> = signed_txs
+            .into_iter()
+            .map(|signed_tx| {
+                json!({
+                    "transaction": {
+                        "content": signed_tx.content,
+                        "isCleanup": signed_tx.is_cleanup
+                    },
+                    "skipPreFlight": submit_opts.skip_pre_flight,
+                    "frontRunningProtection": submit_opts.front_running_protection,
+                    "useStakedRPCs": submit_opts.use_staked_rpcs,
+                    "fastBestEffort": submit_opts.fast_best_effort
+                })
+            })
+            .collect();
+
         let request = json!({
             "entries": entries,
             "useBundle": use_bundle,
             "submitStrategy": submit_opts.submit_strategy
         });
 
-        let response: serde_json::Value = self.conn.request("PostSubmitBatchV2", request).await?;
-
-        let signatures = response["transactions"]
-            .as_array()
-            .ok_or_else(|| anyhow!("Invalid response format"))?
-            .iter()
-            .filter(|entry| entry["submitted"].as_bool().unwrap_or(false))
-            .filter_map(|entry| entry["signature"].as_str().map(String::from))
-            .collect();
+        let response: api::PostSubmitBatchResponse =
+            self.conn.request("PostSubmitBatchV2", request).await?;
+        Ok(response.transactions)
+    }
 
-        Ok(signatures)
+    async fn latest_block_hash(&self) -> Result<String> {
+        let hash_res: GetRecentBlockHashResponseV2 =
+            self.conn.request("GetRecentBlockHashV2", json!({})).await?;
+        Ok(hash_res.block_hash)
     }
 }
@@ -0,0 +1,146 @@
+use anyhow::Result;
+use solana_trader_proto::api;
+
+use crate::common::{
+    clob::{order_transaction, require_order_id, require_transactions, ProjectRequest},
+    signing::{SubmitOutcome, SubmitParams},
+};
+
+use super::WebSocketClient;
+
+impl WebSocketClient {
+    pub async fn post_order(
+        &self,
+        request: &api::PostOrderRequestV2,
+    ) -> Result<api::PostOrderResponse> {
+        let params = serde_json::to_value(request)?;
+
+        self.conn.request("PostOrderV2", params).await
+    }
+
+    pub async fn submit_order(
+        &self,
+        request: api::PostOrderRequestV2,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_order(&request).await?;
+        let txs = order_transaction("PostOrderV2", response)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    pub async fn post_cancel_order(
+        &self,
+        request: &api::PostCancelOrderRequestV2,
+    ) -> Result<api::PostCancelOrderResponseV2> {
+        require_order_id(request)?;
+        let params = serde_json::to_value(request)?;
+
+        self.conn.request("PostCancelOrderV2", params).await
+    }
+
+    pub async fn submit_cancel_order(
+        &self,
+        request: api::PostCancelOrderRequestV2,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_cancel_order(&request).await?;
+        let txs = require_transactions("PostCancelOrderV2", response.transactions)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    // Cancels every open order the owner has on the market; there is no v2 variant.
+    pub async fn post_cancel_all_orders(
+        &self,
+        request: &api::PostCancelAllRequest,
+    ) -> Result<api::PostCancelAllResponse> {
+        let params = serde_json::to_value(request.for_project(api::Project::POpenbook))?;
+
+        self.conn.request("PostCancelAll", params).await
+    }
+
+    pub async fn submit_cancel_all_orders(
+        &self,
+        request: api::PostCancelAllRequest,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_cancel_all_orders(&request).await?;
+        let txs = require_transactions("PostCancelAll", response.transactions)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    pub async fn post_replace_order(
+        &self,
+        request: &api::PostReplaceOrderRequestV2,
+    ) -> Result<api::PostOrderResponse> {
+        let params = serde_json::to_value(request)?;
+
+        self.conn.request("PostReplaceOrderV2", params).await
+    }
+
+    pub async fn submit_replace_order(
+        &self,
+        request: api::PostReplaceOrderRequestV2,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_replace_order(&request).await?;
+        let txs = order_transaction("PostReplaceOrderV2", response)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    pub async fn post_settle(
+        &self,
+        request: &api::PostSettleRequestV2,
+    ) -> Result<api::PostSettleResponse> {
+        let params = serde_json::to_value(request)?;
+
+        self.conn.request("PostSettleV2", params).await
+    }
+
+    pub async fn submit_settle(
+        &self,
+        request: api::PostSettleRequestV2,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_settle(&request).await?;
+        let txs = require_transactions("PostSettleV2", response.transactions)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    pub async fn get_open_orders(
+        &self,
+        request: &api::GetOpenOrdersRequestV2,
+    ) -> Result<api::GetOpenOrdersResponseV2> {
+        let params = serde_json::to_value(request)?;
+
+        self.conn.request("GetOpenOrdersV2", params).await
+    }
+
+    pub async fn get_unsettled(
+        &self,
+        request: &api::GetUnsettledRequestV2,
+    ) -> Result<api::GetUnsettledResponse> {
+        let params = serde_json::to_value(request)?;
+
+        self.conn.request("GetUnsettledV2", params).await
+    }
+}
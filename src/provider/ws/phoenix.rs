@@ -0,0 +1,146 @@
+use anyhow::Result;
+use solana_trader_proto::api;
+
+use crate::common::{
+    clob::{order_transaction, require_transactions, ProjectRequest},
+    signing::{SubmitOutcome, SubmitParams},
+};
+
+use super::WebSocketClient;
+
+// Phoenix is only served by the v1 trade endpoints; every request is sent with the
+// Phoenix project set.
+impl WebSocketClient {
+    pub async fn post_phoenix_order(
+        &self,
+        request: &api::PostOrderRequest,
+    ) -> Result<api::PostOrderResponse> {
+        let params = serde_json::to_value(request.for_project(api::Project::PPhoenix))?;
+
+        self.conn.request("PostOrder", params).await
+    }
+
+    pub async fn submit_phoenix_order(
+        &self,
+        request: api::PostOrderRequest,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_phoenix_order(&request).await?;
+        let txs = order_transaction("PostOrder", response)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    pub async fn post_phoenix_cancel_order(
+        &self,
+        request: &api::PostCancelOrderRequest,
+    ) -> Result<api::PostCancelOrderResponse> {
+        let params = serde_json::to_value(request.for_project(api::Project::PPhoenix))?;
+
+        self.conn.request("PostCancelOrder", params).await
+    }
+
+    pub async fn submit_phoenix_cancel_order(
+        &self,
+        request: api::PostCancelOrderRequest,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_phoenix_cancel_order(&request).await?;
+        let txs = require_transactions("PostCancelOrder", response.transactions)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    pub async fn post_phoenix_cancel_all_orders(
+        &self,
+        request: &api::PostCancelAllRequest,
+    ) -> Result<api::PostCancelAllResponse> {
+        let params = serde_json::to_value(request.for_project(api::Project::PPhoenix))?;
+
+        self.conn.request("PostCancelAll", params).await
+    }
+
+    pub async fn submit_phoenix_cancel_all_orders(
+        &self,
+        request: api::PostCancelAllRequest,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_phoenix_cancel_all_orders(&request).await?;
+        let txs = require_transactions("PostCancelAll", response.transactions)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    pub async fn post_phoenix_replace_order(
+        &self,
+        request: &api::PostReplaceOrderRequest,
+    ) -> Result<api::PostOrderResponse> {
+        let params = serde_json::to_value(request.for_project(api::Project::PPhoenix))?;
+
+        self.conn.request("PostReplaceOrder", params).await
+    }
+
+    pub async fn submit_phoenix_replace_order(
+        &self,
+        request: api::PostReplaceOrderRequest,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_phoenix_replace_order(&request).await?;
+        let txs = order_transaction("PostReplaceOrder", response)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    pub async fn post_phoenix_settle(
+        &self,
+        request: &api::PostSettleRequest,
+    ) -> Result<api::PostSettleResponse> {
+        let params = serde_json::to_value(request.for_project(api::Project::PPhoenix))?;
+
+        self.conn.request("PostSettle", params).await
+    }
+
+    pub async fn submit_phoenix_settle(
+        &self,
+        request: api::PostSettleRequest,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let response = self.post_phoenix_settle(&request).await?;
+        let txs = require_transactions("PostSettle", response.transactions)?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    pub async fn get_phoenix_open_orders(
+        &self,
+        request: &api::GetOpenOrdersRequest,
+    ) -> Result<api::GetOpenOrdersResponse> {
+        let params = serde_json::to_value(request.for_project(api::Project::PPhoenix))?;
+
+        self.conn.request("GetOpenOrders", params).await
+    }
+
+    pub async fn get_phoenix_unsettled(
+        &self,
+        request: &api::GetUnsettledRequest,
+    ) -> Result<api::GetUnsettledResponse> {
+        let params = serde_json::to_value(request.for_project(api::Project::PPhoenix))?;
+
+        self.conn.request("GetUnsettled", params).await
+    }
+}
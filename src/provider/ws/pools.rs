@@ -0,0 +1,66 @@
+use anyhow::Result;
+use solana_trader_proto::api;
+
+use crate::common::pools::{PoolCache, PoolKind};
+
+use super::WebSocketClient;
+
+impl WebSocketClient {
+    pub async fn get_markets(
+        &self,
+        request: &api::GetMarketsRequestV2,
+    ) -> Result<api::GetMarketsResponseV2> {
+        let params = serde_json::to_value(request)?;
+
+        self.conn.request("GetMarketsV2", params).await
+    }
+
+    pub async fn get_raydium_pools(
+        &self,
+        request: &api::GetRaydiumPoolsRequest,
+    ) -> Result<api::GetRaydiumPoolsResponse> {
+        let params = serde_json::to_value(request)?;
+
+        self.conn.request("GetRaydiumPools", params).await
+    }
+
+    pub async fn get_raydium_cpmm_pools(
+        &self,
+        request: &api::GetRaydiumCpmmPoolsRequest,
+    ) -> Result<api::GetRaydiumCpmmPoolsResponse> {
+        let params = serde_json::to_value(request)?;
+
+        self.conn.request("GetRaydiumCPMMPools", params).await
+    }
+
+    pub async fn get_raydium_clmm_pools(
+        &self,
+        request: &api::GetRaydiumClmmPoolsRequest,
+    ) -> Result<api::GetRaydiumClmmPoolsResponse> {
+        let params = serde_json::to_value(request)?;
+
+        self.conn.request("GetRaydiumCLMMPools", params).await
+    }
+
+    pub async fn get_raydium_pool_reserve(
+        &self,
+        request: &api::GetRaydiumPoolReserveRequest,
+    ) -> Result<api::GetRaydiumPoolReserveResponse> {
+        let params = serde_json::to_value(request)?;
+
+        self.conn.request("GetRaydiumPoolReserve", params).await
+    }
+
+    pub async fn load_pool_cache(&self) -> Result<PoolCache> {
+        let mut cache = PoolCache::new();
+
+        let amm = self.get_raydium_pools(&Default::default()).await?;
+        cache.extend(PoolKind::Amm, &amm.pools);
+        let cpmm = self.get_raydium_cpmm_pools(&Default::default()).await?;
+        cache.extend(PoolKind::Cpmm, &cpmm.pools);
+        let clmm = self.get_raydium_clmm_pools(&Default::default()).await?;
+        cache.extend(PoolKind::Clmm, &clmm.pools);
+
+        Ok(cache)
+    }
+}
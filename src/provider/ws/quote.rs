@@ -1,5 +1,8 @@
+use crate::common::quote::{self, BestQuote, BestQuoteRequest, QuoteSource};
+
 use super::WebSocketClient;
 use anyhow::Result;
+use async_trait::async_trait;
 use serde_json::json;
 use solana_trader_proto::api;
 
@@ -90,4 +93,46 @@ impl WebSocketClient {
 
         self.conn.request("GetJupiterPrices", json!(request)).await
     }
+
+    pub async fn best_quote(&self, request: &BestQuoteRequest) -> Result<BestQuote> {
+        quote::best_quote(self, request).await
+    }
+}
+
+#[async_trait]
+impl QuoteSource for WebSocketClient {
+    async fn raydium_quotes(
+        &self,
+        request: &api::GetRaydiumQuotesRequest,
+    ) -> Result<api::GetRaydiumQuotesResponse> {
+        self.get_raydium_quotes(request).await
+    }
+
+    async fn raydium_cpmm_quotes(
+        &self,
+        request: &api::GetRaydiumCpmmQuotesRequest,
+    ) -> Result<api::GetRaydiumCpmmQuotesResponse> {
+        self.get_raydium_cpmm_quotes(request).await
+    }
+
+    async fn raydium_clmm_quotes(
+        &self,
+        request: &api::GetRaydiumClmmQuotesRequest,
+    ) -> Result<api::GetRaydiumClmmQuotesResponse> {
+        self.get_raydium_clmm_quotes(request).await
+    }
+
+    async fn jupiter_quotes(
+        &self,
+        request: &api::GetJupiterQuotesRequest,
+    ) -> Result<api::GetJupiterQuotesResponse> {
+        self.get_jupiter_quotes(request).await
+    }
+
+    async fn pump_fun_quotes(
+        &self,
+        request: &api::GetPumpFunQuotesRequest,
+    ) -> Result<api::GetPumpFunQuotesResponse> {
+        self.get_pump_fun_quotes(request).await
+    }
 }
@@ -1,18 +1,16 @@
-use anyhow::Result;
-use base64::{engine::general_purpose, Engine};
+use anyhow::{anyhow, Result};
 use serde_json::json;
-use solana_sdk::{
-    message::{v0, VersionedMessage},
-    transaction::VersionedTransaction,
-};
+use solana_sdk::signer::Signer;
 use solana_trader_proto::api;
 
 use crate::{
-    common::signing::SubmitParams,
-    provider::utils::{
-        convert_address_lookup_table, convert_jupiter_instructions, convert_raydium_instructions,
-        create_transaction_message,
+    common::{
+        quote::{SwapQuote, SwapRequest, SwapSettings, Venue},
+        signing::{SubmitOutcome, SubmitParams},
+        slippage::{guard_quote_request, trade_swap_venue, SwapGuard},
+        transaction::{TransactionBuilder, TransactionFormat},
     },
+    provider::utils::{convert_address_lookup_table, IntoTransactionMessage},
 };
 
 use super::WebSocketClient;
@@ -77,18 +75,40 @@ impl WebSocketClient {
         request: api::PostRaydiumSwapInstructionsRequest,
         submit_opts: SubmitParams,
         use_bundle: bool,
-    ) -> Result<Vec<String>> {
-        let swap_instructions = self.post_raydium_swap_instructions(&request).await?;
-
-        let instructions = convert_raydium_instructions(&swap_instructions.instructions)?;
-
-        let hash_res: api::GetRecentBlockHashResponseV2 =
-            self.conn.request("GetRecentBlockHashV2", json!({})).await?;
-
-        let tx_message = create_transaction_message(instructions, &hash_res.block_hash)?;
-
-        self.sign_and_submit(vec![tx_message], submit_opts, use_bundle)
-            .await
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let mut guard = SwapGuard::new(&submit_opts, || {
+            guard_quote_request(
+                Venue::Raydium,
+                &request.in_token,
+                &request.out_token,
+                request.in_amount,
+                request.slippage,
+            )
+        })?;
+        guard.start(self).await?;
+
+        let payer = self.get_keypair()?.pubkey();
+
+        loop {
+            let swap_instructions = self.post_raydium_swap_instructions(&request).await?;
+
+            let hash_res: api::GetRecentBlockHashResponseV2 =
+                self.conn.request("GetRecentBlockHashV2", json!({})).await?;
+
+            let tx_message = TransactionBuilder::new(payer)
+                .instructions(&swap_instructions.instructions)?
+                .build(&hash_res.block_hash)?;
+
+            if guard.rebuild(self).await? {
+                continue;
+            }
+
+            return self
+                .sign_and_submit(vec![tx_message], submit_opts, use_bundle)
+                .await;
+        }
     }
 
     pub async fn post_raydium_cpmm_swap(
@@ -200,34 +220,79 @@ impl WebSocketClient {
         request: api::PostJupiterSwapInstructionsRequest,
         submit_opts: SubmitParams,
         use_bundle: bool,
-    ) -> Result<Vec<String>> {
+    ) -> Result<Vec<SubmitOutcome>> {
         let keypair = self.get_keypair()?;
 
-        let swap_instructions = self.post_jupiter_swap_instructions(&request).await?;
-
-        let address_lookup_tables =
-            convert_address_lookup_table(&swap_instructions.address_lookup_table_addresses)?;
-
-        let instructions = convert_jupiter_instructions(&swap_instructions.instructions)?;
+        let mut guard = SwapGuard::new(&submit_opts, || {
+            guard_quote_request(
+                Venue::Jupiter,
+                &request.in_token,
+                &request.out_token,
+                request.in_amount,
+                request.slippage,
+            )
+        })?;
+        guard.start(self).await?;
+
+        loop {
+            let swap_instructions = self.post_jupiter_swap_instructions(&request).await?;
+
+            let address_lookup_tables =
+                convert_address_lookup_table(&swap_instructions.address_lookup_table_addresses)?;
+
+            let hash_res: api::GetRecentBlockHashResponseV2 =
+                self.conn.request("GetRecentBlockHashV2", json!({})).await?;
+
+            let tx_message = TransactionBuilder::new(keypair.pubkey())
+                .instructions(&swap_instructions.instructions)?
+                .lookup_tables(address_lookup_tables)
+                .format(TransactionFormat::V0)
+                .build_signed(&[keypair], &hash_res.block_hash)?;
+
+            if guard.rebuild(self).await? {
+                continue;
+            }
+
+            return self
+                .sign_and_submit(vec![tx_message], submit_opts, use_bundle)
+                .await;
+        }
+    }
 
-        let hash_res: api::GetRecentBlockHashResponseV2 =
-            self.conn.request("GetRecentBlockHashV2", json!({})).await?;
+    pub async fn post_pump_swap(
+        &self,
+        request: &api::PostPumpFunSwapRequest,
+    ) -> Result<api::PostPumpFunSwapResponse> {
+        let params = json!({
+            "userAddress": request.user_address,
+            "bondingCurveAddress": request.bonding_curve_address,
+            "tokenAddress": request.token_address,
+            "tokenAmount": request.token_amount,
+            "solThreshold": request.sol_threshold,
+            "computeLimit": request.compute_limit,
+            "computePrice": request.compute_price,
+            "tip": request.tip,
+            "isBuy": request.is_buy,
+            "slippage": request.slippage,
+        });
 
-        let message = VersionedMessage::V0(v0::Message::try_compile(
-            &self.public_key.unwrap(),
-            &instructions,
-            &address_lookup_tables,
-            hash_res.block_hash.parse()?,
-        )?);
+        self.conn.request("PostPumpFunSwap", params).await
+    }
 
-        let tx = VersionedTransaction::try_new(message, &[keypair])?;
+    pub async fn submit_pump_swap(
+        &self,
+        request: api::PostPumpFunSwapRequest,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
 
-        let tx_message = api::TransactionMessage {
-            content: general_purpose::STANDARD.encode(bincode::serialize(&tx)?),
-            is_cleanup: false,
-        };
+        let response = self.post_pump_swap(&request).await?;
+        let tx = response
+            .transaction
+            .ok_or_else(|| anyhow!("PostPumpFunSwap returned no transaction"))?;
 
-        self.sign_and_submit(vec![tx_message], submit_opts, use_bundle)
+        self.sign_and_submit(vec![tx], submit_opts, use_bundle)
             .await
     }
 
@@ -263,4 +328,128 @@ impl WebSocketClient {
 
         self.conn.request("PostRouteTradeSwap", params).await
     }
+
+    pub async fn submit_trade_swap(
+        &self,
+        request: api::TradeSwapRequest,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let mut guard = SwapGuard::new(&submit_opts, || {
+            guard_quote_request(
+                trade_swap_venue(request.project)?,
+                &request.in_token,
+                &request.out_token,
+                request.in_amount,
+                request.slippage,
+            )
+        })?;
+        guard.start(self).await?;
+
+        loop {
+            let response = self.post_trade_swap(&request).await?;
+
+            if guard.rebuild(self).await? {
+                continue;
+            }
+
+            return self
+                .sign_and_submit(response.transactions, submit_opts, use_bundle)
+                .await;
+        }
+    }
+
+    pub async fn execute_quote<Q: SwapQuote>(
+        &self,
+        quote: &Q,
+        settings: &SwapSettings,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let settings = settings.with_default_owner(self.public_key)?;
+        let request = quote.swap_request(&settings)?;
+
+        self.execute_swap(&request, submit_opts, use_bundle).await
+    }
+
+    pub async fn execute_swap(
+        &self,
+        request: &SwapRequest,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.require_signer()?;
+
+        let txs = self.build_swap(request).await?;
+
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    pub async fn build_swap(&self, request: &SwapRequest) -> Result<Vec<api::TransactionMessage>> {
+        let txs: Vec<api::TransactionMessage> = match request {
+            SwapRequest::Raydium(request) => self
+                .post_raydium_swap(request)
+                .await?
+                .transactions
+                .into_iter()
+                .map(IntoTransactionMessage::into_transaction_message)
+                .collect(),
+            SwapRequest::RaydiumRoute(request) => self
+                .post_raydium_route_swap(request)
+                .await?
+                .transactions
+                .into_iter()
+                .map(IntoTransactionMessage::into_transaction_message)
+                .collect(),
+            SwapRequest::RaydiumCpmm(request) => self
+                .post_raydium_cpmm_swap(request)
+                .await?
+                .transaction
+                .into_iter()
+                .collect(),
+            SwapRequest::RaydiumClmm(request) => self
+                .post_raydium_clmm_swap(request)
+                .await?
+                .transactions
+                .into_iter()
+                .map(IntoTransactionMessage::into_transaction_message)
+                .collect(),
+            SwapRequest::RaydiumClmmRoute(request) => self
+                .post_raydium_clmm_route_swap(request)
+                .await?
+                .transactions
+                .into_iter()
+                .map(IntoTransactionMessage::into_transaction_message)
+                .collect(),
+            SwapRequest::Jupiter(request) => self
+                .post_jupiter_swap(request)
+                .await?
+                .transactions
+                .into_iter()
+                .map(IntoTransactionMessage::into_transaction_message)
+                .collect(),
+            SwapRequest::JupiterRoute(request) => self
+                .post_jupiter_route_swap(request)
+                .await?
+                .transactions
+                .into_iter()
+                .map(IntoTransactionMessage::into_transaction_message)
+                .collect(),
+            SwapRequest::PumpFun(request) => self
+                .post_pump_swap(request)
+                .await?
+                .transaction
+                .into_iter()
+                .collect(),
+        };
+
+        if txs.is_empty() {
+            return Err(anyhow!("Swap request returned no transactions"));
+        }
+        Ok(txs)
+    }
 }
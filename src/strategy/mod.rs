@@ -0,0 +1,188 @@
+pub mod orders;
+pub mod portfolio;
+pub mod pump_tracker;
+pub mod sniper;
+pub mod twap;
+
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use solana_sdk::pubkey::Pubkey;
+use solana_trader_proto::api;
+use tokio::time::{sleep, Instant};
+
+use crate::common::quote::{BestQuote, BestQuoteRequest, SwapRequest};
+use crate::common::signing::{SubmitOutcome, SubmitParams};
+use crate::provider::grpc::GrpcClient;
+use crate::provider::http::HTTPClient;
+use crate::provider::ws::WebSocketClient;
+
+const CONFIRMED_STATUS: &str = "success";
+const FAILED_STATUS: &str = "failed";
+
+#[async_trait]
+pub trait SwapClient: Send + 'static {
+    fn public_key(&self) -> Option<Pubkey>;
+
+    // Strategies check this when they start, so a read-only client fails with
+    // `NoSignerError` before any orders or children are attempted.
+    fn require_signer(&self) -> Result<()>;
+
+    async fn build_swap(&mut self, request: &SwapRequest) -> Result<Vec<api::TransactionMessage>>;
+
+    async fn submit(
+        &mut self,
+        txs: Vec<api::TransactionMessage>,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>>;
+
+    async fn best_quote(&mut self, request: &BestQuoteRequest) -> Result<BestQuote>;
+
+    async fn get_transaction(&mut self, signature: &str) -> Result<api::GetTransactionResponse>;
+}
+
+#[async_trait]
+impl SwapClient for GrpcClient {
+    fn public_key(&self) -> Option<Pubkey> {
+        self.public_key
+    }
+
+    fn require_signer(&self) -> Result<()> {
+        GrpcClient::require_signer(self)
+    }
+
+    async fn build_swap(&mut self, request: &SwapRequest) -> Result<Vec<api::TransactionMessage>> {
+        GrpcClient::build_swap(self, request).await
+    }
+
+    async fn submit(
+        &mut self,
+        txs: Vec<api::TransactionMessage>,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    async fn best_quote(&mut self, request: &BestQuoteRequest) -> Result<BestQuote> {
+        GrpcClient::best_quote(self, request).await
+    }
+
+    async fn get_transaction(&mut self, signature: &str) -> Result<api::GetTransactionResponse> {
+        let request = api::GetTransactionRequest {
+            signature: signature.to_string(),
+        };
+        GrpcClient::get_transaction(self, &request).await
+    }
+}
+
+#[async_trait]
+impl SwapClient for HTTPClient {
+    fn public_key(&self) -> Option<Pubkey> {
+        self.public_key
+    }
+
+    fn require_signer(&self) -> Result<()> {
+        HTTPClient::require_signer(self)
+    }
+
+    async fn build_swap(&mut self, request: &SwapRequest) -> Result<Vec<api::TransactionMessage>> {
+        HTTPClient::build_swap(self, request).await
+    }
+
+    async fn submit(
+        &mut self,
+        txs: Vec<api::TransactionMessage>,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    async fn best_quote(&mut self, request: &BestQuoteRequest) -> Result<BestQuote> {
+        HTTPClient::best_quote(self, request).await
+    }
+
+    async fn get_transaction(&mut self, signature: &str) -> Result<api::GetTransactionResponse> {
+        let request = api::GetTransactionRequest {
+            signature: signature.to_string(),
+        };
+        HTTPClient::get_transaction(self, &request).await
+    }
+}
+
+#[async_trait]
+impl SwapClient for WebSocketClient {
+    fn public_key(&self) -> Option<Pubkey> {
+        self.public_key
+    }
+
+    fn require_signer(&self) -> Result<()> {
+        WebSocketClient::require_signer(self)
+    }
+
+    async fn build_swap(&mut self, request: &SwapRequest) -> Result<Vec<api::TransactionMessage>> {
+        WebSocketClient::build_swap(self, request).await
+    }
+
+    async fn submit(
+        &mut self,
+        txs: Vec<api::TransactionMessage>,
+        submit_opts: SubmitParams,
+        use_bundle: bool,
+    ) -> Result<Vec<SubmitOutcome>> {
+        self.sign_and_submit(txs, submit_opts, use_bundle).await
+    }
+
+    async fn best_quote(&mut self, request: &BestQuoteRequest) -> Result<BestQuote> {
+        WebSocketClient::best_quote(self, request).await
+    }
+
+    async fn get_transaction(&mut self, signature: &str) -> Result<api::GetTransactionResponse> {
+        let request = api::GetTransactionRequest {
+            signature: signature.to_string(),
+        };
+        WebSocketClient::get_transaction(self, request).await
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ConfirmError {
+    #[error("Transaction {0} failed")]
+    Failed(String),
+    // The transaction may still land after this.
+    #[error("Transaction {0} was not confirmed")]
+    TimedOut(String),
+}
+
+pub async fn confirm_transactions<C: SwapClient>(
+    client: &mut C,
+    signatures: &[String],
+    timeout: Duration,
+    poll_interval: Duration,
+) -> Result<(), ConfirmError> {
+    let deadline = Instant::now() + timeout;
+
+    for signature in signatures {
+        loop {
+            // Lookups fail until the transaction lands, so errors are retried until the deadline.
+            let status = client
+                .get_transaction(signature)
+                .await
+                .map(|response| response.status)
+                .unwrap_or_default();
+
+            match status.as_str() {
+                CONFIRMED_STATUS => break,
+                FAILED_STATUS => return Err(ConfirmError::Failed(signature.clone())),
+                _ if Instant::now() >= deadline => {
+                    return Err(ConfirmError::TimedOut(signature.clone()))
+                }
+                _ => sleep(poll_interval).await,
+            }
+        }
+    }
+    Ok(())
+}
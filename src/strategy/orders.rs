@@ -0,0 +1,1215 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, PoisonError};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use futures_util::stream::BoxStream;
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use solana_trader_proto::api;
+use tokio::sync::{mpsc, Mutex, RwLock};
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, Instant};
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
+use tracing::warn;
+
+use crate::common::amounts::{ui_price, SlippageBps, TokenAmount};
+use crate::common::quote::{BestQuoteRequest, SwapQuote, SwapSettings};
+use crate::common::signing::{require_submitted, SubmitParams};
+use crate::provider::grpc::GrpcClient;
+use crate::provider::ws::WebSocketClient;
+
+use super::portfolio::{mark_price, PriceSource};
+use super::{confirm_transactions, ConfirmError, SwapClient};
+
+const REPORT_BUFFER: usize = 1000;
+
+#[async_trait]
+pub trait PriceFeedSource: Send + 'static {
+    async fn token_prices(
+        &mut self,
+        projects: Vec<api::Project>,
+        tokens: Vec<String>,
+    ) -> Result<BoxStream<'static, Result<api::GetPricesStreamResponse>>>;
+
+    async fn pool_reserves(
+        &mut self,
+        projects: Vec<api::Project>,
+        pools: Vec<String>,
+    ) -> Result<BoxStream<'static, Result<api::GetPoolReservesStreamResponse>>>;
+}
+
+#[async_trait]
+impl PriceFeedSource for GrpcClient {
+    async fn token_prices(
+        &mut self,
+        projects: Vec<api::Project>,
+        tokens: Vec<String>,
+    ) -> Result<BoxStream<'static, Result<api::GetPricesStreamResponse>>> {
+        let stream = self.get_prices_stream(projects, tokens).await?;
+        Ok(stream.map(|r| r.map_err(anyhow::Error::from)).boxed())
+    }
+
+    async fn pool_reserves(
+        &mut self,
+        projects: Vec<api::Project>,
+        pools: Vec<String>,
+    ) -> Result<BoxStream<'static, Result<api::GetPoolReservesStreamResponse>>> {
+        let stream = self.get_pool_reserves_stream(projects, pools).await?;
+        Ok(stream.map(|r| r.map_err(anyhow::Error::from)).boxed())
+    }
+}
+
+#[async_trait]
+impl PriceFeedSource for WebSocketClient {
+    async fn token_prices(
+        &mut self,
+        projects: Vec<api::Project>,
+        tokens: Vec<String>,
+    ) -> Result<BoxStream<'static, Result<api::GetPricesStreamResponse>>> {
+        Ok(self.get_prices_stream(projects, tokens).await?.boxed())
+    }
+
+    async fn pool_reserves(
+        &mut self,
+        projects: Vec<api::Project>,
+        pools: Vec<String>,
+    ) -> Result<BoxStream<'static, Result<api::GetPoolReservesStreamResponse>>> {
+        Ok(self
+            .get_pool_reserves_stream(projects, pools)
+            .await?
+            .boxed())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderSide {
+    // Spends the quote token for the base token.
+    Buy,
+    // Spends the base token for the quote token.
+    Sell,
+}
+
+// Prices are always quote tokens per base token.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TriggerCondition {
+    // Buys at or below, or sells at or above, the price.
+    Limit {
+        price: f64,
+    },
+    // Sells at or below, or buys at or above, the price.
+    StopLoss {
+        price: f64,
+    },
+    // Sells at or above, or buys at or below, the price.
+    TakeProfit {
+        price: f64,
+    },
+    // Follows the best price seen: the peak for sells, the trough for buys.
+    TrailingStop {
+        trail_percent: f64,
+        extreme: Option<f64>,
+    },
+}
+
+impl TriggerCondition {
+    // Returns whether the condition fires at `price`, updating the trailing extreme first.
+    pub fn update(&mut self, side: OrderSide, price: f64) -> bool {
+        let (at_or_below, at_or_above) = match (&*self, side) {
+            (TriggerCondition::Limit { price: p }, OrderSide::Buy)
+            | (TriggerCondition::StopLoss { price: p }, OrderSide::Sell)
+            | (TriggerCondition::TakeProfit { price: p }, OrderSide::Buy) => (price <= *p, false),
+            (TriggerCondition::Limit { price: p }, OrderSide::Sell)
+            | (TriggerCondition::StopLoss { price: p }, OrderSide::Buy)
+            | (TriggerCondition::TakeProfit { price: p }, OrderSide::Sell) => (false, price >= *p),
+            (TriggerCondition::TrailingStop { .. }, _) => (false, false),
+        };
+        if at_or_below || at_or_above {
+            return true;
+        }
+
+        let TriggerCondition::TrailingStop {
+            trail_percent,
+            extreme,
+        } = self
+        else {
+            return false;
+        };
+
+        let trail = *trail_percent / 100.0;
+        match side {
+            OrderSide::Sell => {
+                let peak = extreme.map_or(price, |e| e.max(price));
+                *extreme = Some(peak);
+                price <= peak * (1.0 - trail)
+            }
+            OrderSide::Buy => {
+                let trough = extreme.map_or(price, |e| e.min(price));
+                *extreme = Some(trough);
+                price >= trough * (1.0 + trail)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PriceFeed {
+    // Prices stream values are in USD, so token feeds suit orders quoted in a stablecoin.
+    Token {
+        source: PriceSource,
+    },
+    Pool {
+        source: PriceSource,
+        pool_address: String,
+        base_decimals: u8,
+        quote_decimals: u8,
+    },
+}
+
+impl PriceFeed {
+    pub fn source(&self) -> PriceSource {
+        match self {
+            PriceFeed::Token { source } | PriceFeed::Pool { source, .. } => *source,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PriceTick {
+    Token {
+        token: String,
+        price: f64,
+    },
+    Pool {
+        pool_address: String,
+        token1: String,
+        token1_reserves: u64,
+        token2: String,
+        token2_reserves: u64,
+    },
+}
+
+impl PriceTick {
+    pub fn from_prices(response: &api::GetPricesStreamResponse) -> Option<Self> {
+        let price = response.price.as_ref()?;
+        Some(PriceTick::Token {
+            token: price.token.clone(),
+            price: mark_price(price)?,
+        })
+    }
+
+    pub fn from_reserves(response: &api::GetPoolReservesStreamResponse) -> Option<Self> {
+        let reserves = response.reserves.as_ref()?;
+        Some(PriceTick::Pool {
+            pool_address: reserves.pool_address.clone(),
+            token1: reserves.token1_address.clone(),
+            token1_reserves: reserves.token1_reserves,
+            token2: reserves.token2_address.clone(),
+            token2_reserves: reserves.token2_reserves,
+        })
+    }
+
+    // The tick's price for an order on `base_token` with the given feed, if it applies.
+    pub fn price_for(&self, feed: &PriceFeed, base_token: &str) -> Option<f64> {
+        match (self, feed) {
+            (PriceTick::Token { token, price }, PriceFeed::Token { .. }) => {
+                (token == base_token).then_some(*price)
+            }
+            (
+                PriceTick::Pool {
+                    pool_address,
+                    token1,
+                    token1_reserves,
+                    token2,
+                    token2_reserves,
+                },
+                PriceFeed::Pool {
+                    pool_address: feed_pool,
+                    base_decimals,
+                    quote_decimals,
+                    ..
+                },
+            ) if pool_address == feed_pool => {
+                let (base, quote) = if token1 == base_token {
+                    (*token1_reserves, *token2_reserves)
+                } else if token2 == base_token {
+                    (*token2_reserves, *token1_reserves)
+                } else {
+                    return None;
+                };
+                ui_price(
+                    TokenAmount::new(base, *base_decimals),
+                    TokenAmount::new(quote, *quote_decimals),
+                )
+                .ok()
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum OrderStatus {
+    Open,
+    // Fired but not yet submitted. Orders left in this state by a restart are not re-fired,
+    // since the swap may have been sent; they are failed on reload instead.
+    Triggered { price: f64 },
+    // Orders reloaded in this state are confirmed again before they are closed.
+    Submitted { price: f64, signatures: Vec<String> },
+    Filled { price: f64, signatures: Vec<String> },
+    Failed { error: String },
+    Cancelled,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TriggerOrder {
+    pub id: u64,
+    pub base_token: String,
+    pub quote_token: String,
+    pub side: OrderSide,
+    // Amount of the token spent: quote for buys, base for sells.
+    pub in_amount: TokenAmount,
+    pub condition: TriggerCondition,
+    pub feed: PriceFeed,
+    pub slippage: SlippageBps,
+    pub status: OrderStatus,
+    pub created_ms: u128,
+    pub last_price: Option<f64>,
+}
+
+impl TriggerOrder {
+    pub fn new(
+        base_token: &str,
+        quote_token: &str,
+        side: OrderSide,
+        in_amount: TokenAmount,
+        condition: TriggerCondition,
+        feed: PriceFeed,
+        slippage: SlippageBps,
+    ) -> Self {
+        Self {
+            id: 0,
+            base_token: base_token.to_string(),
+            quote_token: quote_token.to_string(),
+            side,
+            in_amount,
+            condition,
+            feed,
+            slippage,
+            status: OrderStatus::Open,
+            created_ms: now_ms(),
+            last_price: None,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.status == OrderStatus::Open
+    }
+
+    pub fn in_token(&self) -> &str {
+        match self.side {
+            OrderSide::Buy => &self.quote_token,
+            OrderSide::Sell => &self.base_token,
+        }
+    }
+
+    pub fn out_token(&self) -> &str {
+        match self.side {
+            OrderSide::Buy => &self.base_token,
+            OrderSide::Sell => &self.quote_token,
+        }
+    }
+
+    pub fn quote_request(&self) -> BestQuoteRequest {
+        BestQuoteRequest {
+            venues: self.feed.source().venues(),
+            ..BestQuoteRequest::new(
+                self.in_token(),
+                self.out_token(),
+                self.in_amount,
+                self.slippage,
+            )
+        }
+    }
+}
+
+// Orders are saved as one JSON document, written to a temporary file, synced and renamed into
+// place, so a crash leaves either the old or the new document.
+#[derive(Debug, Clone)]
+pub struct OrderStore {
+    path: PathBuf,
+    // Version of the last snapshot written; saves can finish out of order on the blocking pool,
+    // and an older snapshot must not replace a newer one.
+    written: Arc<std::sync::Mutex<u64>>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StoredOrders {
+    next_id: u64,
+    orders: Vec<TriggerOrder>,
+}
+
+impl OrderStore {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            written: Arc::default(),
+        }
+    }
+
+    fn load(&self) -> Result<StoredOrders> {
+        if !self.path.exists() {
+            return Ok(StoredOrders::default());
+        }
+        let data = fs::read_to_string(&self.path)?;
+        Ok(serde_json::from_str(&data)?)
+    }
+
+    fn save(&self, version: u64, stored: &StoredOrders) -> Result<()> {
+        let mut written = self.written.lock().unwrap_or_else(PoisonError::into_inner);
+        if version <= *written {
+            return Ok(());
+        }
+
+        let tmp = self.path.with_extension("tmp");
+        let mut file = File::create(&tmp)?;
+        file.write_all(serde_json::to_string_pretty(stored)?.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&tmp, &self.path)?;
+        *written = version;
+        Ok(())
+    }
+}
+
+// A snapshot taken under the manager lock and written after it is released.
+struct PendingSave {
+    store: OrderStore,
+    version: u64,
+    orders: StoredOrders,
+}
+
+impl PendingSave {
+    fn write(&self) -> Result<()> {
+        self.store.save(self.version, &self.orders)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct TriggerManager {
+    orders: BTreeMap<u64, TriggerOrder>,
+    next_id: u64,
+    store: Option<OrderStore>,
+    subscription_dirty: bool,
+    // Changes are written by `flush`; the order manager task saves placed, cancelled, fired and
+    // completed orders right away and trailing extremes once per resubscribe interval.
+    unsaved_changes: bool,
+    saves: u64,
+}
+
+impl TriggerManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_store(store: OrderStore) -> Result<Self> {
+        let stored = store.load()?;
+        Ok(Self {
+            orders: stored.orders.into_iter().map(|o| (o.id, o)).collect(),
+            next_id: stored.next_id,
+            store: Some(store),
+            subscription_dirty: true,
+            unsaved_changes: false,
+            saves: 0,
+        })
+    }
+
+    pub fn order(&self, id: u64) -> Option<&TriggerOrder> {
+        self.orders.get(&id)
+    }
+
+    pub fn orders(&self) -> impl Iterator<Item = &TriggerOrder> {
+        self.orders.values()
+    }
+
+    pub fn place(&mut self, mut order: TriggerOrder) -> Result<u64> {
+        if order.in_amount.is_zero() {
+            return Err(anyhow!("Order amount must be positive"));
+        }
+
+        self.next_id += 1;
+        order.id = self.next_id;
+        order.status = OrderStatus::Open;
+        self.orders.insert(order.id, order);
+        self.subscription_dirty = true;
+        self.unsaved_changes = true;
+        Ok(self.next_id)
+    }
+
+    pub fn cancel(&mut self, id: u64) -> bool {
+        let Some(order) = self.orders.get_mut(&id).filter(|o| o.is_open()) else {
+            return false;
+        };
+        order.status = OrderStatus::Cancelled;
+        self.subscription_dirty = true;
+        self.unsaved_changes = true;
+        true
+    }
+
+    // Marks and returns the open orders whose condition fires on this tick.
+    pub fn on_price(&mut self, tick: &PriceTick) -> Vec<TriggerOrder> {
+        let mut triggered = Vec::new();
+
+        for order in self.orders.values_mut().filter(|o| o.is_open()) {
+            let Some(price) = tick.price_for(&order.feed, &order.base_token) else {
+                continue;
+            };
+
+            let condition = order.condition.clone();
+            order.last_price = Some(price);
+            if order.condition.update(order.side, price) {
+                order.status = OrderStatus::Triggered { price };
+                triggered.push(order.clone());
+            } else if order.condition != condition {
+                self.unsaved_changes = true;
+            }
+        }
+
+        if !triggered.is_empty() {
+            self.subscription_dirty = true;
+            self.unsaved_changes = true;
+        }
+        triggered
+    }
+
+    pub fn submitted(&mut self, id: u64, price: f64, signatures: Vec<String>) -> Result<()> {
+        self.complete(id, OrderStatus::Submitted { price, signatures })
+    }
+
+    // Orders a previous run fired but did not finish.
+    pub fn interrupted(&self) -> Vec<TriggerOrder> {
+        self.orders
+            .values()
+            .filter(|o| {
+                matches!(
+                    o.status,
+                    OrderStatus::Triggered { .. } | OrderStatus::Submitted { .. }
+                )
+            })
+            .cloned()
+            .collect()
+    }
+
+    // Writes the changes made since the last save to the store, if there is one.
+    pub fn flush(&mut self) -> Result<()> {
+        let Some(pending) = self.take_unsaved() else {
+            return Ok(());
+        };
+        let saved = pending.write();
+        self.unsaved_changes |= saved.is_err();
+        saved
+    }
+
+    pub fn complete(&mut self, id: u64, status: OrderStatus) -> Result<()> {
+        let order = self
+            .orders
+            .get_mut(&id)
+            .ok_or_else(|| anyhow!("Unknown order {}", id))?;
+        order.status = status;
+        self.unsaved_changes = true;
+        Ok(())
+    }
+
+    // Token and pool subscriptions for the open orders, when they changed since the last call.
+    pub fn take_subscription_change(&mut self) -> Option<FeedSubscription> {
+        if !std::mem::take(&mut self.subscription_dirty) {
+            return None;
+        }
+
+        let mut subscription = FeedSubscription::default();
+        for order in self.orders.values().filter(|o| o.is_open()) {
+            match &order.feed {
+                PriceFeed::Token { source } => {
+                    subscription.token_projects.insert(source.project() as i32);
+                    subscription.tokens.insert(order.base_token.clone());
+                }
+                PriceFeed::Pool {
+                    source,
+                    pool_address,
+                    ..
+                } => {
+                    subscription.pool_projects.insert(source.project() as i32);
+                    subscription.pools.insert(pool_address.clone());
+                }
+            }
+        }
+        Some(subscription)
+    }
+
+    fn take_unsaved(&mut self) -> Option<PendingSave> {
+        let store = self.store.clone()?;
+        if !std::mem::take(&mut self.unsaved_changes) {
+            return None;
+        }
+
+        self.saves += 1;
+        Some(PendingSave {
+            store,
+            version: self.saves,
+            orders: StoredOrders {
+                next_id: self.next_id,
+                orders: self.orders.values().cloned().collect(),
+            },
+        })
+    }
+}
+
+// Writes the manager's unsaved changes on the blocking pool, so the lock is not held over
+// file I/O. A failed write leaves the changes unsaved for the next attempt.
+async fn save_orders(manager: &RwLock<TriggerManager>) -> Result<()> {
+    let Some(pending) = manager.write().await.take_unsaved() else {
+        return Ok(());
+    };
+
+    let saved = match tokio::task::spawn_blocking(move || pending.write()).await {
+        Ok(saved) => saved,
+        Err(e) => Err(e.into()),
+    };
+    if saved.is_err() {
+        manager.write().await.unsaved_changes = true;
+    }
+    saved
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FeedSubscription {
+    pub token_projects: BTreeSet<i32>,
+    pub tokens: BTreeSet<String>,
+    pub pool_projects: BTreeSet<i32>,
+    pub pools: BTreeSet<String>,
+}
+
+impl FeedSubscription {
+    fn projects(projects: &BTreeSet<i32>) -> Vec<api::Project> {
+        projects
+            .iter()
+            .filter_map(|&p| api::Project::try_from(p).ok())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum OrderEvent {
+    Placed,
+    Cancelled,
+    Triggered { price: f64 },
+    Submitted { signatures: Vec<String> },
+    Filled { signatures: Vec<String> },
+    Failed { error: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OrderReport {
+    pub timestamp_ms: u128,
+    pub order_id: u64,
+    pub event: OrderEvent,
+}
+
+impl OrderReport {
+    fn new(order_id: u64, event: OrderEvent) -> Self {
+        Self {
+            timestamp_ms: now_ms(),
+            order_id,
+            event,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OrderManagerConfig {
+    // Owner falls back to the client's public key when empty; slippage comes from each order.
+    // No tip is paid unless `settings.tip` is set.
+    pub settings: SwapSettings,
+    pub submit_opts: SubmitParams,
+    // Bundles need a tip, so set `settings.tip` along with this.
+    pub use_bundle: bool,
+    pub confirm_timeout: Duration,
+    pub confirm_poll_interval: Duration,
+    // Placed and cancelled orders are batched into one resubscription per interval,
+    // and trailing extremes are saved at the same interval.
+    pub resubscribe_interval: Duration,
+}
+
+impl Default for OrderManagerConfig {
+    fn default() -> Self {
+        Self {
+            settings: SwapSettings {
+                compute_limit: 200_000,
+                compute_price: 100_000,
+                ..Default::default()
+            },
+            submit_opts: SubmitParams::default(),
+            use_bundle: false,
+            confirm_timeout: Duration::from_secs(60),
+            confirm_poll_interval: Duration::from_secs(2),
+            resubscribe_interval: Duration::from_secs(2),
+        }
+    }
+}
+
+pub struct OrderManagerHandle {
+    manager: Arc<RwLock<TriggerManager>>,
+    reports: mpsc::Sender<Result<OrderReport>>,
+    task: JoinHandle<()>,
+}
+
+impl OrderManagerHandle {
+    pub async fn place(&self, order: TriggerOrder) -> Result<u64> {
+        let id = self.manager.write().await.place(order)?;
+        save_orders(&self.manager).await?;
+        let _ = self
+            .reports
+            .send(Ok(OrderReport::new(id, OrderEvent::Placed)))
+            .await;
+        Ok(id)
+    }
+
+    pub async fn cancel(&self, id: u64) -> Result<bool> {
+        let cancelled = self.manager.write().await.cancel(id);
+        save_orders(&self.manager).await?;
+        if cancelled {
+            let _ = self
+                .reports
+                .send(Ok(OrderReport::new(id, OrderEvent::Cancelled)))
+                .await;
+        }
+        Ok(cancelled)
+    }
+
+    pub async fn order(&self, id: u64) -> Option<TriggerOrder> {
+        self.manager.read().await.order(id).cloned()
+    }
+
+    pub async fn orders(&self) -> Vec<TriggerOrder> {
+        self.manager.read().await.orders().cloned().collect()
+    }
+
+    pub fn stop(&self) {
+        self.task.abort();
+    }
+}
+
+impl Drop for OrderManagerHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+pub async fn spawn_order_manager<S, C>(
+    mut source: S,
+    client: C,
+    manager: TriggerManager,
+    config: OrderManagerConfig,
+) -> Result<(
+    OrderManagerHandle,
+    impl Stream<Item = Result<OrderReport>> + Unpin,
+)>
+where
+    S: PriceFeedSource,
+    C: SwapClient,
+{
+    client.require_signer()?;
+    let settings = config.settings.with_default_owner(client.public_key())?;
+    let client = Arc::new(Mutex::new(client));
+    let manager = Arc::new(RwLock::new(manager));
+    let (tx, rx) = mpsc::channel(REPORT_BUFFER);
+
+    let state = manager.clone();
+    let reports = tx.clone();
+    let task = tokio::spawn(async move {
+        let mut prices: Option<BoxStream<'static, Result<api::GetPricesStreamResponse>>> = None;
+        let mut reserves: Option<BoxStream<'static, Result<api::GetPoolReservesStreamResponse>>> =
+            None;
+        let mut ticker = tokio::time::interval(config.resubscribe_interval);
+
+        for order in state.read().await.interrupted() {
+            tokio::spawn(recover_order(
+                order,
+                client.clone(),
+                state.clone(),
+                config.clone(),
+                tx.clone(),
+            ));
+        }
+
+        loop {
+            let tick = tokio::select! {
+                price = next_item(&mut prices) => match price {
+                    Some(Ok(price)) => PriceTick::from_prices(&price),
+                    // The next resubscription reopens the dropped stream.
+                    Some(Err(_)) | None => {
+                        prices = None;
+                        state.write().await.subscription_dirty = true;
+                        None
+                    }
+                },
+                reserve = next_item(&mut reserves) => match reserve {
+                    Some(Ok(reserve)) => PriceTick::from_reserves(&reserve),
+                    Some(Err(_)) | None => {
+                        reserves = None;
+                        state.write().await.subscription_dirty = true;
+                        None
+                    }
+                },
+                // Failed saves and resubscriptions are reported and retried on the next tick.
+                _ = ticker.tick() => {
+                    if let Err(e) = save_orders(&state).await {
+                        warn!(error = %e, "Saving trigger orders failed");
+                        if tx.send(Err(e)).await.is_err() {
+                            return;
+                        }
+                    }
+
+                    let change = state.write().await.take_subscription_change();
+                    if let Some(change) = change {
+                        if let Err(e) = resubscribe(&mut source, &change, &mut prices, &mut reserves).await {
+                            warn!(error = %e, "Resubscribing to order price feeds failed");
+                            state.write().await.subscription_dirty = true;
+                            if tx.send(Err(e)).await.is_err() {
+                                return;
+                            }
+                        }
+                    }
+                    None
+                }
+            };
+
+            let Some(tick) = tick else {
+                continue;
+            };
+            let triggered = state.write().await.on_price(&tick);
+            if triggered.is_empty() {
+                continue;
+            }
+            // Fired orders are filled even if this save fails; the next tick retries it.
+            if let Err(e) = save_orders(&state).await {
+                warn!(error = %e, "Saving trigger orders failed");
+                if tx.send(Err(e)).await.is_err() {
+                    return;
+                }
+            }
+
+            for order in triggered {
+                let price = order.last_price.unwrap_or_default();
+                let report = OrderReport::new(order.id, OrderEvent::Triggered { price });
+                if tx.send(Ok(report)).await.is_err() {
+                    return;
+                }
+
+                tokio::spawn(fill_order(
+                    order,
+                    price,
+                    client.clone(),
+                    state.clone(),
+                    settings.clone(),
+                    config.clone(),
+                    tx.clone(),
+                ));
+            }
+        }
+    });
+
+    Ok((
+        OrderManagerHandle {
+            manager,
+            reports,
+            task,
+        },
+        ReceiverStream::new(rx),
+    ))
+}
+
+async fn resubscribe<S: PriceFeedSource>(
+    source: &mut S,
+    change: &FeedSubscription,
+    prices: &mut Option<BoxStream<'static, Result<api::GetPricesStreamResponse>>>,
+    reserves: &mut Option<BoxStream<'static, Result<api::GetPoolReservesStreamResponse>>>,
+) -> Result<()> {
+    *prices = None;
+    if !change.tokens.is_empty() {
+        let projects = FeedSubscription::projects(&change.token_projects);
+        let tokens = change.tokens.iter().cloned().collect();
+        *prices = Some(source.token_prices(projects, tokens).await?);
+    }
+
+    *reserves = None;
+    if !change.pools.is_empty() {
+        let projects = FeedSubscription::projects(&change.pool_projects);
+        let pools = change.pools.iter().cloned().collect();
+        *reserves = Some(source.pool_reserves(projects, pools).await?);
+    }
+    Ok(())
+}
+
+// The client is locked per call, so one slow confirmation does not hold up other fills.
+async fn fill_order<C: SwapClient>(
+    order: TriggerOrder,
+    price: f64,
+    client: Arc<Mutex<C>>,
+    manager: Arc<RwLock<TriggerManager>>,
+    mut settings: SwapSettings,
+    config: OrderManagerConfig,
+    tx: mpsc::Sender<Result<OrderReport>>,
+) {
+    settings.slippage = order.slippage;
+
+    let result = async {
+        let quote = client
+            .lock()
+            .await
+            .best_quote(&order.quote_request())
+            .await?;
+        let request = quote.swap_request(&settings)?;
+        let txs = client.lock().await.build_swap(&request).await?;
+        let outcomes = client
+            .lock()
+            .await
+            .submit(txs, config.submit_opts.clone(), config.use_bundle)
+            .await?;
+        let signatures = require_submitted(outcomes)?;
+
+        manager
+            .write()
+            .await
+            .submitted(order.id, price, signatures.clone())?;
+        save_orders(&manager).await?;
+        let report = OrderReport::new(
+            order.id,
+            OrderEvent::Submitted {
+                signatures: signatures.clone(),
+            },
+        );
+        let _ = tx.send(Ok(report)).await;
+
+        confirm_shared(&client, &signatures, &config).await?;
+        Ok::<_, anyhow::Error>(signatures)
+    }
+    .await;
+
+    finish_order(order.id, price, result, &manager, &tx).await;
+}
+
+async fn recover_order<C: SwapClient>(
+    order: TriggerOrder,
+    client: Arc<Mutex<C>>,
+    manager: Arc<RwLock<TriggerManager>>,
+    config: OrderManagerConfig,
+    tx: mpsc::Sender<Result<OrderReport>>,
+) {
+    let (price, result) = match order.status {
+        OrderStatus::Submitted { price, signatures } => {
+            let confirmed = confirm_shared(&client, &signatures, &config).await;
+            (price, confirmed.map(|()| signatures).map_err(Into::into))
+        }
+        OrderStatus::Triggered { price } => (
+            price,
+            Err(anyhow!(
+                "Interrupted while filling; the swap may have been sent, check the wallet before placing the order again"
+            )),
+        ),
+        _ => return,
+    };
+
+    finish_order(order.id, price, result, &manager, &tx).await;
+}
+
+async fn finish_order(
+    id: u64,
+    price: f64,
+    result: Result<Vec<String>>,
+    manager: &RwLock<TriggerManager>,
+    tx: &mpsc::Sender<Result<OrderReport>>,
+) {
+    let (status, event) = match result {
+        Ok(signatures) => (
+            OrderStatus::Filled {
+                price,
+                signatures: signatures.clone(),
+            },
+            OrderEvent::Filled { signatures },
+        ),
+        Err(e) => (
+            OrderStatus::Failed {
+                error: e.to_string(),
+            },
+            OrderEvent::Failed {
+                error: e.to_string(),
+            },
+        ),
+    };
+
+    let completed = manager.write().await.complete(id, status);
+    let report = match completed {
+        Ok(()) => save_orders(manager)
+            .await
+            .map(|()| OrderReport::new(id, event)),
+        Err(e) => Err(e),
+    };
+    let _ = tx.send(report).await;
+}
+
+// Polls with the client locked only for each status lookup.
+async fn confirm_shared<C: SwapClient>(
+    client: &Mutex<C>,
+    signatures: &[String],
+    config: &OrderManagerConfig,
+) -> Result<(), ConfirmError> {
+    let deadline = Instant::now() + config.confirm_timeout;
+    loop {
+        let confirmed = confirm_transactions(
+            &mut *client.lock().await,
+            signatures,
+            Duration::ZERO,
+            config.confirm_poll_interval,
+        )
+        .await;
+        match confirmed {
+            Err(ConfirmError::TimedOut(_)) if Instant::now() < deadline => {
+                sleep(config.confirm_poll_interval).await
+            }
+            confirmed => return confirmed,
+        }
+    }
+}
+
+async fn next_item<T>(stream: &mut Option<BoxStream<'static, Result<T>>>) -> Option<Result<T>> {
+    match stream {
+        Some(stream) => stream.next().await,
+        None => std::future::pending().await,
+    }
+}
+
+fn now_ms() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool_feed() -> PriceFeed {
+        PriceFeed::Pool {
+            source: PriceSource::Raydium,
+            pool_address: "pool".to_string(),
+            base_decimals: 9,
+            quote_decimals: 6,
+        }
+    }
+
+    fn pool_tick(sol: u64, usdc: u64) -> PriceTick {
+        PriceTick::Pool {
+            pool_address: "pool".to_string(),
+            token1: "usdc".to_string(),
+            token1_reserves: usdc,
+            token2: "sol".to_string(),
+            token2_reserves: sol,
+        }
+    }
+
+    #[test]
+    fn test_conditions() {
+        let mut stop = TriggerCondition::StopLoss { price: 90.0 };
+        assert!(!stop.update(OrderSide::Sell, 95.0));
+        assert!(stop.update(OrderSide::Sell, 90.0));
+
+        let mut limit = TriggerCondition::Limit { price: 90.0 };
+        assert!(!limit.update(OrderSide::Buy, 95.0));
+        assert!(limit.update(OrderSide::Buy, 89.0));
+
+        let mut take_profit = TriggerCondition::TakeProfit { price: 120.0 };
+        assert!(!take_profit.update(OrderSide::Sell, 119.0));
+        assert!(take_profit.update(OrderSide::Sell, 121.0));
+
+        let mut trailing = TriggerCondition::TrailingStop {
+            trail_percent: 10.0,
+            extreme: None,
+        };
+        assert!(!trailing.update(OrderSide::Sell, 100.0));
+        assert!(!trailing.update(OrderSide::Sell, 150.0));
+        assert!(!trailing.update(OrderSide::Sell, 136.0));
+        assert!(trailing.update(OrderSide::Sell, 135.0));
+        assert_eq!(
+            trailing,
+            TriggerCondition::TrailingStop {
+                trail_percent: 10.0,
+                extreme: Some(150.0)
+            }
+        );
+    }
+
+    #[test]
+    fn test_pool_price_from_reserves() {
+        // 1,000 SOL against 150,000 USDC.
+        let tick = pool_tick(1_000_000_000_000, 150_000_000_000);
+        assert_eq!(tick.price_for(&pool_feed(), "sol"), Some(150.0));
+        assert_eq!(tick.price_for(&pool_feed(), "other"), None);
+        assert_eq!(
+            tick.price_for(
+                &PriceFeed::Token {
+                    source: PriceSource::Raydium
+                },
+                "sol"
+            ),
+            None
+        );
+
+        // 1e9 tokens of a 6 decimal memecoin against 0.5 SOL is 5e-10 SOL per token.
+        let feed = PriceFeed::Pool {
+            source: PriceSource::Raydium,
+            pool_address: "pool".to_string(),
+            base_decimals: 6,
+            quote_decimals: 9,
+        };
+        let tick = PriceTick::Pool {
+            pool_address: "pool".to_string(),
+            token1: "meme".to_string(),
+            token1_reserves: 1_000_000_000_000_000,
+            token2: "sol".to_string(),
+            token2_reserves: 500_000_000,
+        };
+        let price = tick.price_for(&feed, "meme").unwrap();
+        assert!((price - 5e-10).abs() < 1e-22, "{}", price);
+
+        let mut stop = TriggerCondition::StopLoss { price: 4e-10 };
+        assert!(!stop.update(OrderSide::Sell, price));
+    }
+
+    #[test]
+    fn test_manager_triggers_and_persists() {
+        let path = std::env::temp_dir().join(format!("trigger-orders-{}.json", now_ms()));
+        let mut manager = TriggerManager::with_store(OrderStore::new(&path)).unwrap();
+
+        let order = TriggerOrder::new(
+            "sol",
+            "usdc",
+            OrderSide::Sell,
+            TokenAmount::new(1_000_000_000, 9),
+            TriggerCondition::StopLoss { price: 140.0 },
+            pool_feed(),
+            SlippageBps::clamped(100),
+        );
+        assert!(manager
+            .place(TriggerOrder {
+                in_amount: TokenAmount::zero(9),
+                ..order.clone()
+            })
+            .is_err());
+        let id = manager.place(order.clone()).unwrap();
+        let cancelled = manager.place(order).unwrap();
+        assert!(manager.cancel(cancelled));
+
+        let subscription = manager.take_subscription_change().unwrap();
+        assert_eq!(subscription.pools.len(), 1);
+        assert!(manager.take_subscription_change().is_none());
+
+        assert!(manager
+            .on_price(&pool_tick(1_000_000_000_000, 145_000_000_000))
+            .is_empty());
+        let triggered = manager.on_price(&pool_tick(1_000_000_000_000, 139_000_000_000));
+        assert_eq!(triggered.len(), 1);
+        assert_eq!(triggered[0].id, id);
+
+        // Nothing is written until the changes are flushed.
+        assert!(!path.exists());
+        manager.flush().unwrap();
+        let restored = TriggerManager::with_store(OrderStore::new(&path)).unwrap();
+        assert_eq!(
+            restored.order(id).unwrap().status,
+            OrderStatus::Triggered { price: 139.0 }
+        );
+        assert_eq!(
+            restored.order(cancelled).unwrap().status,
+            OrderStatus::Cancelled
+        );
+        assert_eq!(restored.next_id, 2);
+        assert_eq!(restored.interrupted().len(), 1);
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_trailing_extremes_are_saved_on_flush() {
+        let path = std::env::temp_dir().join(format!("trailing-orders-{}.json", now_ms()));
+        let mut manager = TriggerManager::with_store(OrderStore::new(&path)).unwrap();
+
+        let id = manager
+            .place(TriggerOrder::new(
+                "sol",
+                "usdc",
+                OrderSide::Sell,
+                TokenAmount::new(1_000_000_000, 9),
+                TriggerCondition::TrailingStop {
+                    trail_percent: 10.0,
+                    extreme: None,
+                },
+                pool_feed(),
+                SlippageBps::clamped(100),
+            ))
+            .unwrap();
+        manager.flush().unwrap();
+        manager.on_price(&pool_tick(1_000_000_000_000, 150_000_000_000));
+
+        let extreme = |manager: &TriggerManager| match &manager.order(id).unwrap().condition {
+            TriggerCondition::TrailingStop { extreme, .. } => *extreme,
+            _ => unreachable!(),
+        };
+        let stored = TriggerManager::with_store(OrderStore::new(&path)).unwrap();
+        assert_eq!(extreme(&stored), None);
+
+        manager.flush().unwrap();
+        let stored = TriggerManager::with_store(OrderStore::new(&path)).unwrap();
+        assert_eq!(extreme(&stored), Some(150.0));
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_saves_off_the_lock_keep_the_newest_snapshot() {
+        let path = std::env::temp_dir().join(format!("saved-orders-{}.json", now_ms()));
+        let manager = RwLock::new(TriggerManager::with_store(OrderStore::new(&path)).unwrap());
+
+        let order = TriggerOrder::new(
+            "sol",
+            "usdc",
+            OrderSide::Sell,
+            TokenAmount::new(1_000_000_000, 9),
+            TriggerCondition::StopLoss { price: 140.0 },
+            pool_feed(),
+            SlippageBps::clamped(100),
+        );
+        let id = manager.write().await.place(order).unwrap();
+        let older = manager.write().await.take_unsaved().unwrap();
+        manager.write().await.cancel(id);
+        save_orders(&manager).await.unwrap();
+
+        // A slower write of the earlier snapshot does not undo the cancellation.
+        older.write().unwrap();
+        let stored = TriggerManager::with_store(OrderStore::new(&path)).unwrap();
+        assert_eq!(stored.order(id).unwrap().status, OrderStatus::Cancelled);
+        assert!(manager.write().await.take_unsaved().is_none());
+
+        let _ = fs::remove_file(path);
+    }
+}
@@ -0,0 +1,829 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use solana_trader_proto::api;
+
+use super::{CONFIRMED_STATUS, FAILED_STATUS};
+
+use crate::common::amounts::TokenAmount;
+use crate::common::constants::WRAPPED_SOL;
+use crate::common::quote::Venue;
+use crate::common::signing::{require_submitted, SubmitOutcome};
+use crate::provider::grpc::GrpcClient;
+use crate::provider::http::HTTPClient;
+use crate::provider::ws::WebSocketClient;
+
+// Balances below this are treated as closed positions.
+const DUST: f64 = 1e-12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceSource {
+    Raydium,
+    Jupiter,
+}
+
+impl PriceSource {
+    pub fn project(&self) -> api::Project {
+        match self {
+            PriceSource::Raydium => api::Project::PRaydium,
+            PriceSource::Jupiter => api::Project::PJupiter,
+        }
+    }
+
+    pub fn venues(&self) -> Vec<Venue> {
+        match self {
+            PriceSource::Raydium => vec![Venue::Raydium, Venue::RaydiumCpmm, Venue::RaydiumClmm],
+            PriceSource::Jupiter => vec![Venue::Jupiter],
+        }
+    }
+}
+
+#[async_trait]
+pub trait PortfolioSource: Send + 'static {
+    async fn balances(&mut self, owner_address: String) -> Result<api::GetAccountBalanceResponse>;
+
+    async fn token_accounts(
+        &mut self,
+        owner_address: String,
+    ) -> Result<api::GetTokenAccountsResponse>;
+
+    async fn transaction(&mut self, signature: String) -> Result<api::GetTransactionResponse>;
+
+    async fn prices(
+        &mut self,
+        source: PriceSource,
+        tokens: Vec<String>,
+    ) -> Result<Vec<api::TokenPrice>>;
+}
+
+#[async_trait]
+impl PortfolioSource for GrpcClient {
+    async fn balances(&mut self, owner_address: String) -> Result<api::GetAccountBalanceResponse> {
+        self.get_account_balance(owner_address).await
+    }
+
+    async fn token_accounts(
+        &mut self,
+        owner_address: String,
+    ) -> Result<api::GetTokenAccountsResponse> {
+        self.get_token_accounts(owner_address).await
+    }
+
+    async fn transaction(&mut self, signature: String) -> Result<api::GetTransactionResponse> {
+        let request = api::GetTransactionRequest { signature };
+        self.get_transaction(&request).await
+    }
+
+    async fn prices(
+        &mut self,
+        source: PriceSource,
+        tokens: Vec<String>,
+    ) -> Result<Vec<api::TokenPrice>> {
+        match source {
+            PriceSource::Raydium => Ok(self.get_raydium_prices(tokens).await?.token_prices),
+            PriceSource::Jupiter => Ok(self.get_jupiter_prices(tokens).await?.token_prices),
+        }
+    }
+}
+
+#[async_trait]
+impl PortfolioSource for HTTPClient {
+    async fn balances(&mut self, owner_address: String) -> Result<api::GetAccountBalanceResponse> {
+        self.get_account_balance(owner_address).await
+    }
+
+    async fn token_accounts(
+        &mut self,
+        owner_address: String,
+    ) -> Result<api::GetTokenAccountsResponse> {
+        self.get_token_accounts(owner_address).await
+    }
+
+    async fn transaction(&mut self, signature: String) -> Result<api::GetTransactionResponse> {
+        let request = api::GetTransactionRequest { signature };
+        self.get_transaction(&request).await
+    }
+
+    async fn prices(
+        &mut self,
+        source: PriceSource,
+        tokens: Vec<String>,
+    ) -> Result<Vec<api::TokenPrice>> {
+        match source {
+            PriceSource::Raydium => Ok(self.get_raydium_prices(tokens).await?.token_prices),
+            PriceSource::Jupiter => Ok(self.get_jupiter_prices(tokens).await?.token_prices),
+        }
+    }
+}
+
+#[async_trait]
+impl PortfolioSource for WebSocketClient {
+    async fn balances(&mut self, owner_address: String) -> Result<api::GetAccountBalanceResponse> {
+        self.get_account_balance(owner_address).await
+    }
+
+    async fn token_accounts(
+        &mut self,
+        owner_address: String,
+    ) -> Result<api::GetTokenAccountsResponse> {
+        self.get_token_accounts(owner_address).await
+    }
+
+    async fn transaction(&mut self, signature: String) -> Result<api::GetTransactionResponse> {
+        let request = api::GetTransactionRequest { signature };
+        self.get_transaction(request).await
+    }
+
+    async fn prices(
+        &mut self,
+        source: PriceSource,
+        tokens: Vec<String>,
+    ) -> Result<Vec<api::TokenPrice>> {
+        match source {
+            PriceSource::Raydium => Ok(self.get_raydium_prices(tokens).await?.token_prices),
+            PriceSource::Jupiter => Ok(self.get_jupiter_prices(tokens).await?.token_prices),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Position {
+    pub mint: String,
+    pub symbol: String,
+    pub amount: f64,
+    // Total cost of the current amount. Holdings that predate tracking are costed
+    // at the first price seen for them.
+    pub cost_basis: Option<f64>,
+    pub realized_pnl: f64,
+    pub price: Option<f64>,
+}
+
+impl Position {
+    fn new(mint: &str) -> Self {
+        Self {
+            mint: mint.to_string(),
+            symbol: String::new(),
+            amount: 0.0,
+            cost_basis: None,
+            realized_pnl: 0.0,
+            price: None,
+        }
+    }
+
+    pub fn average_cost(&self) -> Option<f64> {
+        let cost_basis = self.cost_basis?;
+        (self.amount > DUST).then(|| cost_basis / self.amount)
+    }
+
+    pub fn market_value(&self) -> Option<f64> {
+        self.price.map(|price| price * self.amount)
+    }
+
+    pub fn unrealized_pnl(&self) -> Option<f64> {
+        Some(self.market_value()? - self.cost_basis?)
+    }
+
+    fn buy(&mut self, amount: f64, cost: f64) {
+        self.cost_basis = Some(self.cost_basis.unwrap_or(0.0) + cost);
+        self.amount += amount;
+    }
+
+    fn sell(&mut self, amount: f64, proceeds: f64) {
+        let amount = amount.min(self.amount);
+        let cost = self.average_cost().unwrap_or(0.0) * amount;
+
+        self.realized_pnl += proceeds - cost;
+        self.amount -= amount;
+        self.cost_basis = self.cost_basis.map(|basis| (basis - cost).max(0.0));
+        if self.amount <= DUST {
+            self.amount = 0.0;
+            self.cost_basis = Some(0.0);
+        }
+    }
+
+    // Balance changes that did not come from a recorded swap (transfers, fees, airdrops)
+    // move the amount at the position's average cost.
+    fn reconcile(&mut self, amount: f64) {
+        match self.average_cost() {
+            Some(average_cost) => self.cost_basis = Some(average_cost * amount),
+            None if amount <= DUST => self.cost_basis = Some(0.0),
+            None => {}
+        }
+        self.amount = amount;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SwapFill {
+    pub in_token: String,
+    pub in_amount: TokenAmount,
+    pub out_token: String,
+    pub out_amount: TokenAmount,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Portfolio {
+    pub owner_address: String,
+    positions: BTreeMap<String, Position>,
+    // Signatures of the swaps already recorded, so a fill is never applied twice.
+    #[serde(skip)]
+    applied: HashSet<String>,
+}
+
+impl Portfolio {
+    pub fn new(owner_address: &str) -> Self {
+        Self {
+            owner_address: owner_address.to_string(),
+            positions: BTreeMap::new(),
+            applied: HashSet::new(),
+        }
+    }
+
+    pub fn position(&self, mint: &str) -> Option<&Position> {
+        self.positions.get(mint)
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = &Position> {
+        self.positions.values()
+    }
+
+    pub fn mints(&self) -> Vec<String> {
+        self.positions.keys().cloned().collect()
+    }
+
+    pub fn apply_balances(&mut self, balances: &api::GetAccountBalanceResponse) {
+        let mut held: HashMap<&str, (&str, f64)> = HashMap::new();
+        for token in &balances.tokens {
+            let entry = held
+                .entry(token.token_mint.as_str())
+                .or_insert((token.symbol.as_str(), 0.0));
+            entry.1 += token.settled_amount;
+        }
+        self.apply_held(&held);
+    }
+
+    // SPL holdings come from the owner's token accounts; only the native SOL
+    // balance is taken from the account balance.
+    pub fn apply_holdings(
+        &mut self,
+        balances: &api::GetAccountBalanceResponse,
+        accounts: &api::GetTokenAccountsResponse,
+    ) {
+        let mut held: HashMap<&str, (&str, f64)> = HashMap::new();
+        for token in balances
+            .tokens
+            .iter()
+            .filter(|token| token.token_mint == WRAPPED_SOL)
+        {
+            let entry = held
+                .entry(token.token_mint.as_str())
+                .or_insert((token.symbol.as_str(), 0.0));
+            entry.1 += token.settled_amount;
+        }
+        for account in &accounts.accounts {
+            let entry = held
+                .entry(account.token_mint.as_str())
+                .or_insert((account.symbol.as_str(), 0.0));
+            entry.1 += account.amount;
+        }
+        self.apply_held(&held);
+    }
+
+    fn apply_held(&mut self, held: &HashMap<&str, (&str, f64)>) {
+        for (mint, (symbol, amount)) in held {
+            let position = self
+                .positions
+                .entry(mint.to_string())
+                .or_insert_with(|| Position::new(mint));
+            position.symbol = symbol.to_string();
+            position.reconcile(*amount);
+        }
+
+        // Positions missing from the snapshot were fully sold or transferred out.
+        for (mint, position) in self.positions.iter_mut() {
+            if !held.contains_key(mint.as_str()) {
+                position.reconcile(0.0);
+            }
+        }
+    }
+
+    pub fn apply_prices(&mut self, prices: &[api::TokenPrice]) {
+        for price in prices {
+            let Some(position) = self.positions.get_mut(&price.token) else {
+                continue;
+            };
+            let Some(mark) = mark_price(price) else {
+                continue;
+            };
+
+            position.price = Some(mark);
+            if position.cost_basis.is_none() {
+                position.cost_basis = Some(mark * position.amount);
+            }
+        }
+    }
+
+    pub fn is_applied(&self, signatures: &[String]) -> bool {
+        signatures
+            .iter()
+            .any(|signature| self.applied.contains(signature))
+    }
+
+    // Values the swap at the last known price of either side, preferring the token spent.
+    // Returns false without changing anything if one of the swap's signatures was
+    // already recorded.
+    pub fn record_swap(&mut self, signatures: &[String], fill: &SwapFill) -> Result<bool> {
+        if signatures.is_empty() {
+            return Err(anyhow!("Swap fill has no signatures"));
+        }
+        if self.is_applied(signatures) {
+            return Ok(false);
+        }
+
+        let in_amount = fill.in_amount.to_ui_f64();
+        let out_amount = fill.out_amount.to_ui_f64();
+        let value = self
+            .price_of(&fill.in_token)
+            .map(|price| price * in_amount)
+            .or_else(|| {
+                self.price_of(&fill.out_token)
+                    .map(|price| price * out_amount)
+            })
+            .ok_or_else(|| {
+                anyhow!(
+                    "No price for {} or {} to value the swap",
+                    fill.in_token,
+                    fill.out_token
+                )
+            })?;
+
+        self.positions
+            .entry(fill.in_token.clone())
+            .or_insert_with(|| Position::new(&fill.in_token))
+            .sell(in_amount, value);
+        self.positions
+            .entry(fill.out_token.clone())
+            .or_insert_with(|| Position::new(&fill.out_token))
+            .buy(out_amount, value);
+        self.applied.extend(signatures.iter().cloned());
+        Ok(true)
+    }
+
+    pub fn snapshot(&self) -> PortfolioSnapshot {
+        let positions: Vec<PositionSnapshot> = self
+            .positions
+            .values()
+            .filter(|p| p.amount > DUST || p.realized_pnl != 0.0)
+            .map(PositionSnapshot::from)
+            .collect();
+
+        PortfolioSnapshot {
+            timestamp_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis())
+                .unwrap_or_default(),
+            owner_address: self.owner_address.clone(),
+            total_value: positions.iter().filter_map(|p| p.market_value).sum(),
+            realized_pnl: positions.iter().map(|p| p.realized_pnl).sum(),
+            unrealized_pnl: positions.iter().filter_map(|p| p.unrealized_pnl).sum(),
+            positions,
+        }
+    }
+
+    fn price_of(&self, mint: &str) -> Option<f64> {
+        self.positions.get(mint).and_then(|p| p.price)
+    }
+}
+
+pub fn mark_price(price: &api::TokenPrice) -> Option<f64> {
+    match (price.buy > 0.0, price.sell > 0.0) {
+        (true, true) => Some((price.buy + price.sell) / 2.0),
+        (true, false) => Some(price.buy),
+        (false, true) => Some(price.sell),
+        (false, false) => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PositionSnapshot {
+    pub mint: String,
+    pub symbol: String,
+    pub amount: f64,
+    pub price: Option<f64>,
+    pub market_value: Option<f64>,
+    pub cost_basis: Option<f64>,
+    pub average_cost: Option<f64>,
+    pub realized_pnl: f64,
+    pub unrealized_pnl: Option<f64>,
+}
+
+impl From<&Position> for PositionSnapshot {
+    fn from(position: &Position) -> Self {
+        Self {
+            mint: position.mint.clone(),
+            symbol: position.symbol.clone(),
+            amount: position.amount,
+            price: position.price,
+            market_value: position.market_value(),
+            cost_basis: position.cost_basis,
+            average_cost: position.average_cost(),
+            realized_pnl: position.realized_pnl,
+            unrealized_pnl: position.unrealized_pnl(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PortfolioSnapshot {
+    pub timestamp_ms: u128,
+    pub owner_address: String,
+    pub total_value: f64,
+    pub realized_pnl: f64,
+    pub unrealized_pnl: f64,
+    pub positions: Vec<PositionSnapshot>,
+}
+
+impl PortfolioSnapshot {
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "timestamp_ms,mint,symbol,amount,price,market_value,cost_basis,average_cost,realized_pnl,unrealized_pnl\n",
+        );
+
+        for p in &self.positions {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{},{},{},{},{}",
+                self.timestamp_ms,
+                csv_field(&p.mint),
+                csv_field(&p.symbol),
+                p.amount,
+                optional(p.price),
+                optional(p.market_value),
+                optional(p.cost_basis),
+                optional(p.average_cost),
+                p.realized_pnl,
+                optional(p.unrealized_pnl),
+            );
+        }
+        csv
+    }
+
+    pub fn write_json<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
+    pub fn write_csv<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        fs::write(path, self.to_csv())?;
+        Ok(())
+    }
+}
+
+fn optional(value: Option<f64>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PendingSwap {
+    fill: SwapFill,
+    signatures: Vec<String>,
+}
+
+pub struct PortfolioTracker<C: PortfolioSource> {
+    client: C,
+    price_source: PriceSource,
+    portfolio: Portfolio,
+    pending: Vec<PendingSwap>,
+}
+
+impl<C: PortfolioSource> PortfolioTracker<C> {
+    pub fn new(client: C, owner_address: &str, price_source: PriceSource) -> Self {
+        Self {
+            client,
+            price_source,
+            portfolio: Portfolio::new(owner_address),
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn portfolio(&self) -> &Portfolio {
+        &self.portfolio
+    }
+
+    pub fn client(&mut self) -> &mut C {
+        &mut self.client
+    }
+
+    pub async fn refresh(&mut self) -> Result<PortfolioSnapshot> {
+        self.settle_pending().await;
+
+        let owner_address = self.portfolio.owner_address.clone();
+        let balances = self.client.balances(owner_address.clone()).await?;
+        let accounts = self.client.token_accounts(owner_address).await?;
+        self.portfolio.apply_holdings(&balances, &accounts);
+
+        let mints = self.portfolio.mints();
+        if !mints.is_empty() {
+            let prices = self.client.prices(self.price_source, mints).await?;
+            self.portfolio.apply_prices(&prices);
+        }
+
+        Ok(self.portfolio.snapshot())
+    }
+
+    // Takes the outcomes of the submitted swap. The fill is only applied once every
+    // transaction is confirmed, and dropped if any of them fails. Swaps that are
+    // already pending or recorded are ignored.
+    pub fn track_swap(&mut self, fill: SwapFill, outcomes: Vec<SubmitOutcome>) -> Result<()> {
+        let signatures = require_submitted(outcomes)?;
+        if signatures.is_empty() {
+            return Err(anyhow!("Swap has no submitted transactions"));
+        }
+        let pending = self.pending.iter().any(|swap| {
+            swap.signatures
+                .iter()
+                .any(|signature| signatures.contains(signature))
+        });
+        if pending || self.portfolio.is_applied(&signatures) {
+            return Ok(());
+        }
+        self.pending.push(PendingSwap { fill, signatures });
+        Ok(())
+    }
+
+    pub fn pending_swaps(&self) -> usize {
+        self.pending.len()
+    }
+
+    async fn settle_pending(&mut self) {
+        let mut pending = Vec::new();
+        for swap in std::mem::take(&mut self.pending) {
+            match self.swap_status(&swap.signatures).await {
+                SwapStatus::Confirmed => {
+                    // Unpriced fills still land in the balances, just without a cost basis.
+                    let _ = self.portfolio.record_swap(&swap.signatures, &swap.fill);
+                }
+                SwapStatus::Failed => {}
+                SwapStatus::Pending => pending.push(swap),
+            }
+        }
+        self.pending = pending;
+    }
+
+    async fn swap_status(&mut self, signatures: &[String]) -> SwapStatus {
+        let mut status = SwapStatus::Confirmed;
+        for signature in signatures {
+            // Lookups fail until the transaction lands.
+            let status_text = self
+                .client
+                .transaction(signature.clone())
+                .await
+                .map(|response| response.status)
+                .unwrap_or_default();
+            match status_text.as_str() {
+                CONFIRMED_STATUS => {}
+                FAILED_STATUS => return SwapStatus::Failed,
+                _ => status = SwapStatus::Pending,
+            }
+        }
+        status
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SwapStatus {
+    Confirmed,
+    Failed,
+    Pending,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn balance(mint: &str, symbol: &str, amount: f64) -> api::TokenBalance {
+        api::TokenBalance {
+            symbol: symbol.to_string(),
+            token_mint: mint.to_string(),
+            settled_amount: amount,
+            ..Default::default()
+        }
+    }
+
+    fn price(mint: &str, price: f64) -> api::TokenPrice {
+        api::TokenPrice {
+            token: mint.to_string(),
+            buy: price,
+            sell: price,
+            ..Default::default()
+        }
+    }
+
+    fn fill(in_token: &str, in_amount: f64, out_token: &str, out_amount: f64) -> SwapFill {
+        SwapFill {
+            in_token: in_token.to_string(),
+            in_amount: TokenAmount::from_ui_f64(in_amount, 9).unwrap(),
+            out_token: out_token.to_string(),
+            out_amount: TokenAmount::from_ui_f64(out_amount, 5).unwrap(),
+        }
+    }
+
+    fn signatures(signature: &str) -> Vec<String> {
+        vec![signature.to_string()]
+    }
+
+    #[test]
+    fn test_cost_basis_and_pnl() {
+        let mut portfolio = Portfolio::new("owner");
+        portfolio.apply_balances(&api::GetAccountBalanceResponse {
+            tokens: vec![balance("sol", "SOL", 10.0)],
+        });
+        portfolio.apply_prices(&[price("sol", 100.0)]);
+
+        let sol = portfolio.position("sol").unwrap();
+        assert_eq!(sol.cost_basis, Some(1000.0));
+        assert_eq!(sol.unrealized_pnl(), Some(0.0));
+
+        portfolio
+            .record_swap(&signatures("buy"), &fill("sol", 2.0, "bonk", 1000.0))
+            .unwrap();
+        portfolio.apply_prices(&[price("sol", 150.0), price("bonk", 0.3)]);
+
+        let bonk = portfolio.position("bonk").unwrap();
+        assert_eq!(bonk.cost_basis, Some(200.0));
+        assert_eq!(bonk.average_cost(), Some(0.2));
+        assert_eq!(bonk.unrealized_pnl(), Some(100.0));
+
+        portfolio
+            .record_swap(&signatures("sell"), &fill("bonk", 500.0, "sol", 1.0))
+            .unwrap();
+        // Seeing the same swap again, e.g. from a tracked submission, changes nothing.
+        assert!(!portfolio
+            .record_swap(&signatures("sell"), &fill("bonk", 500.0, "sol", 1.0))
+            .unwrap());
+
+        let bonk = portfolio.position("bonk").unwrap();
+        assert_eq!(bonk.amount, 500.0);
+        assert_eq!(bonk.realized_pnl, 50.0);
+        assert_eq!(bonk.cost_basis, Some(100.0));
+
+        let snapshot = portfolio.snapshot();
+        assert_eq!(snapshot.realized_pnl, 50.0);
+        assert_eq!(snapshot.positions.len(), 2);
+    }
+
+    #[test]
+    fn test_balances_reconcile_at_average_cost() {
+        let mut portfolio = Portfolio::new("owner");
+        portfolio.apply_balances(&api::GetAccountBalanceResponse {
+            tokens: vec![balance("sol", "SOL", 4.0)],
+        });
+        portfolio.apply_prices(&[price("sol", 50.0)]);
+
+        portfolio.apply_balances(&api::GetAccountBalanceResponse {
+            tokens: vec![balance("sol", "SOL", 2.0)],
+        });
+        let sol = portfolio.position("sol").unwrap();
+        assert_eq!(sol.cost_basis, Some(100.0));
+
+        portfolio.apply_balances(&api::GetAccountBalanceResponse { tokens: vec![] });
+        assert_eq!(portfolio.position("sol").unwrap().amount, 0.0);
+        assert!(portfolio.snapshot().positions.is_empty());
+    }
+
+    #[test]
+    fn test_holdings_from_token_accounts() {
+        let mut portfolio = Portfolio::new("owner");
+        let account = |mint: &str, amount: f64| api::TokenAccount {
+            symbol: "BONK".to_string(),
+            token_mint: mint.to_string(),
+            amount,
+            ..Default::default()
+        };
+
+        portfolio.apply_holdings(
+            &api::GetAccountBalanceResponse {
+                tokens: vec![
+                    balance(WRAPPED_SOL, "SOL", 3.0),
+                    balance("bonk", "BONK", 9.0),
+                ],
+            },
+            &api::GetTokenAccountsResponse {
+                accounts: vec![account("bonk", 100.0), account("bonk", 50.0)],
+            },
+        );
+
+        assert_eq!(portfolio.position(WRAPPED_SOL).unwrap().amount, 3.0);
+        assert_eq!(portfolio.position("bonk").unwrap().amount, 150.0);
+    }
+
+    struct StatusSource {
+        statuses: HashMap<String, String>,
+    }
+
+    #[async_trait]
+    impl PortfolioSource for StatusSource {
+        async fn balances(&mut self, _: String) -> Result<api::GetAccountBalanceResponse> {
+            Ok(api::GetAccountBalanceResponse {
+                tokens: vec![balance(WRAPPED_SOL, "SOL", 8.0)],
+            })
+        }
+
+        async fn token_accounts(&mut self, _: String) -> Result<api::GetTokenAccountsResponse> {
+            Ok(api::GetTokenAccountsResponse::default())
+        }
+
+        async fn transaction(&mut self, signature: String) -> Result<api::GetTransactionResponse> {
+            let status = self
+                .statuses
+                .get(&signature)
+                .ok_or_else(|| anyhow!("not found"))?;
+            Ok(api::GetTransactionResponse {
+                status: status.clone(),
+                ..Default::default()
+            })
+        }
+
+        async fn prices(&mut self, _: PriceSource, _: Vec<String>) -> Result<Vec<api::TokenPrice>> {
+            Ok(vec![price(WRAPPED_SOL, 100.0)])
+        }
+    }
+
+    fn submitted(signature: &str) -> Vec<SubmitOutcome> {
+        vec![SubmitOutcome::submitted(0, signature.to_string())]
+    }
+
+    #[tokio::test]
+    async fn test_tracked_swaps_apply_once_confirmed() {
+        let source = StatusSource {
+            statuses: HashMap::from([("failed".to_string(), FAILED_STATUS.to_string())]),
+        };
+        let mut tracker = PortfolioTracker::new(source, "owner", PriceSource::Raydium);
+        tracker.refresh().await.unwrap();
+
+        let fill = fill(WRAPPED_SOL, 2.0, "bonk", 1000.0);
+        tracker.track_swap(fill.clone(), submitted("sig")).unwrap();
+        tracker.track_swap(fill.clone(), submitted("sig")).unwrap();
+        tracker
+            .track_swap(fill.clone(), submitted("failed"))
+            .unwrap();
+
+        let mut rejected = submitted("rejected");
+        rejected[0].submitted = false;
+        assert!(tracker.track_swap(fill.clone(), rejected).is_err());
+
+        tracker.refresh().await.unwrap();
+        assert_eq!(tracker.pending_swaps(), 1);
+        assert!(tracker.portfolio().position("bonk").is_none());
+
+        tracker
+            .client()
+            .statuses
+            .insert("sig".to_string(), CONFIRMED_STATUS.to_string());
+        tracker.refresh().await.unwrap();
+        assert_eq!(tracker.pending_swaps(), 0);
+        assert!(tracker.portfolio().is_applied(&signatures("sig")));
+        tracker.track_swap(fill, submitted("sig")).unwrap();
+        assert_eq!(tracker.pending_swaps(), 0);
+        // The fill sets the cost basis; the refreshed balances then close the position.
+        let bonk = tracker.portfolio().position("bonk").unwrap();
+        assert_eq!(bonk.amount, 0.0);
+        assert_eq!(
+            tracker.portfolio().position(WRAPPED_SOL).unwrap().amount,
+            8.0
+        );
+    }
+
+    #[test]
+    fn test_snapshot_csv() {
+        let mut portfolio = Portfolio::new("owner");
+        portfolio.apply_balances(&api::GetAccountBalanceResponse {
+            tokens: vec![balance("mint", "A,B", 1.5)],
+        });
+
+        let csv = portfolio.snapshot().to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("timestamp_ms,mint,symbol"));
+        assert!(lines[1].ends_with(",mint,\"A,B\",1.5,,,,,0,"));
+    }
+}
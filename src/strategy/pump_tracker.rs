@@ -0,0 +1,634 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use futures_util::stream::BoxStream;
+use futures_util::StreamExt;
+use solana_trader_proto::api;
+use tokio::sync::{mpsc, RwLock};
+use tokio::task::JoinHandle;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
+
+use crate::common::pump::{BondingCurve, PUMP_FUN_TOKEN_DECIMALS};
+use crate::provider::grpc::GrpcClient;
+use crate::provider::ws::WebSocketClient;
+
+const EVENT_BUFFER: usize = 1000;
+const SEEN_SWAPS: usize = 4096;
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+#[async_trait]
+pub trait PumpFunEventSource: Send + 'static {
+    async fn new_tokens(
+        &mut self,
+    ) -> Result<BoxStream<'static, Result<api::GetPumpFunNewTokensStreamResponse>>>;
+
+    async fn swaps(
+        &mut self,
+        tokens: Vec<String>,
+    ) -> Result<BoxStream<'static, Result<api::GetPumpFunSwapsStreamResponse>>>;
+}
+
+#[async_trait]
+impl PumpFunEventSource for GrpcClient {
+    async fn new_tokens(
+        &mut self,
+    ) -> Result<BoxStream<'static, Result<api::GetPumpFunNewTokensStreamResponse>>> {
+        let stream = self.get_pump_fun_new_tokens_stream().await?;
+        Ok(stream.map(|r| r.map_err(anyhow::Error::from)).boxed())
+    }
+
+    async fn swaps(
+        &mut self,
+        tokens: Vec<String>,
+    ) -> Result<BoxStream<'static, Result<api::GetPumpFunSwapsStreamResponse>>> {
+        let stream = self.get_pump_fun_swaps_stream(tokens).await?;
+        Ok(stream.map(|r| r.map_err(anyhow::Error::from)).boxed())
+    }
+}
+
+#[async_trait]
+impl PumpFunEventSource for WebSocketClient {
+    async fn new_tokens(
+        &mut self,
+    ) -> Result<BoxStream<'static, Result<api::GetPumpFunNewTokensStreamResponse>>> {
+        Ok(self.get_pump_fun_new_tokens_stream().await?.boxed())
+    }
+
+    async fn swaps(
+        &mut self,
+        tokens: Vec<String>,
+    ) -> Result<BoxStream<'static, Result<api::GetPumpFunSwapsStreamResponse>>> {
+        Ok(self.get_pump_fun_swaps_stream(tokens).await?.boxed())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PumpTrackerConfig {
+    pub migration_threshold_percent: f64,
+    pub volume_spike_sol: f64,
+    pub volume_window: Duration,
+    pub max_tracked_tokens: usize,
+    pub idle_timeout: Duration,
+    // New tokens are batched into one swaps resubscription per interval.
+    pub resubscribe_interval: Duration,
+}
+
+impl Default for PumpTrackerConfig {
+    fn default() -> Self {
+        Self {
+            migration_threshold_percent: 95.0,
+            volume_spike_sol: 10.0,
+            volume_window: Duration::from_secs(60),
+            max_tracked_tokens: 200,
+            idle_timeout: Duration::from_secs(30 * 60),
+            resubscribe_interval: Duration::from_secs(2),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TokenState {
+    pub mint: String,
+    pub creator: String,
+    pub bonding_curve: String,
+    pub name: String,
+    pub symbol: String,
+    pub buys: u64,
+    pub sells: u64,
+    pub buy_volume_sol: f64,
+    pub sell_volume_sol: f64,
+    pub traders: HashSet<String>,
+    pub price_sol: f64,
+    pub market_cap_sol: f64,
+    pub curve_progress_percent: f64,
+    pub migration_ready: bool,
+    pub created_at: Instant,
+    pub last_activity: Instant,
+    recent_volume: VecDeque<(Instant, f64)>,
+    last_spike: Option<Instant>,
+}
+
+impl TokenState {
+    pub fn unique_traders(&self) -> usize {
+        self.traders.len()
+    }
+
+    pub fn net_volume_sol(&self) -> f64 {
+        self.buy_volume_sol - self.sell_volume_sol
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum PumpTrackerEvent {
+    TokenCreated {
+        mint: String,
+        creator: String,
+        symbol: String,
+    },
+    Trade {
+        mint: String,
+        trader: String,
+        is_buy: bool,
+        sol_amount: f64,
+        token_amount: f64,
+        market_cap_sol: f64,
+    },
+    MigrationReady {
+        mint: String,
+        curve_progress_percent: f64,
+        market_cap_sol: f64,
+    },
+    VolumeSpike {
+        mint: String,
+        volume_sol: f64,
+        window: Duration,
+    },
+    TokenDropped {
+        mint: String,
+    },
+}
+
+pub struct PumpTracker {
+    config: PumpTrackerConfig,
+    tokens: HashMap<String, TokenState>,
+    order: VecDeque<String>,
+    subscription_dirty: bool,
+    // Swaps delivered by both streams while a resubscription overlaps.
+    seen_swaps: HashSet<(String, String)>,
+    seen_order: VecDeque<(String, String)>,
+}
+
+impl PumpTracker {
+    pub fn new(config: PumpTrackerConfig) -> Self {
+        Self {
+            config,
+            tokens: HashMap::new(),
+            order: VecDeque::new(),
+            subscription_dirty: false,
+            seen_swaps: HashSet::new(),
+            seen_order: VecDeque::new(),
+        }
+    }
+
+    pub fn token(&self, mint: &str) -> Option<&TokenState> {
+        self.tokens.get(mint)
+    }
+
+    pub fn tokens(&self) -> impl Iterator<Item = &TokenState> {
+        self.tokens.values()
+    }
+
+    pub fn tracked_mints(&self) -> Vec<String> {
+        self.order.iter().cloned().collect()
+    }
+
+    pub fn on_new_token(
+        &mut self,
+        token: &api::GetPumpFunNewTokensStreamResponse,
+    ) -> Vec<PumpTrackerEvent> {
+        self.on_new_token_at(token, Instant::now())
+    }
+
+    pub fn on_new_token_at(
+        &mut self,
+        token: &api::GetPumpFunNewTokensStreamResponse,
+        now: Instant,
+    ) -> Vec<PumpTrackerEvent> {
+        if token.mint.is_empty() || self.tokens.contains_key(&token.mint) {
+            return Vec::new();
+        }
+
+        let mut events = Vec::new();
+        while self.order.len() >= self.config.max_tracked_tokens.max(1) {
+            if let Some(mint) = self.order.pop_front() {
+                self.tokens.remove(&mint);
+                events.push(PumpTrackerEvent::TokenDropped { mint });
+            }
+        }
+
+        self.tokens.insert(
+            token.mint.clone(),
+            TokenState {
+                mint: token.mint.clone(),
+                creator: token.creator.clone(),
+                bonding_curve: token.bonding_curve.clone(),
+                name: token.name.clone(),
+                symbol: token.symbol.clone(),
+                buys: 0,
+                sells: 0,
+                buy_volume_sol: 0.0,
+                sell_volume_sol: 0.0,
+                traders: HashSet::new(),
+                price_sol: 0.0,
+                market_cap_sol: 0.0,
+                curve_progress_percent: 0.0,
+                migration_ready: false,
+                created_at: now,
+                last_activity: now,
+                recent_volume: VecDeque::new(),
+                last_spike: None,
+            },
+        );
+        self.order.push_back(token.mint.clone());
+        self.subscription_dirty = true;
+
+        events.push(PumpTrackerEvent::TokenCreated {
+            mint: token.mint.clone(),
+            creator: token.creator.clone(),
+            symbol: token.symbol.clone(),
+        });
+        events
+    }
+
+    pub fn on_swap(&mut self, swap: &api::GetPumpFunSwapsStreamResponse) -> Vec<PumpTrackerEvent> {
+        self.on_swap_at(swap, Instant::now())
+    }
+
+    pub fn on_swap_at(
+        &mut self,
+        swap: &api::GetPumpFunSwapsStreamResponse,
+        now: Instant,
+    ) -> Vec<PumpTrackerEvent> {
+        if !self.tokens.contains_key(&swap.mint_address) || !self.mark_seen(swap) {
+            return Vec::new();
+        }
+
+        let config = &self.config;
+        let Some(state) = self.tokens.get_mut(&swap.mint_address) else {
+            return Vec::new();
+        };
+
+        let sol_amount = swap.sol_amount as f64 / LAMPORTS_PER_SOL;
+        let token_amount = swap.token_amount as f64 / 10f64.powi(PUMP_FUN_TOKEN_DECIMALS as i32);
+
+        if swap.is_buy {
+            state.buys += 1;
+            state.buy_volume_sol += sol_amount;
+        } else {
+            state.sells += 1;
+            state.sell_volume_sol += sol_amount;
+        }
+        state.traders.insert(swap.user_address.clone());
+        state.last_activity = now;
+
+        let curve = BondingCurve::new(swap.virtual_sol_reserves, swap.virtual_token_reserves);
+        if swap.virtual_token_reserves > 0 {
+            state.price_sol = curve.price_in_sol();
+            state.market_cap_sol = curve.market_cap_sol();
+            state.curve_progress_percent = curve.progress_percent();
+        }
+
+        let mut events = vec![PumpTrackerEvent::Trade {
+            mint: state.mint.clone(),
+            trader: swap.user_address.clone(),
+            is_buy: swap.is_buy,
+            sol_amount,
+            token_amount,
+            market_cap_sol: state.market_cap_sol,
+        }];
+
+        if !state.migration_ready
+            && state.curve_progress_percent >= config.migration_threshold_percent
+        {
+            state.migration_ready = true;
+            events.push(PumpTrackerEvent::MigrationReady {
+                mint: state.mint.clone(),
+                curve_progress_percent: state.curve_progress_percent,
+                market_cap_sol: state.market_cap_sol,
+            });
+        }
+
+        state.recent_volume.push_back((now, sol_amount));
+        while let Some(&(at, _)) = state.recent_volume.front() {
+            if now.duration_since(at) <= config.volume_window {
+                break;
+            }
+            state.recent_volume.pop_front();
+        }
+
+        let volume_sol: f64 = state.recent_volume.iter().map(|(_, sol)| sol).sum();
+        let cooled_down = !matches!(
+            state.last_spike,
+            Some(at) if now.duration_since(at) <= config.volume_window
+        );
+        if volume_sol >= config.volume_spike_sol && cooled_down {
+            state.last_spike = Some(now);
+            events.push(PumpTrackerEvent::VolumeSpike {
+                mint: state.mint.clone(),
+                volume_sol,
+                window: config.volume_window,
+            });
+        }
+
+        events
+    }
+
+    pub fn prune(&mut self) -> Vec<PumpTrackerEvent> {
+        self.prune_at(Instant::now())
+    }
+
+    pub fn prune_at(&mut self, now: Instant) -> Vec<PumpTrackerEvent> {
+        let idle_timeout = self.config.idle_timeout;
+        let expired: Vec<String> = self
+            .tokens
+            .values()
+            .filter(|state| now.duration_since(state.last_activity) > idle_timeout)
+            .map(|state| state.mint.clone())
+            .collect();
+
+        if expired.is_empty() {
+            return Vec::new();
+        }
+
+        for mint in &expired {
+            self.tokens.remove(mint);
+        }
+        self.order.retain(|mint| self.tokens.contains_key(mint));
+        self.subscription_dirty = true;
+
+        expired
+            .into_iter()
+            .map(|mint| PumpTrackerEvent::TokenDropped { mint })
+            .collect()
+    }
+
+    fn mark_seen(&mut self, swap: &api::GetPumpFunSwapsStreamResponse) -> bool {
+        if swap.txn_hash.is_empty() {
+            return true;
+        }
+
+        let key = (swap.txn_hash.clone(), swap.mint_address.clone());
+        if !self.seen_swaps.insert(key.clone()) {
+            return false;
+        }
+        self.seen_order.push_back(key);
+        while self.seen_order.len() > SEEN_SWAPS {
+            if let Some(old) = self.seen_order.pop_front() {
+                self.seen_swaps.remove(&old);
+            }
+        }
+        true
+    }
+
+    fn take_subscription_change(&mut self) -> Option<Vec<String>> {
+        if !self.subscription_dirty {
+            return None;
+        }
+        self.subscription_dirty = false;
+        Some(self.tracked_mints())
+    }
+}
+
+pub struct PumpTrackerHandle {
+    tracker: Arc<RwLock<PumpTracker>>,
+    task: JoinHandle<()>,
+}
+
+impl PumpTrackerHandle {
+    pub async fn token(&self, mint: &str) -> Option<TokenState> {
+        self.tracker.read().await.token(mint).cloned()
+    }
+
+    pub async fn tokens(&self) -> Vec<TokenState> {
+        self.tracker.read().await.tokens().cloned().collect()
+    }
+
+    pub fn stop(&self) {
+        self.task.abort();
+    }
+}
+
+impl Drop for PumpTrackerHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+pub async fn spawn_pump_tracker<S: PumpFunEventSource>(
+    mut source: S,
+    config: PumpTrackerConfig,
+) -> Result<(
+    PumpTrackerHandle,
+    impl Stream<Item = Result<PumpTrackerEvent>> + Unpin,
+)> {
+    let mut new_tokens = source.new_tokens().await?;
+    let resubscribe_interval = config.resubscribe_interval;
+    let tracker = Arc::new(RwLock::new(PumpTracker::new(config)));
+    let (tx, rx) = mpsc::channel(EVENT_BUFFER);
+
+    let state = tracker.clone();
+    let task = tokio::spawn(async move {
+        let mut swaps: Option<SwapStream> = None;
+        // The replaced stream keeps delivering until the new one yields, so
+        // no swaps are lost while the subscription changes.
+        let mut draining: Option<SwapStream> = None;
+        let mut ticker = tokio::time::interval(resubscribe_interval);
+
+        loop {
+            let events = tokio::select! {
+                token = new_tokens.next() => match token {
+                    Some(Ok(token)) => state.write().await.on_new_token(&token),
+                    Some(Err(e)) => {
+                        let _ = tx.send(Err(e)).await;
+                        return;
+                    }
+                    None => return,
+                },
+                swap = next_swap(&mut swaps) => match swap {
+                    Some(Ok(swap)) => {
+                        draining = None;
+                        state.write().await.on_swap(&swap)
+                    }
+                    // The next tick resubscribes with the current token set.
+                    Some(Err(_)) | None => {
+                        swaps = None;
+                        state.write().await.subscription_dirty = true;
+                        Vec::new()
+                    }
+                },
+                swap = next_swap(&mut draining) => match swap {
+                    Some(Ok(swap)) => state.write().await.on_swap(&swap),
+                    Some(Err(_)) | None => {
+                        draining = None;
+                        Vec::new()
+                    }
+                },
+                _ = ticker.tick() => {
+                    let (events, change) = {
+                        let mut tracker = state.write().await;
+                        (tracker.prune(), tracker.take_subscription_change())
+                    };
+
+                    match change {
+                        Some(mints) if mints.is_empty() => {
+                            swaps = None;
+                            draining = None;
+                        }
+                        Some(mints) => match source.swaps(mints).await {
+                            Ok(stream) => {
+                                if let Some(current) = swaps.replace(stream) {
+                                    draining = Some(current);
+                                }
+                            }
+                            Err(e) => {
+                                let _ = tx.send(Err(e)).await;
+                                return;
+                            }
+                        },
+                        None => {}
+                    }
+                    events
+                }
+            };
+
+            for event in events {
+                if tx.send(Ok(event)).await.is_err() {
+                    return;
+                }
+            }
+        }
+    });
+
+    Ok((PumpTrackerHandle { tracker, task }, ReceiverStream::new(rx)))
+}
+
+type SwapStream = BoxStream<'static, Result<api::GetPumpFunSwapsStreamResponse>>;
+
+async fn next_swap(
+    swaps: &mut Option<SwapStream>,
+) -> Option<Result<api::GetPumpFunSwapsStreamResponse>> {
+    match swaps {
+        Some(stream) => stream.next().await,
+        None => std::future::pending().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::pump::{
+        PUMP_FUN_INITIAL_REAL_TOKEN_RESERVES, PUMP_FUN_INITIAL_VIRTUAL_TOKEN_RESERVES,
+    };
+
+    fn new_token(mint: &str) -> api::GetPumpFunNewTokensStreamResponse {
+        api::GetPumpFunNewTokensStreamResponse {
+            mint: mint.to_string(),
+            creator: "creator".to_string(),
+            symbol: "TEST".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn swap(
+        mint: &str,
+        user: &str,
+        is_buy: bool,
+        lamports: u64,
+        vt: u64,
+    ) -> api::GetPumpFunSwapsStreamResponse {
+        api::GetPumpFunSwapsStreamResponse {
+            mint_address: mint.to_string(),
+            user_address: user.to_string(),
+            is_buy,
+            sol_amount: lamports,
+            token_amount: 1_000_000,
+            virtual_sol_reserves: 30_000_000_000,
+            virtual_token_reserves: vt,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_tracks_trades_and_traders() {
+        let mut tracker = PumpTracker::new(PumpTrackerConfig::default());
+        let now = Instant::now();
+        tracker.on_new_token_at(&new_token("mint"), now);
+
+        let vt = PUMP_FUN_INITIAL_VIRTUAL_TOKEN_RESERVES;
+        tracker.on_swap_at(&swap("mint", "a", true, 1_000_000_000, vt), now);
+        tracker.on_swap_at(&swap("mint", "a", false, 500_000_000, vt), now);
+        tracker.on_swap_at(&swap("mint", "b", true, 1_000_000_000, vt), now);
+        assert!(tracker
+            .on_swap_at(&swap("other", "c", true, 1, vt), now)
+            .is_empty());
+
+        let state = tracker.token("mint").unwrap();
+        assert_eq!(state.buys, 2);
+        assert_eq!(state.sells, 1);
+        assert_eq!(state.unique_traders(), 2);
+        assert_eq!(state.net_volume_sol(), 1.5);
+        assert!(state.market_cap_sol > 0.0);
+    }
+
+    #[test]
+    fn test_threshold_events_fire_once() {
+        let config = PumpTrackerConfig {
+            volume_spike_sol: 2.0,
+            ..Default::default()
+        };
+        let mut tracker = PumpTracker::new(config);
+        let now = Instant::now();
+        tracker.on_new_token_at(&new_token("mint"), now);
+
+        let near_complete = PUMP_FUN_INITIAL_VIRTUAL_TOKEN_RESERVES
+            - PUMP_FUN_INITIAL_REAL_TOKEN_RESERVES * 96 / 100;
+        let events =
+            tracker.on_swap_at(&swap("mint", "a", true, 3_000_000_000, near_complete), now);
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, PumpTrackerEvent::MigrationReady { .. })));
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, PumpTrackerEvent::VolumeSpike { .. })));
+
+        let events =
+            tracker.on_swap_at(&swap("mint", "b", true, 3_000_000_000, near_complete), now);
+        assert_eq!(events.len(), 1);
+    }
+
+    #[test]
+    fn test_ignores_swaps_seen_on_both_streams() {
+        let mut tracker = PumpTracker::new(PumpTrackerConfig::default());
+        let now = Instant::now();
+        tracker.on_new_token_at(&new_token("mint"), now);
+
+        let vt = PUMP_FUN_INITIAL_VIRTUAL_TOKEN_RESERVES;
+        let mut trade = swap("mint", "a", true, 1_000_000_000, vt);
+        trade.txn_hash = "sig".to_string();
+        assert_eq!(tracker.on_swap_at(&trade, now).len(), 1);
+        assert!(tracker.on_swap_at(&trade, now).is_empty());
+
+        trade.txn_hash = "other".to_string();
+        assert_eq!(tracker.on_swap_at(&trade, now).len(), 1);
+        assert_eq!(tracker.token("mint").unwrap().buys, 2);
+    }
+
+    #[test]
+    fn test_evicts_and_resubscribes() {
+        let config = PumpTrackerConfig {
+            max_tracked_tokens: 2,
+            idle_timeout: Duration::from_secs(10),
+            ..Default::default()
+        };
+        let mut tracker = PumpTracker::new(config);
+        let now = Instant::now();
+
+        tracker.on_new_token_at(&new_token("a"), now);
+        tracker.on_new_token_at(&new_token("b"), now);
+        let events = tracker.on_new_token_at(&new_token("c"), now);
+        assert!(matches!(&events[0], PumpTrackerEvent::TokenDropped { mint } if mint == "a"));
+        assert_eq!(
+            tracker.take_subscription_change(),
+            Some(vec!["b".to_string(), "c".to_string()])
+        );
+        assert_eq!(tracker.take_subscription_change(), None);
+
+        let dropped = tracker.prune_at(now + Duration::from_secs(11));
+        assert_eq!(dropped.len(), 2);
+        assert_eq!(tracker.take_subscription_change(), Some(vec![]));
+    }
+}
@@ -0,0 +1,835 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use futures_util::stream::BoxStream;
+use futures_util::StreamExt;
+use serde::Serialize;
+use solana_trader_proto::api;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
+
+use crate::common::amounts::{Lamports, SlippageBps};
+use crate::common::constants::WRAPPED_SOL;
+use crate::common::pools::PoolKind;
+use crate::common::quote::{SwapRequest, SwapSettings};
+use crate::common::signing::{submitted_signatures, SubmitParams};
+use crate::provider::grpc::GrpcClient;
+use crate::provider::ws::WebSocketClient;
+
+use super::SwapClient;
+
+const RECORD_BUFFER: usize = 1000;
+// Raydium AMM v4 swap fee.
+const RAYDIUM_AMM_FEE: f64 = 0.0025;
+
+#[async_trait]
+pub trait NewPoolSource: Send + 'static {
+    async fn new_pools(
+        &mut self,
+        include_cpmm: bool,
+    ) -> Result<BoxStream<'static, Result<api::GetNewRaydiumPoolsResponse>>>;
+}
+
+#[async_trait]
+impl NewPoolSource for GrpcClient {
+    async fn new_pools(
+        &mut self,
+        include_cpmm: bool,
+    ) -> Result<BoxStream<'static, Result<api::GetNewRaydiumPoolsResponse>>> {
+        let stream = self.get_new_raydium_pools_stream(include_cpmm).await?;
+        Ok(stream.map(|r| r.map_err(anyhow::Error::from)).boxed())
+    }
+}
+
+#[async_trait]
+impl NewPoolSource for WebSocketClient {
+    async fn new_pools(
+        &mut self,
+        include_cpmm: bool,
+    ) -> Result<BoxStream<'static, Result<api::GetNewRaydiumPoolsResponse>>> {
+        Ok(self
+            .get_new_raydium_pools_stream(include_cpmm)
+            .await?
+            .boxed())
+    }
+}
+
+// On-chain signals the pools stream does not carry. Anything left as None is
+// treated according to `SniperConfig::unknown_signals`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PoolSignals {
+    pub creator: Option<String>,
+    pub lp_burned: Option<bool>,
+    pub freezable: Option<bool>,
+    // Needed to pin non-CPMM buys to the detected pool.
+    pub base_decimals: Option<u8>,
+}
+
+#[async_trait]
+pub trait PoolInspector: Send + Sync + 'static {
+    async fn inspect(&self, candidate: &PoolCandidate) -> Result<PoolSignals>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnknownSignal {
+    Allow,
+    Reject,
+}
+
+#[derive(Debug, Clone)]
+pub struct SniperConfig {
+    // Quote mints a new pool must be paired with, and their decimals. Only
+    // SOL-quoted pools are bought, since buys are sized in SOL.
+    pub quote_tokens: HashMap<String, u8>,
+    pub min_quote_liquidity: f64,
+    pub max_quote_liquidity: Option<f64>,
+    pub creator_blacklist: HashSet<String>,
+    pub mint_blacklist: HashSet<String>,
+    pub require_lp_burned: bool,
+    // Pools whose freeze authority is unknown are rejected too, so `spawn_sniper`
+    // fails if this is set without an inspector.
+    pub reject_freezable: bool,
+    pub unknown_signals: UnknownSignal,
+    // Buy size, capped at `max_pool_share_percent` of the pool's SOL reserves.
+    pub buy_amount: Lamports,
+    pub max_pool_share_percent: f64,
+    pub slippage: SlippageBps,
+    pub compute_limit: u32,
+    pub compute_price: u64,
+    // No tip is paid unless this is set.
+    pub tip: Option<Lamports>,
+    // Buys are only built and logged unless this is set.
+    pub submit: bool,
+    pub submit_opts: SubmitParams,
+    // Bundles need a tip, so set `tip` along with this.
+    pub use_bundle: bool,
+    pub include_cpmm: bool,
+    pub max_snipes: Option<usize>,
+}
+
+impl Default for SniperConfig {
+    fn default() -> Self {
+        Self {
+            quote_tokens: HashMap::from([(WRAPPED_SOL.to_string(), 9)]),
+            min_quote_liquidity: 0.0,
+            max_quote_liquidity: None,
+            creator_blacklist: HashSet::new(),
+            mint_blacklist: HashSet::new(),
+            require_lp_burned: false,
+            reject_freezable: false,
+            unknown_signals: UnknownSignal::Allow,
+            buy_amount: Lamports(10_000_000),
+            max_pool_share_percent: 1.0,
+            slippage: SlippageBps::clamped(1000),
+            compute_limit: 200_000,
+            compute_price: 100_000,
+            tip: None,
+            submit: false,
+            submit_opts: SubmitParams::default(),
+            use_bundle: false,
+            include_cpmm: true,
+            max_snipes: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PoolCandidate {
+    pub slot: u64,
+    pub pool_address: String,
+    pub pool: String,
+    pub cpmm: bool,
+    pub base_mint: Option<String>,
+    pub quote_mint: Option<String>,
+    pub quote_liquidity: Option<f64>,
+    pub base_reserves: Option<u64>,
+    pub signals: PoolSignals,
+}
+
+impl PoolCandidate {
+    pub fn from_response(
+        response: &api::GetNewRaydiumPoolsResponse,
+        quote_tokens: &HashMap<String, u8>,
+    ) -> Result<Self> {
+        let pool = response
+            .pool
+            .as_ref()
+            .ok_or_else(|| anyhow!("New pool response without pool"))?;
+        let kind = PoolKind::from_label(&pool.pool)
+            .ok_or_else(|| anyhow!("Unknown pool type {:?}", pool.pool))?;
+
+        let quote = [
+            (
+                &pool.token2_mint_address,
+                pool.token2_reserves,
+                &pool.token1_mint_address,
+            ),
+            (
+                &pool.token1_mint_address,
+                pool.token1_reserves,
+                &pool.token2_mint_address,
+            ),
+        ]
+        .into_iter()
+        .find_map(|(quote, reserves, base)| {
+            let decimals = quote_tokens.get(quote)?;
+            let liquidity = reserves as f64 / 10f64.powi(*decimals as i32);
+            Some((quote.clone(), liquidity, base.clone()))
+        });
+
+        let (quote_mint, quote_liquidity, base_mint) = match quote {
+            Some((quote, liquidity, base)) => (Some(quote), Some(liquidity), Some(base)),
+            None => (None, None, None),
+        };
+        let base_reserves = base_mint.as_ref().map(|base| {
+            if *base == pool.token1_mint_address {
+                pool.token1_reserves
+            } else {
+                pool.token2_reserves
+            }
+        });
+
+        Ok(Self {
+            slot: response.slot,
+            pool_address: pool.pool_address.clone(),
+            pool: pool.pool.clone(),
+            cpmm: kind == PoolKind::Cpmm,
+            base_mint,
+            quote_mint,
+            quote_liquidity,
+            base_reserves,
+            signals: PoolSignals::default(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum SkipReason {
+    AlreadySeen,
+    NoQuoteToken,
+    NotSolQuoted,
+    MintBlacklisted,
+    CreatorBlacklisted,
+    LiquidityTooLow { quote_liquidity: f64 },
+    LiquidityTooHigh { quote_liquidity: f64 },
+    LpNotBurned,
+    Freezable,
+    UnknownSignal { signal: String },
+    BuyTooSmall,
+    SnipeLimitReached,
+    InspectionFailed { error: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "decision", rename_all = "snake_case")]
+pub enum SnipeDecision {
+    Buy { in_amount: f64 },
+    Skip(SkipReason),
+}
+
+pub struct Sniper {
+    config: SniperConfig,
+    seen: HashSet<String>,
+    snipes: usize,
+}
+
+impl Sniper {
+    pub fn new(config: SniperConfig) -> Self {
+        Self {
+            config,
+            seen: HashSet::new(),
+            snipes: 0,
+        }
+    }
+
+    pub fn config(&self) -> &SniperConfig {
+        &self.config
+    }
+
+    pub fn snipes(&self) -> usize {
+        self.snipes
+    }
+
+    pub fn candidate(&self, response: &api::GetNewRaydiumPoolsResponse) -> Result<PoolCandidate> {
+        PoolCandidate::from_response(response, &self.config.quote_tokens)
+    }
+
+    // Filters that need no on-chain lookups, checked before any inspector is called.
+    pub fn prefilter(&self, candidate: &PoolCandidate) -> Option<SkipReason> {
+        if self.seen.contains(&candidate.pool_address) {
+            return Some(SkipReason::AlreadySeen);
+        }
+        if matches!(self.config.max_snipes, Some(max) if self.snipes >= max) {
+            return Some(SkipReason::SnipeLimitReached);
+        }
+
+        let (Some(base_mint), Some(liquidity)) = (&candidate.base_mint, candidate.quote_liquidity)
+        else {
+            return Some(SkipReason::NoQuoteToken);
+        };
+        if candidate.quote_mint.as_deref() != Some(WRAPPED_SOL) {
+            return Some(SkipReason::NotSolQuoted);
+        }
+
+        if self.config.mint_blacklist.contains(base_mint) {
+            return Some(SkipReason::MintBlacklisted);
+        }
+        if liquidity < self.config.min_quote_liquidity {
+            return Some(SkipReason::LiquidityTooLow {
+                quote_liquidity: liquidity,
+            });
+        }
+        if matches!(self.config.max_quote_liquidity, Some(max) if liquidity > max) {
+            return Some(SkipReason::LiquidityTooHigh {
+                quote_liquidity: liquidity,
+            });
+        }
+        None
+    }
+
+    // Every evaluated pool is marked as seen; a buy counts towards `max_snipes`
+    // whether or not it is later submitted successfully.
+    pub fn evaluate(&mut self, candidate: &PoolCandidate) -> SnipeDecision {
+        let decision = match self.prefilter(candidate) {
+            Some(reason) => SnipeDecision::Skip(reason),
+            None => match self.check_signals(&candidate.signals) {
+                Some(reason) => SnipeDecision::Skip(reason),
+                None => match self.buy_amount(candidate) {
+                    Some(in_amount) => SnipeDecision::Buy { in_amount },
+                    None => SnipeDecision::Skip(SkipReason::BuyTooSmall),
+                },
+            },
+        };
+
+        self.seen.insert(candidate.pool_address.clone());
+        if let SnipeDecision::Buy { .. } = decision {
+            self.snipes += 1;
+        }
+        decision
+    }
+
+    pub fn skip(&mut self, candidate: &PoolCandidate, reason: SkipReason) -> SnipeDecision {
+        self.seen.insert(candidate.pool_address.clone());
+        SnipeDecision::Skip(reason)
+    }
+
+    pub fn buy_amount(&self, candidate: &PoolCandidate) -> Option<f64> {
+        let liquidity = candidate.quote_liquidity?;
+        let cap = liquidity * self.config.max_pool_share_percent / 100.0;
+        let amount = self.config.buy_amount.to_sol().min(cap);
+        (amount > 0.0).then_some(amount)
+    }
+
+    pub fn swap_request(
+        &self,
+        candidate: &PoolCandidate,
+        in_amount: f64,
+        owner_address: &str,
+    ) -> Result<SwapRequest> {
+        let (Some(quote_mint), Some(base_mint)) = (&candidate.quote_mint, &candidate.base_mint)
+        else {
+            return Err(anyhow!(
+                "Pool {} has no configured quote token",
+                candidate.pool_address
+            ));
+        };
+
+        let settings = SwapSettings {
+            owner_address: owner_address.to_string(),
+            slippage: self.config.slippage,
+            compute_limit: self.config.compute_limit,
+            compute_price: self.config.compute_price,
+            tip: self.config.tip,
+        };
+
+        if candidate.cpmm {
+            return Ok(SwapRequest::RaydiumCpmm(api::PostRaydiumCpmmSwapRequest {
+                owner_address: settings.owner_address.clone(),
+                pool_address: candidate.pool_address.clone(),
+                in_token: quote_mint.clone(),
+                out_token: base_mint.clone(),
+                in_amount,
+                slippage: settings.slippage_percent(),
+                compute_limit: settings.compute_limit,
+                compute_price: settings.compute_price,
+                tip: settings.tip_lamports(),
+            }));
+        }
+
+        // A plain swap request lets the router pick any pool, so the buy is
+        // sent as a one-step route through the detected pool instead.
+        let out_amount = expected_out(candidate, in_amount).ok_or_else(|| {
+            anyhow!(
+                "Pool {} has no reserves or base decimals to price the buy",
+                candidate.pool_address
+            )
+        })?;
+        let out_amount_min = out_amount * (1.0 - settings.slippage_percent() / 100.0);
+
+        let request = api::PostRaydiumRouteSwapRequest {
+            owner_address: settings.owner_address.clone(),
+            slippage: settings.slippage_percent(),
+            steps: vec![api::RaydiumRouteStep {
+                in_token: quote_mint.clone(),
+                out_token: base_mint.clone(),
+                in_amount,
+                out_amount,
+                out_amount_min,
+                pool_address: candidate.pool_address.clone(),
+                project: Some(api::StepProject {
+                    label: "Raydium".to_string(),
+                    id: String::new(),
+                }),
+            }],
+            compute_limit: settings.compute_limit,
+            compute_price: settings.compute_price,
+            tip: settings.tip_lamports(),
+        };
+        Ok(SwapRequest::RaydiumRoute(request))
+    }
+
+    fn check_signals(&self, signals: &PoolSignals) -> Option<SkipReason> {
+        let reject_unknown = self.config.unknown_signals == UnknownSignal::Reject;
+        let unknown = |signal: &str| {
+            reject_unknown.then(|| SkipReason::UnknownSignal {
+                signal: signal.to_string(),
+            })
+        };
+
+        match &signals.creator {
+            Some(creator) if self.config.creator_blacklist.contains(creator) => {
+                return Some(SkipReason::CreatorBlacklisted)
+            }
+            None if !self.config.creator_blacklist.is_empty() => {
+                if let Some(reason) = unknown("creator") {
+                    return Some(reason);
+                }
+            }
+            _ => {}
+        }
+
+        if self.config.require_lp_burned {
+            match signals.lp_burned {
+                Some(false) => return Some(SkipReason::LpNotBurned),
+                None => {
+                    if let Some(reason) = unknown("lp_burned") {
+                        return Some(reason);
+                    }
+                }
+                Some(true) => {}
+            }
+        }
+
+        if self.config.reject_freezable {
+            match signals.freezable {
+                Some(true) => return Some(SkipReason::Freezable),
+                // A freezable mint can trap the buy, so this check never lets unknowns through.
+                None => {
+                    return Some(SkipReason::UnknownSignal {
+                        signal: "freezable".to_string(),
+                    })
+                }
+                Some(false) => {}
+            }
+        }
+
+        None
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditRecord {
+    pub timestamp_ms: u128,
+    pub candidate: PoolCandidate,
+    pub decision: SnipeDecision,
+    pub request: Option<serde_json::Value>,
+    pub transactions: usize,
+    pub signatures: Vec<String>,
+    pub error: Option<String>,
+}
+
+impl AuditRecord {
+    pub fn new(candidate: PoolCandidate, decision: SnipeDecision) -> Self {
+        Self {
+            timestamp_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis())
+                .unwrap_or_default(),
+            candidate,
+            decision,
+            request: None,
+            transactions: 0,
+            signatures: Vec::new(),
+            error: None,
+        }
+    }
+}
+
+// Writes one JSON object per line.
+#[derive(Clone)]
+pub struct AuditLog {
+    writer: Arc<Mutex<Box<dyn Write + Send>>>,
+}
+
+impl AuditLog {
+    pub fn new<W: Write + Send + 'static>(writer: W) -> Self {
+        Self {
+            writer: Arc::new(Mutex::new(Box::new(writer))),
+        }
+    }
+
+    pub fn file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self::new(file))
+    }
+
+    pub fn sink() -> Self {
+        Self::new(std::io::sink())
+    }
+
+    pub fn record(&self, record: &AuditRecord) -> Result<()> {
+        let line = serde_json::to_string(record)?;
+        let mut writer = self
+            .writer
+            .lock()
+            .map_err(|_| anyhow!("Audit log lock poisoned"))?;
+        writeln!(writer, "{}", line)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+pub struct SniperHandle {
+    task: JoinHandle<()>,
+}
+
+impl SniperHandle {
+    pub fn stop(&self) {
+        self.task.abort();
+    }
+}
+
+impl Drop for SniperHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+pub async fn spawn_sniper<S, C>(
+    mut source: S,
+    client: C,
+    config: SniperConfig,
+    inspector: Option<Arc<dyn PoolInspector>>,
+    audit: AuditLog,
+) -> Result<(
+    SniperHandle,
+    impl Stream<Item = Result<AuditRecord>> + Unpin,
+)>
+where
+    S: NewPoolSource,
+    C: SwapClient,
+{
+    client.require_signer()?;
+    if config.reject_freezable && inspector.is_none() {
+        return Err(anyhow!(
+            "reject_freezable needs a PoolInspector, otherwise every pool is rejected"
+        ));
+    }
+    let owner_address = client
+        .public_key()
+        .map(|key| key.to_string())
+        .ok_or_else(|| anyhow!("Sniper client has no public key"))?;
+    let mut pools = source.new_pools(config.include_cpmm).await?;
+    let (tx, rx) = mpsc::channel(RECORD_BUFFER);
+
+    let task = tokio::spawn(async move {
+        let mut sniper = Sniper::new(config);
+        let mut client = client;
+
+        while let Some(response) = pools.next().await {
+            let result = match response {
+                Ok(response) => {
+                    process_pool(
+                        &mut sniper,
+                        &mut client,
+                        inspector.as_deref(),
+                        &owner_address,
+                        &response,
+                    )
+                    .await
+                }
+                Err(e) => Err(e),
+            };
+
+            let item = result.and_then(|record| audit.record(&record).map(|_| record));
+            if tx.send(item).await.is_err() {
+                return;
+            }
+        }
+    });
+
+    Ok((SniperHandle { task }, ReceiverStream::new(rx)))
+}
+
+async fn process_pool<C: SwapClient>(
+    sniper: &mut Sniper,
+    client: &mut C,
+    inspector: Option<&dyn PoolInspector>,
+    owner_address: &str,
+    response: &api::GetNewRaydiumPoolsResponse,
+) -> Result<AuditRecord> {
+    let mut candidate = sniper.candidate(response)?;
+
+    // Inspection costs RPC calls, so pools that fail the stream-only filters skip it.
+    if sniper.prefilter(&candidate).is_none() {
+        if let Some(inspector) = inspector {
+            match inspector.inspect(&candidate).await {
+                Ok(signals) => candidate.signals = signals,
+                Err(e) => {
+                    let reason = SkipReason::InspectionFailed {
+                        error: e.to_string(),
+                    };
+                    let decision = sniper.skip(&candidate, reason);
+                    return Ok(AuditRecord::new(candidate, decision));
+                }
+            }
+        }
+    }
+
+    let decision = sniper.evaluate(&candidate);
+    let SnipeDecision::Buy { in_amount } = decision else {
+        return Ok(AuditRecord::new(candidate, decision));
+    };
+
+    let mut record = AuditRecord::new(candidate.clone(), decision);
+    let request = match sniper.swap_request(&candidate, in_amount, owner_address) {
+        Ok(request) => request,
+        Err(e) => {
+            record.error = Some(e.to_string());
+            return Ok(record);
+        }
+    };
+    match serde_json::to_value(&request) {
+        Ok(value) => record.request = Some(value),
+        Err(e) => {
+            record.error = Some(format!("request not serializable: {}", e));
+            return Ok(record);
+        }
+    }
+
+    let txs = match client.build_swap(&request).await {
+        Ok(txs) => txs,
+        Err(e) => {
+            record.error = Some(format!("build failed: {}", e));
+            return Ok(record);
+        }
+    };
+    record.transactions = txs.len();
+
+    if sniper.config().submit {
+        let submit_opts = sniper.config().submit_opts.clone();
+        let use_bundle = sniper.config().use_bundle;
+        match client.submit(txs, submit_opts, use_bundle).await {
+            Ok(outcomes) => {
+                record.signatures = submitted_signatures(&outcomes);
+                if let Some(failed) = outcomes.iter().find(|outcome| !outcome.submitted) {
+                    record.error = Some(format!(
+                        "transaction {} not submitted: {}",
+                        failed.index,
+                        failed.error.as_deref().unwrap_or("unknown error")
+                    ));
+                }
+            }
+            Err(e) => record.error = Some(format!("submit failed: {}", e)),
+        }
+    }
+
+    Ok(record)
+}
+
+// Constant-product output of the pool for `in_amount` quote tokens, in base token units.
+fn expected_out(candidate: &PoolCandidate, in_amount: f64) -> Option<f64> {
+    let quote_liquidity = candidate.quote_liquidity?;
+    let decimals = candidate.signals.base_decimals?;
+    let base_liquidity = candidate.base_reserves? as f64 / 10f64.powi(decimals as i32);
+
+    let in_after_fee = in_amount * (1.0 - RAYDIUM_AMM_FEE);
+    let out = base_liquidity * in_after_fee / (quote_liquidity + in_after_fee);
+    (out.is_finite() && out > 0.0).then_some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(pool: &str, liquidity: f64) -> PoolCandidate {
+        PoolCandidate {
+            slot: 1,
+            pool_address: pool.to_string(),
+            pool: "Raydium".to_string(),
+            cpmm: false,
+            base_mint: Some("mint".to_string()),
+            quote_mint: Some(WRAPPED_SOL.to_string()),
+            quote_liquidity: Some(liquidity),
+            base_reserves: Some(1_000_000_000_000),
+            signals: PoolSignals {
+                freezable: Some(false),
+                base_decimals: Some(6),
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_candidate_from_response() {
+        let response = api::GetNewRaydiumPoolsResponse {
+            slot: 7,
+            pool: Some(api::ProjectPool {
+                pool: "Raydium CPMM".to_string(),
+                pool_address: "pool".to_string(),
+                token1_mint_address: WRAPPED_SOL.to_string(),
+                token1_reserves: 25_000_000_000,
+                token2_mint_address: "mint".to_string(),
+                token2_reserves: 1_000_000,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let candidate =
+            PoolCandidate::from_response(&response, &SniperConfig::default().quote_tokens).unwrap();
+        assert!(candidate.cpmm);
+        assert_eq!(candidate.quote_mint.as_deref(), Some(WRAPPED_SOL));
+        assert_eq!(candidate.base_mint.as_deref(), Some("mint"));
+        assert_eq!(candidate.quote_liquidity, Some(25.0));
+        assert_eq!(candidate.base_reserves, Some(1_000_000));
+    }
+
+    #[test]
+    fn test_evaluate_filters() {
+        let mut sniper = Sniper::new(SniperConfig {
+            min_quote_liquidity: 10.0,
+            reject_freezable: true,
+            creator_blacklist: HashSet::from(["rugger".to_string()]),
+            max_snipes: Some(1),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            sniper.evaluate(&candidate("low", 5.0)),
+            SnipeDecision::Skip(SkipReason::LiquidityTooLow {
+                quote_liquidity: 5.0
+            })
+        );
+
+        let mut blacklisted = candidate("blacklisted", 50.0);
+        blacklisted.signals.creator = Some("rugger".to_string());
+        assert_eq!(
+            sniper.evaluate(&blacklisted),
+            SnipeDecision::Skip(SkipReason::CreatorBlacklisted)
+        );
+
+        let mut freezable = candidate("freezable", 50.0);
+        freezable.signals.freezable = Some(true);
+        assert_eq!(
+            sniper.evaluate(&freezable),
+            SnipeDecision::Skip(SkipReason::Freezable)
+        );
+
+        let mut unknown = candidate("unknown", 50.0);
+        unknown.signals.freezable = None;
+        assert_eq!(
+            sniper.evaluate(&unknown),
+            SnipeDecision::Skip(SkipReason::UnknownSignal {
+                signal: "freezable".to_string()
+            })
+        );
+
+        let mut none = candidate("none", 50.0);
+        none.quote_mint = None;
+        none.quote_liquidity = None;
+        assert_eq!(
+            sniper.evaluate(&none),
+            SnipeDecision::Skip(SkipReason::NoQuoteToken)
+        );
+
+        let mut usdc = candidate("usdc", 50.0);
+        usdc.quote_mint = Some("usdc".to_string());
+        assert_eq!(
+            sniper.evaluate(&usdc),
+            SnipeDecision::Skip(SkipReason::NotSolQuoted)
+        );
+
+        assert_eq!(
+            sniper.evaluate(&candidate("good", 50.0)),
+            SnipeDecision::Buy { in_amount: 0.01 }
+        );
+        assert_eq!(
+            sniper.evaluate(&candidate("good", 50.0)),
+            SnipeDecision::Skip(SkipReason::AlreadySeen)
+        );
+        assert_eq!(
+            sniper.evaluate(&candidate("next", 50.0)),
+            SnipeDecision::Skip(SkipReason::SnipeLimitReached)
+        );
+    }
+
+    #[test]
+    fn test_unknown_signals_and_sizing() {
+        let mut sniper = Sniper::new(SniperConfig {
+            require_lp_burned: true,
+            unknown_signals: UnknownSignal::Reject,
+            buy_amount: Lamports(5_000_000_000),
+            max_pool_share_percent: 2.0,
+            tip: Some(Lamports(1_000_000)),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            sniper.evaluate(&candidate("unknown", 100.0)),
+            SnipeDecision::Skip(SkipReason::UnknownSignal {
+                signal: "lp_burned".to_string()
+            })
+        );
+
+        let mut known = candidate("known", 100.0);
+        known.signals.lp_burned = Some(true);
+        assert_eq!(
+            sniper.evaluate(&known),
+            SnipeDecision::Buy { in_amount: 2.0 }
+        );
+
+        let request = sniper.swap_request(&known, 2.0, "owner").unwrap();
+        let audit = serde_json::to_value(&request).unwrap();
+        assert_eq!(audit["venue"], "raydium_route");
+        let SwapRequest::RaydiumRoute(request) = request else {
+            panic!("expected a Raydium route swap");
+        };
+        assert_eq!(request.slippage, 10.0);
+        assert_eq!(request.tip, Some(1_000_000));
+        assert_eq!(request.steps.len(), 1);
+
+        let step = &request.steps[0];
+        assert_eq!(step.pool_address, "known");
+        assert_eq!(step.in_token, WRAPPED_SOL);
+        assert_eq!(step.out_token, "mint");
+        // 1M base tokens against 100 quote tokens, buying with 2 after the 0.25% fee.
+        let in_after_fee = 2.0 * (1.0 - RAYDIUM_AMM_FEE);
+        let out = 1_000_000.0 * in_after_fee / (100.0 + in_after_fee);
+        assert!((step.out_amount - out).abs() < 1e-6);
+        assert!((step.out_amount_min - out * 0.9).abs() < 1e-6);
+
+        known.signals.base_decimals = None;
+        assert!(sniper.swap_request(&known, 2.0, "owner").is_err());
+    }
+}
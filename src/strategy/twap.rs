@@ -0,0 +1,861 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use tokio::sync::{mpsc, watch};
+use tokio::task::JoinHandle;
+use tokio::time::{sleep_until, Instant};
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
+
+use crate::common::amounts::{SlippageBps, TokenAmount};
+use crate::common::quote::{BestQuote, BestQuoteRequest, SwapQuote, SwapSettings, Venue};
+use crate::common::signing::{
+    require_submitted, submitted_signatures, SubmitOutcome, SubmitParams,
+};
+use crate::common::slippage::deterioration_percent;
+
+use super::{confirm_transactions, ConfirmError, SwapClient};
+
+const REPORT_BUFFER: usize = 1000;
+
+#[derive(Debug, Clone)]
+pub struct ExecutionTarget {
+    pub in_token: String,
+    pub out_token: String,
+    pub total_in_amount: TokenAmount,
+    pub slices: u32,
+    pub interval: Duration,
+    // Slippage for each child quote and swap.
+    pub max_slippage: SlippageBps,
+    // Children are deferred while the quoted price is this much worse than the reference.
+    pub max_price_deviation_percent: Option<f64>,
+    // Out tokens per in token; the first child's quote is used when unset.
+    pub reference_price: Option<f64>,
+    pub venues: Vec<Venue>,
+}
+
+impl ExecutionTarget {
+    pub fn twap(
+        in_token: &str,
+        out_token: &str,
+        total_in_amount: TokenAmount,
+        duration: Duration,
+        slices: u32,
+        max_slippage: SlippageBps,
+    ) -> Self {
+        let interval = duration / slices.max(1);
+        Self::dca(
+            in_token,
+            out_token,
+            total_in_amount,
+            interval,
+            slices,
+            max_slippage,
+        )
+    }
+
+    pub fn dca(
+        in_token: &str,
+        out_token: &str,
+        total_in_amount: TokenAmount,
+        interval: Duration,
+        slices: u32,
+        max_slippage: SlippageBps,
+    ) -> Self {
+        Self {
+            in_token: in_token.to_string(),
+            out_token: out_token.to_string(),
+            total_in_amount,
+            slices,
+            interval,
+            max_slippage,
+            max_price_deviation_percent: None,
+            reference_price: None,
+            venues: BestQuoteRequest::default_venues(),
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.slices == 0 {
+            return Err(anyhow!("Execution needs at least one slice"));
+        }
+        if self.total_in_amount.is_zero() {
+            return Err(anyhow!("Execution size must be positive"));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ExecutionConfig {
+    // Owner falls back to the client's public key when empty; slippage comes from the target.
+    // No tip is paid unless `settings.tip` is set.
+    pub settings: SwapSettings,
+    pub submit_opts: SubmitParams,
+    // Sends multi-transaction children as a bundle, so they land all or nothing. Bundles need
+    // a tip, so set `settings.tip` along with this.
+    pub use_bundle: bool,
+    pub confirm_timeout: Duration,
+    pub confirm_poll_interval: Duration,
+}
+
+impl Default for ExecutionConfig {
+    fn default() -> Self {
+        Self {
+            settings: SwapSettings {
+                compute_limit: 200_000,
+                compute_price: 100_000,
+                ..Default::default()
+            },
+            submit_opts: SubmitParams::default(),
+            use_bundle: false,
+            confirm_timeout: Duration::from_secs(60),
+            confirm_poll_interval: Duration::from_secs(2),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionState {
+    Running,
+    Paused,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ExecutionEvent {
+    ChildSubmitted {
+        in_amount: TokenAmount,
+        quoted_out_amount: f64,
+        signatures: Vec<String>,
+    },
+    ChildFilled {
+        in_amount: TokenAmount,
+        quoted_out_amount: f64,
+        signatures: Vec<String>,
+    },
+    ChildFailed {
+        in_amount: TokenAmount,
+        error: String,
+    },
+    // Some of the child's transactions were sent and others rejected. Resending could fill the
+    // sent part twice, so the amount stays unconfirmed and is not retried; `confirmed` tells
+    // whether the sent transactions landed.
+    ChildPartiallySubmitted {
+        in_amount: TokenAmount,
+        signatures: Vec<String>,
+        confirmed: bool,
+        error: String,
+    },
+    // Not confirmed within the timeout. The amount is held back from later children
+    // until a status lookup shows whether the transaction landed.
+    ChildUnconfirmed {
+        in_amount: TokenAmount,
+        quoted_out_amount: f64,
+        signatures: Vec<String>,
+    },
+    ChildDeferred {
+        in_amount: TokenAmount,
+        price: f64,
+        reference_price: f64,
+    },
+    Paused,
+    Resumed,
+    Cancelled,
+    Completed,
+    // Slices ran out with part of the total unfilled or still unconfirmed.
+    Incomplete,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExecutionReport {
+    pub slice: u32,
+    pub slices: u32,
+    pub event: ExecutionEvent,
+    pub filled_in_amount: TokenAmount,
+    // Out amounts are UI amounts taken from each filled child's quote.
+    pub filled_out_amount: f64,
+    pub remaining_in_amount: TokenAmount,
+    pub unconfirmed_in_amount: TokenAmount,
+}
+
+// Amounts left unfilled by deferred or failed children are spread over the remaining slices.
+// Unconfirmed and partially submitted children are not part of the remaining amount, so they
+// are never resent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutionProgress {
+    pub slices: u32,
+    pub next_slice: u32,
+    pub filled_in_amount: TokenAmount,
+    pub filled_out_amount: f64,
+    pub remaining_in_amount: TokenAmount,
+    pub unconfirmed_in_amount: TokenAmount,
+    pub reference_price: Option<f64>,
+}
+
+impl ExecutionProgress {
+    pub fn new(target: &ExecutionTarget) -> Self {
+        Self {
+            slices: target.slices,
+            next_slice: 0,
+            filled_in_amount: TokenAmount::zero(target.total_in_amount.decimals),
+            filled_out_amount: 0.0,
+            remaining_in_amount: target.total_in_amount,
+            unconfirmed_in_amount: TokenAmount::zero(target.total_in_amount.decimals),
+            reference_price: target.reference_price,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.next_slice >= self.slices || self.remaining_in_amount.is_zero()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.remaining_in_amount.is_zero() && self.unconfirmed_in_amount.is_zero()
+    }
+
+    // Raw units that don't divide evenly go to the earlier children.
+    pub fn next_child_amount(&self) -> TokenAmount {
+        let slices_left = self.slices.saturating_sub(self.next_slice).max(1);
+        TokenAmount::new(
+            self.remaining_in_amount.raw.div_ceil(slices_left as u64),
+            self.remaining_in_amount.decimals,
+        )
+    }
+
+    pub fn record_fill(&mut self, in_amount: TokenAmount, out_amount: f64) {
+        self.filled_in_amount.raw = self.filled_in_amount.raw.saturating_add(in_amount.raw);
+        self.filled_out_amount += out_amount;
+        self.remaining_in_amount.raw = self.remaining_in_amount.raw.saturating_sub(in_amount.raw);
+    }
+
+    pub fn record_unconfirmed(&mut self, in_amount: TokenAmount) {
+        self.remaining_in_amount.raw = self.remaining_in_amount.raw.saturating_sub(in_amount.raw);
+        self.unconfirmed_in_amount.raw =
+            self.unconfirmed_in_amount.raw.saturating_add(in_amount.raw);
+    }
+
+    // A landed child counts as filled; a failed one goes back to the remaining amount.
+    pub fn resolve_unconfirmed(&mut self, in_amount: TokenAmount, out_amount: Option<f64>) {
+        self.unconfirmed_in_amount.raw =
+            self.unconfirmed_in_amount.raw.saturating_sub(in_amount.raw);
+        match out_amount {
+            Some(out_amount) => {
+                self.filled_in_amount.raw = self.filled_in_amount.raw.saturating_add(in_amount.raw);
+                self.filled_out_amount += out_amount;
+            }
+            None => {
+                self.remaining_in_amount.raw =
+                    self.remaining_in_amount.raw.saturating_add(in_amount.raw);
+            }
+        }
+    }
+
+    pub fn report(&self, event: ExecutionEvent) -> ExecutionReport {
+        ExecutionReport {
+            slice: self.next_slice,
+            slices: self.slices,
+            event,
+            filled_in_amount: self.filled_in_amount,
+            filled_out_amount: self.filled_out_amount,
+            remaining_in_amount: self.remaining_in_amount,
+            unconfirmed_in_amount: self.unconfirmed_in_amount,
+        }
+    }
+
+    // Returns the price and reference when the child should be deferred.
+    pub fn check_price(&mut self, price: f64, max_deviation: Option<f64>) -> Option<(f64, f64)> {
+        let reference = *self.reference_price.get_or_insert(price);
+        let max_deviation = max_deviation?;
+        (deterioration_percent(reference, price) > max_deviation).then_some((price, reference))
+    }
+}
+
+pub struct ExecutionHandle {
+    control: watch::Sender<ExecutionState>,
+    task: JoinHandle<()>,
+}
+
+impl ExecutionHandle {
+    pub fn pause(&self) {
+        self.set(ExecutionState::Paused);
+    }
+
+    pub fn resume(&self) {
+        self.set(ExecutionState::Running);
+    }
+
+    // Pause and cancel take effect before the next child; a child already in flight
+    // is still confirmed and reported.
+    pub fn cancel(&self) {
+        self.set(ExecutionState::Cancelled);
+    }
+
+    pub fn state(&self) -> ExecutionState {
+        *self.control.borrow()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+
+    fn set(&self, state: ExecutionState) {
+        self.control.send_if_modified(|current| {
+            // A cancelled execution cannot be resumed.
+            if *current == ExecutionState::Cancelled || *current == state {
+                return false;
+            }
+            *current = state;
+            true
+        });
+    }
+}
+
+impl Drop for ExecutionHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+pub fn spawn_execution<C: SwapClient>(
+    client: C,
+    target: ExecutionTarget,
+    config: ExecutionConfig,
+) -> Result<(
+    ExecutionHandle,
+    impl Stream<Item = Result<ExecutionReport>> + Unpin,
+)> {
+    target.validate()?;
+    client.require_signer()?;
+
+    let mut settings = config.settings.with_default_owner(client.public_key())?;
+    settings.slippage = target.max_slippage;
+
+    let (control, control_rx) = watch::channel(ExecutionState::Running);
+    let (tx, rx) = mpsc::channel(REPORT_BUFFER);
+
+    let task = tokio::spawn(async move {
+        let mut engine = Engine {
+            client,
+            target,
+            config,
+            settings,
+            control: control_rx,
+            tx,
+            unconfirmed: Vec::new(),
+        };
+        if let Err(e) = engine.run().await {
+            let _ = engine.tx.send(Err(e)).await;
+        }
+    });
+
+    Ok((ExecutionHandle { control, task }, ReceiverStream::new(rx)))
+}
+
+struct Engine<C> {
+    client: C,
+    target: ExecutionTarget,
+    config: ExecutionConfig,
+    settings: SwapSettings,
+    control: watch::Receiver<ExecutionState>,
+    tx: mpsc::Sender<Result<ExecutionReport>>,
+    unconfirmed: Vec<UnconfirmedChild>,
+}
+
+struct UnconfirmedChild {
+    in_amount: TokenAmount,
+    quoted_out_amount: f64,
+    signatures: Vec<String>,
+}
+
+impl<C: SwapClient> Engine<C> {
+    async fn run(&mut self) -> Result<()> {
+        let mut progress = ExecutionProgress::new(&self.target);
+        let mut next_fire = Instant::now();
+
+        while !progress.is_done() {
+            if !self.wait_until(next_fire, &progress).await? {
+                self.send(progress.report(ExecutionEvent::Cancelled))
+                    .await?;
+                return Ok(());
+            }
+            next_fire = Instant::now() + self.target.interval;
+
+            self.resolve_unconfirmed(&mut progress, Duration::ZERO)
+                .await?;
+            let event = self.child(&mut progress).await;
+            progress.next_slice += 1;
+            self.send(progress.report(event)).await?;
+        }
+
+        if !self.unconfirmed.is_empty() {
+            let timeout = self.config.confirm_timeout;
+            self.resolve_unconfirmed(&mut progress, timeout).await?;
+        }
+
+        let event = if progress.is_complete() {
+            ExecutionEvent::Completed
+        } else {
+            ExecutionEvent::Incomplete
+        };
+        self.send(progress.report(event)).await
+    }
+
+    // Looks up children whose confirmation timed out and settles those that have resolved.
+    async fn resolve_unconfirmed(
+        &mut self,
+        progress: &mut ExecutionProgress,
+        timeout: Duration,
+    ) -> Result<()> {
+        for child in std::mem::take(&mut self.unconfirmed) {
+            let confirmed = confirm_transactions(
+                &mut self.client,
+                &child.signatures,
+                timeout,
+                self.config.confirm_poll_interval,
+            )
+            .await;
+
+            let event = match confirmed {
+                Ok(()) => {
+                    progress.resolve_unconfirmed(child.in_amount, Some(child.quoted_out_amount));
+                    ExecutionEvent::ChildFilled {
+                        in_amount: child.in_amount,
+                        quoted_out_amount: child.quoted_out_amount,
+                        signatures: child.signatures,
+                    }
+                }
+                Err(e @ ConfirmError::Failed(_)) => {
+                    progress.resolve_unconfirmed(child.in_amount, None);
+                    failed(child.in_amount, e.into())
+                }
+                Err(ConfirmError::TimedOut(_)) => {
+                    self.unconfirmed.push(child);
+                    continue;
+                }
+            };
+            self.send(progress.report(event)).await?;
+        }
+        Ok(())
+    }
+
+    async fn child(&mut self, progress: &mut ExecutionProgress) -> ExecutionEvent {
+        let in_amount = progress.next_child_amount();
+
+        let quote = BestQuoteRequest {
+            venues: self.target.venues.clone(),
+            ..BestQuoteRequest::new(
+                &self.target.in_token,
+                &self.target.out_token,
+                in_amount,
+                self.target.max_slippage,
+            )
+        };
+        let quote = match self.client.best_quote(&quote).await {
+            Ok(quote) => quote,
+            Err(e) => return failed(in_amount, e),
+        };
+
+        let price = quote.best.out_amount / in_amount.to_ui_f64();
+        if let Some((price, reference_price)) =
+            progress.check_price(price, self.target.max_price_deviation_percent)
+        {
+            return ExecutionEvent::ChildDeferred {
+                in_amount,
+                price,
+                reference_price,
+            };
+        }
+
+        let outcomes = match self.submit(&quote).await {
+            Ok(outcomes) => outcomes,
+            Err(e) => return failed(in_amount, e),
+        };
+        let sent = submitted_signatures(&outcomes);
+        let signatures = match require_submitted(outcomes) {
+            Ok(signatures) => signatures,
+            Err(e) if sent.is_empty() => return failed(in_amount, e),
+            Err(e) => return self.partially_submitted(progress, in_amount, sent, e).await,
+        };
+        let quoted_out_amount = quote.best.out_amount;
+        let submitted = progress.report(ExecutionEvent::ChildSubmitted {
+            in_amount,
+            quoted_out_amount,
+            signatures: signatures.clone(),
+        });
+        if self.send(submitted).await.is_err() {
+            return failed(in_amount, anyhow!("Report stream closed"));
+        }
+
+        let confirmed = confirm_transactions(
+            &mut self.client,
+            &signatures,
+            self.config.confirm_timeout,
+            self.config.confirm_poll_interval,
+        )
+        .await;
+        match confirmed {
+            Ok(()) => {
+                progress.record_fill(in_amount, quoted_out_amount);
+                ExecutionEvent::ChildFilled {
+                    in_amount,
+                    quoted_out_amount,
+                    signatures,
+                }
+            }
+            Err(ConfirmError::TimedOut(_)) => {
+                progress.record_unconfirmed(in_amount);
+                self.unconfirmed.push(UnconfirmedChild {
+                    in_amount,
+                    quoted_out_amount,
+                    signatures: signatures.clone(),
+                });
+                ExecutionEvent::ChildUnconfirmed {
+                    in_amount,
+                    quoted_out_amount,
+                    signatures,
+                }
+            }
+            Err(e) => failed(in_amount, e.into()),
+        }
+    }
+
+    async fn partially_submitted(
+        &mut self,
+        progress: &mut ExecutionProgress,
+        in_amount: TokenAmount,
+        signatures: Vec<String>,
+        error: anyhow::Error,
+    ) -> ExecutionEvent {
+        progress.record_unconfirmed(in_amount);
+        let confirmed = confirm_transactions(
+            &mut self.client,
+            &signatures,
+            self.config.confirm_timeout,
+            self.config.confirm_poll_interval,
+        )
+        .await;
+
+        ExecutionEvent::ChildPartiallySubmitted {
+            in_amount,
+            signatures,
+            confirmed: confirmed.is_ok(),
+            error: error.to_string(),
+        }
+    }
+
+    async fn submit(&mut self, quote: &BestQuote) -> Result<Vec<SubmitOutcome>> {
+        let request = quote.swap_request(&self.settings)?;
+        let txs = self.client.build_swap(&request).await?;
+        self.client
+            .submit(txs, self.config.submit_opts.clone(), self.config.use_bundle)
+            .await
+    }
+
+    // Waits for the next slice while honoring pause and cancel; false means cancelled.
+    async fn wait_until(
+        &mut self,
+        deadline: Instant,
+        progress: &ExecutionProgress,
+    ) -> Result<bool> {
+        let mut paused = false;
+
+        loop {
+            let state = *self.control.borrow_and_update();
+            match state {
+                ExecutionState::Cancelled => return Ok(false),
+                ExecutionState::Paused if !paused => {
+                    paused = true;
+                    self.send(progress.report(ExecutionEvent::Paused)).await?;
+                }
+                ExecutionState::Running if paused => {
+                    paused = false;
+                    self.send(progress.report(ExecutionEvent::Resumed)).await?;
+                }
+                _ => {}
+            }
+
+            if paused {
+                if self.control.changed().await.is_err() {
+                    return Ok(false);
+                }
+                continue;
+            }
+
+            tokio::select! {
+                _ = sleep_until(deadline) => return Ok(true),
+                changed = self.control.changed() => {
+                    if changed.is_err() {
+                        return Ok(false);
+                    }
+                }
+            }
+        }
+    }
+
+    async fn send(&self, report: ExecutionReport) -> Result<()> {
+        self.tx
+            .send(Ok(report))
+            .await
+            .map_err(|_| anyhow!("Report stream closed"))
+    }
+}
+
+fn failed(in_amount: TokenAmount, error: anyhow::Error) -> ExecutionEvent {
+    ExecutionEvent::ChildFailed {
+        in_amount,
+        error: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use async_trait::async_trait;
+    use solana_sdk::pubkey::Pubkey;
+    use solana_trader_proto::api;
+    use tokio_stream::StreamExt;
+
+    use super::*;
+    use crate::common::quote::{NormalizedQuote, SwapRequest};
+
+    fn amount(ui: f64) -> TokenAmount {
+        TokenAmount::from_ui_f64(ui, 1).unwrap()
+    }
+
+    // Builds two transactions per child and has the second one rejected.
+    #[derive(Default)]
+    struct PartialSubmitClient {
+        last_in_amount: f64,
+    }
+
+    #[async_trait]
+    impl SwapClient for PartialSubmitClient {
+        fn public_key(&self) -> Option<Pubkey> {
+            Some(Pubkey::new_unique())
+        }
+
+        fn require_signer(&self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn build_swap(&mut self, _: &SwapRequest) -> Result<Vec<api::TransactionMessage>> {
+            Ok(vec![Default::default(), Default::default()])
+        }
+
+        async fn submit(
+            &mut self,
+            txs: Vec<api::TransactionMessage>,
+            _: SubmitParams,
+            _: bool,
+        ) -> Result<Vec<SubmitOutcome>> {
+            assert_eq!(txs.len(), 2);
+            Ok(vec![
+                SubmitOutcome::submitted(0, "setup".to_string()),
+                SubmitOutcome::rejected(1, "swap".to_string(), "blockhash expired".to_string()),
+            ])
+        }
+
+        async fn best_quote(&mut self, request: &BestQuoteRequest) -> Result<BestQuote> {
+            self.last_in_amount = request.in_amount.to_ui_f64();
+            Ok(BestQuote {
+                best: NormalizedQuote {
+                    venue: Venue::Jupiter,
+                    in_token: request.in_token.clone(),
+                    out_token: request.out_token.clone(),
+                    in_amount: self.last_in_amount,
+                    out_amount: self.last_in_amount * 100.0,
+                    out_amount_min: self.last_in_amount * 99.0,
+                    price_impact_percent: None,
+                    fees: BTreeMap::new(),
+                    route: Vec::new(),
+                },
+                alternatives: Vec::new(),
+                failures: Vec::new(),
+            })
+        }
+
+        async fn get_transaction(&mut self, _: &str) -> Result<api::GetTransactionResponse> {
+            Ok(api::GetTransactionResponse {
+                status: "success".to_string(),
+                ..Default::default()
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_partially_submitted_children_are_not_resent() {
+        let target = ExecutionTarget::dca(
+            "SOL",
+            "USDC",
+            amount(10.0),
+            Duration::from_millis(1),
+            2,
+            SlippageBps::clamped(100),
+        );
+        let (_handle, reports) =
+            spawn_execution(PartialSubmitClient::default(), target, Default::default()).unwrap();
+        let reports: Vec<_> = reports.map(Result::unwrap).collect().await;
+
+        let partial: Vec<_> = reports
+            .iter()
+            .filter_map(|report| match &report.event {
+                ExecutionEvent::ChildPartiallySubmitted {
+                    in_amount,
+                    signatures,
+                    confirmed,
+                    ..
+                } => Some((*in_amount, signatures.clone(), *confirmed)),
+                _ => None,
+            })
+            .collect();
+        // Each slice is sent once; the second child does not pick up the first one's amount.
+        assert_eq!(
+            partial,
+            vec![
+                (amount(5.0), vec!["setup".to_string()], true),
+                (amount(5.0), vec!["setup".to_string()], true),
+            ]
+        );
+
+        let last = reports.last().unwrap();
+        assert_eq!(last.event, ExecutionEvent::Incomplete);
+        assert_eq!(last.unconfirmed_in_amount, amount(10.0));
+        assert!(last.remaining_in_amount.is_zero());
+    }
+
+    #[test]
+    fn test_unfilled_amounts_carry_over() {
+        let target = ExecutionTarget::dca(
+            "SOL",
+            "USDC",
+            amount(100.0),
+            Duration::from_secs(1),
+            4,
+            SlippageBps::clamped(100),
+        );
+        let mut progress = ExecutionProgress::new(&target);
+
+        assert_eq!(progress.next_child_amount(), amount(25.0));
+        progress.record_fill(amount(25.0), 2500.0);
+        progress.next_slice += 1;
+
+        // The second child fails, so the last two slices split what is left.
+        progress.next_slice += 1;
+        assert_eq!(progress.next_child_amount(), amount(37.5));
+        progress.record_fill(amount(37.5), 3750.0);
+        progress.next_slice += 1;
+
+        assert_eq!(progress.next_child_amount(), amount(37.5));
+        progress.record_fill(amount(37.5), 3750.0);
+        progress.next_slice += 1;
+
+        assert!(progress.is_done());
+        assert_eq!(progress.filled_in_amount, amount(100.0));
+        assert!(progress.remaining_in_amount.is_zero());
+    }
+
+    #[test]
+    fn test_unconfirmed_children_are_held_back() {
+        let target = ExecutionTarget::dca(
+            "SOL",
+            "USDC",
+            amount(90.0),
+            Duration::from_secs(1),
+            3,
+            SlippageBps::clamped(100),
+        );
+        let mut progress = ExecutionProgress::new(&target);
+
+        // The first child times out, so it is neither resent nor counted as filled.
+        progress.record_unconfirmed(amount(30.0));
+        progress.next_slice += 1;
+        assert_eq!(progress.next_child_amount(), amount(30.0));
+        progress.record_fill(amount(30.0), 300.0);
+        progress.next_slice += 1;
+
+        // It turns out to have failed, so the last slice picks its amount up.
+        progress.resolve_unconfirmed(amount(30.0), None);
+        assert_eq!(progress.next_child_amount(), amount(60.0));
+        progress.record_unconfirmed(amount(60.0));
+        progress.next_slice += 1;
+
+        assert!(progress.is_done());
+        assert!(!progress.is_complete());
+        assert_eq!(progress.unconfirmed_in_amount, amount(60.0));
+
+        progress.resolve_unconfirmed(amount(60.0), Some(600.0));
+        assert!(progress.is_complete());
+        assert_eq!(progress.filled_in_amount, amount(90.0));
+        assert_eq!(progress.filled_out_amount, 900.0);
+    }
+
+    #[test]
+    fn test_child_amounts_are_exact() {
+        let target = ExecutionTarget::dca(
+            "SOL",
+            "USDC",
+            TokenAmount::new(10, 9),
+            Duration::from_secs(1),
+            3,
+            SlippageBps::clamped(100),
+        );
+        let mut progress = ExecutionProgress::new(&target);
+
+        let mut children = vec![];
+        while !progress.is_done() {
+            let child = progress.next_child_amount();
+            progress.record_fill(child, 0.0);
+            progress.next_slice += 1;
+            children.push(child.raw);
+        }
+        assert_eq!(children, vec![4, 3, 3]);
+        assert_eq!(progress.filled_in_amount, target.total_in_amount);
+    }
+
+    #[test]
+    fn test_price_check_uses_first_quote_as_reference() {
+        let target = ExecutionTarget::twap(
+            "SOL",
+            "USDC",
+            amount(10.0),
+            Duration::from_secs(60),
+            6,
+            SlippageBps::clamped(100),
+        );
+        assert_eq!(target.interval, Duration::from_secs(10));
+
+        let mut progress = ExecutionProgress::new(&target);
+        assert_eq!(progress.check_price(100.0, Some(2.0)), None);
+        assert_eq!(progress.check_price(99.0, Some(2.0)), None);
+        assert_eq!(progress.check_price(97.0, Some(2.0)), Some((97.0, 100.0)));
+        assert_eq!(progress.check_price(50.0, None), None);
+    }
+
+    #[test]
+    fn test_target_validation() {
+        let target = ExecutionTarget::dca(
+            "SOL",
+            "USDC",
+            amount(1.0),
+            Duration::from_secs(1),
+            0,
+            SlippageBps::clamped(100),
+        );
+        assert!(target.validate().is_err());
+
+        let target = ExecutionTarget::dca(
+            "SOL",
+            "USDC",
+            amount(0.0),
+            Duration::from_secs(1),
+            2,
+            SlippageBps::clamped(100),
+        );
+        assert!(target.validate().is_err());
+    }
+}
@@ -0,0 +1,101 @@
+use anyhow::Result;
+use futures_util::StreamExt;
+use solana_trader_client_rust::{
+    common::constants::{USDC, WRAPPED_SOL},
+    provider::{failover::FailoverClient, grpc::GrpcClient},
+};
+use solana_trader_proto::api;
+use test_case::test_case;
+
+#[tokio::test]
+#[ignore]
+async fn test_failover_health_grpc() -> Result<()> {
+    let client = FailoverClient::<GrpcClient>::mainnet().await?;
+    client.check_health().await;
+
+    for (region, health) in client.health().await {
+        println!("{}: {:?}", region, health);
+    }
+
+    assert!(
+        client.active_region().await.is_some(),
+        "Expected an active region"
+    );
+
+    Ok(())
+}
+
+#[test_case(
+    WRAPPED_SOL,
+    USDC,
+    0.1,
+    0.2;
+    "SOL to USDC raydium quote with failover"
+)]
+#[tokio::test]
+#[ignore]
+async fn test_failover_raydium_quotes_grpc(
+    in_token: &str,
+    out_token: &str,
+    in_amount: f64,
+    slippage: f64,
+) -> Result<()> {
+    let client = FailoverClient::<GrpcClient>::mainnet().await?;
+
+    let request = api::GetRaydiumQuotesRequest {
+        in_token: in_token.to_string(),
+        out_token: out_token.to_string(),
+        in_amount,
+        slippage,
+    };
+
+    let response = client
+        .execute(|c| {
+            let request = request.clone();
+            Box::pin(async move { c.get_raydium_quotes(&request).await })
+        })
+        .await?;
+
+    println!(
+        "Raydium Quote: {}",
+        serde_json::to_string_pretty(&response)?
+    );
+    assert!(
+        !response.routes.is_empty(),
+        "Expected at least one route in response"
+    );
+
+    Ok(())
+}
+
+#[test_case(
+    vec![api::Project::PRaydium],
+    vec![WRAPPED_SOL.to_string()] ;
+    "raydium SOL price stream with failover"
+)]
+#[tokio::test]
+#[ignore]
+async fn test_failover_price_stream_grpc(
+    projects: Vec<api::Project>,
+    tokens: Vec<String>,
+) -> Result<()> {
+    let client = FailoverClient::<GrpcClient>::mainnet().await?;
+
+    let mut stream = client
+        .stream(move |c| {
+            let (projects, tokens) = (projects.clone(), tokens.clone());
+            Box::pin(async move {
+                let stream = c.get_prices_stream(projects, tokens).await?;
+                Ok(stream.map(|r| r.map_err(anyhow::Error::from)).boxed())
+            })
+        })
+        .await?;
+
+    let response = stream
+        .next()
+        .await
+        .ok_or_else(|| anyhow::anyhow!("Stream ended without data"))??;
+
+    println!("Response received: {:#?}", response);
+    Ok(())
+}
@@ -1,5 +1,9 @@
+pub mod failover;
 pub mod general;
 pub mod memo;
+pub mod openbook;
+pub mod pools;
 pub mod quote;
+pub mod read_only;
 pub mod stream;
 pub mod swap;
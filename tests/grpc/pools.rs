@@ -0,0 +1,41 @@
+use anyhow::Result;
+
+use solana_trader_client_rust::{
+    common::constants::{USDC, WRAPPED_SOL},
+    provider::grpc::GrpcClient,
+};
+use solana_trader_proto::api;
+use test_case::test_case;
+
+#[test_case("SOL/USDC" ; "sol usdc")]
+#[tokio::test]
+#[ignore]
+async fn test_get_raydium_pool_reserve_grpc(pair: &str) -> Result<()> {
+    let mut client = GrpcClient::new_read_only(None).await?;
+
+    let request = api::GetRaydiumPoolReserveRequest {
+        pairs_or_addresses: vec![pair.to_string()],
+    };
+
+    let response = client.get_raydium_pool_reserve(&request).await?;
+    println!(
+        "Pool reserve response: {}",
+        serde_json::to_string_pretty(&response)?
+    );
+
+    Ok(())
+}
+
+#[test_case(WRAPPED_SOL, USDC ; "sol usdc")]
+#[tokio::test]
+#[ignore]
+async fn test_load_pool_cache_grpc(in_token: &str, out_token: &str) -> Result<()> {
+    let mut client = GrpcClient::new_read_only(None).await?;
+
+    let cache = client.load_pool_cache().await?;
+    let pools = cache.pools_for_pair(in_token, out_token);
+    println!("{} pools cached, {} for the pair", cache.len(), pools.len());
+    assert!(!pools.is_empty());
+
+    Ok(())
+}
@@ -1,13 +1,20 @@
 use anyhow::Result;
+use solana_sdk::pubkey::Pubkey;
 use solana_trader_client_rust::{
     common::{
+        amounts::{Lamports, SlippageBps},
+        bundle::BundleBuilder,
         constants::{MAINNET_PUMP_NY, USDC, WRAPPED_SOL},
+        pump::{bonding_curve_address, pump_fun_quote_request, PumpFunQuote, PumpFunQuoteType},
+        quote::SwapSettings,
         signing::SubmitParams,
+        slippage::SlippageGuard,
     },
     provider::grpc::GrpcClient,
 };
 use solana_trader_proto::api;
 use solana_trader_proto::common::Fee;
+use std::str::FromStr;
 use test_case::test_case;
 
 #[test_case(
@@ -520,3 +527,173 @@ async fn test_pumpfun_swap_grpc(in_amount: f64, slippage: f64) -> Result<()> {
     println!("signature : {:#?}", s?);
     Ok(())
 }
+
+#[test_case(
+    WRAPPED_SOL,
+    USDC,
+    0.001,
+    0.5;
+    "Raydium SOL to USDC quote executed via gRPC"
+)]
+#[tokio::test]
+#[ignore]
+async fn test_execute_raydium_quote_grpc(
+    in_token: &str,
+    out_token: &str,
+    in_amount: f64,
+    slippage: f64,
+) -> Result<()> {
+    let mut client = GrpcClient::new(None).await?;
+
+    let quotes = client
+        .get_raydium_quotes(&api::GetRaydiumQuotesRequest {
+            in_token: in_token.to_string(),
+            out_token: out_token.to_string(),
+            in_amount,
+            slippage,
+        })
+        .await?;
+
+    let settings = SwapSettings {
+        slippage: SlippageBps::from_percent(slippage)?,
+        compute_limit: 300000,
+        compute_price: 2000,
+        tip: Some(Lamports(2000001)),
+        ..Default::default()
+    };
+
+    let signatures = client
+        .execute_quote(&quotes, &settings, SubmitParams::default(), false)
+        .await?;
+    println!("Raydium quote signatures: {:#?}", signatures);
+    assert!(!signatures.is_empty(), "Expected at least one signature");
+    assert!(signatures.iter().all(|outcome| outcome.submitted));
+
+    Ok(())
+}
+
+#[test_case(
+    USDC,
+    WRAPPED_SOL,
+    0.001,
+    0.4;
+    "Guarded Raydium swap instructions USDC to SOL via gRPC"
+)]
+#[tokio::test]
+#[ignore]
+async fn test_raydium_swap_instructions_guarded_grpc(
+    in_token: &str,
+    out_token: &str,
+    in_amount: f64,
+    slippage: f64,
+) -> Result<()> {
+    let mut client = GrpcClient::new(None).await?;
+
+    let request = api::PostRaydiumSwapInstructionsRequest {
+        owner_address: client
+            .public_key
+            .unwrap_or_else(|| panic!("Public key is required for Raydium swap instructions"))
+            .to_string(),
+        in_token: in_token.to_string(),
+        out_token: out_token.to_string(),
+        in_amount,
+        slippage,
+        compute_limit: 300000,
+        compute_price: 2000,
+        tip: Some(2000001),
+    };
+
+    let submit_opts = SubmitParams {
+        slippage_guard: Some(SlippageGuard::rebuild(SlippageBps::clamped(100), 2)),
+        ..Default::default()
+    };
+    let signatures = client
+        .submit_raydium_swap_instructions(request, submit_opts, false)
+        .await?;
+
+    println!("Guarded Raydium swap signatures: {:#?}", signatures);
+
+    Ok(())
+}
+
+#[test_case(PumpFunQuoteType::Buy, 0.0001; "pump.fun buy via gRPC")]
+#[test_case(PumpFunQuoteType::Sell, 100.0; "pump.fun sell via gRPC")]
+#[tokio::test]
+#[ignore]
+async fn test_pump_fun_swap_grpc(quote_type: PumpFunQuoteType, amount: f64) -> Result<()> {
+    let mint = Pubkey::from_str("2DEsbYgW94AtZxgUfYXoL8DqJAorsLrEWZdSfriipump")?;
+    let bonding_curve = bonding_curve_address(&mint)?;
+    let mut client = GrpcClient::new(Some(MAINNET_PUMP_NY.to_string())).await?;
+
+    let request = pump_fun_quote_request(
+        quote_type,
+        &mint.to_string(),
+        &bonding_curve.to_string(),
+        amount,
+    );
+    let response = client.get_pump_fun_quotes(&request).await?;
+    println!(
+        "pump.fun {} quote: {}",
+        quote_type,
+        serde_json::to_string_pretty(&response)?
+    );
+
+    let settings = SwapSettings {
+        slippage: SlippageBps::clamped(1000),
+        compute_limit: 300000,
+        compute_price: 2000,
+        tip: Some(Lamports(2000001)),
+        ..Default::default()
+    };
+    let quote = PumpFunQuote { request, response };
+
+    let signatures = client
+        .execute_quote(&quote, &settings, SubmitParams::default(), false)
+        .await?;
+    println!("pump.fun {} signatures: {:#?}", quote_type, signatures);
+
+    Ok(())
+}
+
+#[test_case(
+    WRAPPED_SOL,
+    USDC,
+    0.001,
+    1.0;
+    "Raydium SOL to USDC bundle via gRPC"
+)]
+#[tokio::test]
+#[ignore]
+async fn test_submit_bundle_grpc(
+    in_token: &str,
+    out_token: &str,
+    in_amount: f64,
+    slippage: f64,
+) -> Result<()> {
+    let mut client = GrpcClient::new(None).await?;
+
+    let request = api::PostRaydiumSwapRequest {
+        owner_address: client
+            .public_key
+            .ok_or_else(|| anyhow::anyhow!("Public key is required for bundle submission"))?
+            .to_string(),
+        in_token: in_token.to_string(),
+        out_token: out_token.to_string(),
+        in_amount,
+        slippage,
+        compute_limit: 300000,
+        compute_price: 2000,
+        tip: Some(2000001),
+    };
+    let response = client.post_raydium_swap(&request).await?;
+
+    let bundle = BundleBuilder::new().extend(response.transactions).build()?;
+
+    let result = client
+        .submit_bundle(&bundle, SubmitParams::default())
+        .await?;
+    println!("Bundle result: {:#?}", result);
+    assert_eq!(result.entries.len(), bundle.len());
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use anyhow::Result;
+use base64::{engine::general_purpose, Engine};
+use solana_sdk::signature::Signer;
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::Transaction;
+use solana_trader_client_rust::common::constants::{MAINNET_NY, MAINNET_UK};
+use solana_trader_client_rust::common::signing::SubmitParams;
+use solana_trader_client_rust::provider::broadcast::Broadcaster;
+use solana_trader_client_rust::provider::http::HTTPClient;
+use solana_trader_proto::api::TransactionMessage;
+
+#[tokio::test]
+#[ignore]
+async fn test_broadcast_transfer_http() -> Result<()> {
+    let client = HTTPClient::new(None)?;
+    let keypair = client.get_keypair()?;
+    let pubkey = keypair.pubkey();
+
+    let transfer_instruction = system_instruction::transfer(&pubkey, &pubkey, 2000);
+    let transaction = Transaction::new_with_payer(&[transfer_instruction], Some(&pubkey));
+    let tx_message = TransactionMessage {
+        content: general_purpose::STANDARD.encode(bincode::serialize(&transaction)?),
+        is_cleanup: false,
+    };
+
+    let broadcaster = Broadcaster::http_regions(&[MAINNET_NY, MAINNET_UK])?;
+    let mut report = broadcaster
+        .sign_and_broadcast(&tx_message, keypair, SubmitParams::default())
+        .await?;
+
+    assert!(
+        report.first_accepted.is_some(),
+        "Expected at least one endpoint to accept the transaction"
+    );
+
+    for outcome in report.wait_all().await {
+        println!(
+            "{}: signature={:?} error={:?} latency={:?}",
+            outcome.target, outcome.signature, outcome.error, outcome.latency
+        );
+    }
+
+    Ok(())
+}
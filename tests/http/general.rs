@@ -3,6 +3,7 @@ use anyhow::Result;
 use solana_trader_client_rust::{
     common::{constants::SAMPLE_OWNER_ADDR, constants::SAMPLE_TX_SIGNATURE},
     provider::http::HTTPClient,
+    strategy::portfolio::{PortfolioTracker, PriceSource},
 };
 use solana_trader_proto::api;
 use test_case::test_case;
@@ -165,3 +166,18 @@ async fn test_get_account_balance_http(owner_address: &str) -> Result<()> {
 
     Ok(())
 }
+
+#[test_case(SAMPLE_OWNER_ADDR; "portfolio snapshot - via http")]
+#[tokio::test]
+#[ignore]
+async fn test_portfolio_snapshot_http(owner_address: &str) -> Result<()> {
+    let client = HTTPClient::new(None)?;
+    let mut tracker = PortfolioTracker::new(client, owner_address, PriceSource::Raydium);
+
+    let snapshot = tracker.refresh().await?;
+    println!("portfolio: {}", snapshot.to_json()?);
+    println!("{}", snapshot.to_csv());
+
+    assert_eq!(snapshot.owner_address, owner_address);
+    Ok(())
+}
@@ -2,20 +2,19 @@
 use base64::engine::general_purpose;
 use base64::Engine;
 use solana_hash::Hash;
-use solana_sdk::instruction::{AccountMeta, CompiledInstruction, Instruction};
-use solana_sdk::message::VersionedMessage;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::{Signature, Signer};
 use solana_sdk::system_instruction;
-use solana_sdk::transaction::{Transaction, VersionedTransaction};
+use solana_sdk::transaction::Transaction;
+use solana_trader_client_rust::common::memo::{
+    add_memo_to_serialized_tx, memo_instruction, MEMO_MESSAGE,
+};
 use solana_trader_client_rust::common::signing::SubmitParams;
 use solana_trader_client_rust::provider::http::HTTPClient;
 use solana_trader_proto::api::{GetRecentBlockHashRequestV2, TransactionMessage};
 use std::str::FromStr;
 
-const TRADER_API_MEMO_PROGRAM: &str = "HQ2UUt18uJqKaQFJhgV9zaTdQxUZjNrsKFgoEDquBkcx";
 const JITO_TIP_WALLET: &str = "95cfoy472fcQHaw4tPGBTKpn6ZQnfEPfBgDQx6gcRmRg";
-const MEMO_MESSAGE: &str = "Powered by bloXroute Trader Api";
 
 #[tokio::test]
 #[ignore]
@@ -41,7 +40,7 @@ async fn test_add_memo_to_tx_http() -> anyhow::Result<()> {
         &[
             transfer_instruction,
             jito_tip_instruction,
-            build_memo_instruction(),
+            memo_instruction(MEMO_MESSAGE),
         ],
         Some(&pubkey),
         &[keypair],
@@ -82,25 +81,9 @@ async fn test_add_memo_to_serialized_tx_http() -> anyhow::Result<()> {
     transaction.signatures = vec![Signature::default()];
     transaction.signatures[0] = keypair.sign_message(&message_data);
 
-    let serialized_tx = bincode::serialize(&transaction)?;
-    let mut deserialized_tx: VersionedTransaction = bincode::deserialize(&serialized_tx)?;
-
-    if let VersionedMessage::Legacy(message) = &mut deserialized_tx.message {
-        let memo_program = Pubkey::from_str(TRADER_API_MEMO_PROGRAM)?;
-        message.account_keys.push(memo_program);
-
-        message
-            .instructions
-            .push(CompiledInstruction::new_from_raw_parts(
-                (message.account_keys.len() - 1) as u8,
-                MEMO_MESSAGE.as_bytes().to_vec(),
-                vec![],
-            ));
-    }
-
-    let content = bincode::serialize(&deserialized_tx)?;
+    let serialized_tx = general_purpose::STANDARD.encode(bincode::serialize(&transaction)?);
     let messages = vec![TransactionMessage {
-        content: general_purpose::STANDARD.encode(content),
+        content: add_memo_to_serialized_tx(&serialized_tx, MEMO_MESSAGE)?,
         is_cleanup: false,
     }];
 
@@ -114,12 +97,28 @@ async fn test_add_memo_to_serialized_tx_http() -> anyhow::Result<()> {
     Ok(())
 }
 
-fn build_memo_instruction() -> Instruction {
-    let program_id = Pubkey::from_str(TRADER_API_MEMO_PROGRAM).unwrap();
+#[tokio::test]
+#[ignore]
+async fn test_auto_memo_on_submit_http() -> anyhow::Result<()> {
+    let client = HTTPClient::new(None)?;
+    let pubkey = client.public_key.unwrap();
+
+    let transaction = Transaction::new_with_payer(
+        &[system_instruction::transfer(&pubkey, &pubkey, 2000)],
+        Some(&pubkey),
+    );
+    let messages = vec![TransactionMessage {
+        content: general_purpose::STANDARD.encode(bincode::serialize(&transaction)?),
+        is_cleanup: false,
+    }];
+
+    let submit_opts = SubmitParams {
+        add_memo: true,
+        ..Default::default()
+    };
 
-    Instruction {
-        program_id,
-        accounts: vec![AccountMeta::new(program_id, false)],
-        data: MEMO_MESSAGE.as_bytes().to_vec(),
-    }
+    let outcomes = client.sign_and_submit(messages, submit_opts, false).await?;
+    println!("HTTP Signature: {outcomes:?}");
+    assert!(outcomes.iter().all(|outcome| outcome.submitted));
+    Ok(())
 }
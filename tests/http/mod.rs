@@ -1,4 +1,8 @@
+pub mod broadcast;
 pub mod general;
 pub mod memo;
+pub mod openbook;
+pub mod pools;
 pub mod quote;
+pub mod read_only;
 pub mod swap;
@@ -0,0 +1,93 @@
+use anyhow::Result;
+
+use solana_trader_client_rust::{common::constants::SAMPLE_OWNER_ADDR, provider::http::HTTPClient};
+use solana_trader_proto::api;
+use test_case::test_case;
+
+#[test_case("SOL/USDC", SAMPLE_OWNER_ADDR ; "sol usdc")]
+#[tokio::test]
+#[ignore]
+async fn test_get_open_orders_http(market: &str, owner: &str) -> Result<()> {
+    let client = HTTPClient::new_read_only(None)?;
+
+    let request = api::GetOpenOrdersRequestV2 {
+        market: market.to_string(),
+        owner_address: owner.to_string(),
+        limit: 10,
+        ..Default::default()
+    };
+
+    let response = client.get_open_orders(&request).await?;
+    println!(
+        "Open orders response: {}",
+        serde_json::to_string_pretty(&response)?
+    );
+
+    Ok(())
+}
+
+#[test_case("SOL/USDC", SAMPLE_OWNER_ADDR ; "sol usdc")]
+#[tokio::test]
+#[ignore]
+async fn test_get_unsettled_http(market: &str, owner: &str) -> Result<()> {
+    let client = HTTPClient::new_read_only(None)?;
+
+    let request = api::GetUnsettledRequestV2 {
+        market: market.to_string(),
+        owner_address: owner.to_string(),
+    };
+
+    let response = client.get_unsettled(&request).await?;
+    println!(
+        "Unsettled response: {}",
+        serde_json::to_string_pretty(&response)?
+    );
+
+    Ok(())
+}
+
+#[test_case("SOL/USDC" ; "sol usdc")]
+#[tokio::test]
+#[ignore]
+async fn test_post_cancel_all_orders_http(market: &str) -> Result<()> {
+    let client = HTTPClient::new(None)?;
+
+    let owner = client
+        .public_key
+        .ok_or_else(|| anyhow::anyhow!("PUBLIC_KEY is required"))?;
+    let request = api::PostCancelAllRequest {
+        market: market.to_string(),
+        owner_address: owner.to_string(),
+        ..Default::default()
+    };
+
+    let response = client.post_cancel_all_orders(&request).await?;
+    println!(
+        "Cancel all response: {}",
+        serde_json::to_string_pretty(&response)?
+    );
+
+    Ok(())
+}
+
+#[test_case("SOL/USDC", SAMPLE_OWNER_ADDR ; "sol usdc")]
+#[tokio::test]
+#[ignore]
+async fn test_get_phoenix_open_orders_http(market: &str, owner: &str) -> Result<()> {
+    let client = HTTPClient::new_read_only(None)?;
+
+    let request = api::GetOpenOrdersRequest {
+        market: market.to_string(),
+        address: owner.to_string(),
+        limit: 10,
+        ..Default::default()
+    };
+
+    let response = client.get_phoenix_open_orders(&request).await?;
+    println!(
+        "Phoenix open orders response: {}",
+        serde_json::to_string_pretty(&response)?
+    );
+
+    Ok(())
+}
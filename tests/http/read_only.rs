@@ -0,0 +1,258 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures_util::stream::{self, BoxStream};
+use futures_util::StreamExt;
+use solana_trader_client_rust::common::amounts::{SlippageBps, TokenAmount};
+use solana_trader_client_rust::common::bundle::BundleBuilder;
+use solana_trader_client_rust::common::constants::{MAINNET_NY, MAINNET_UK, USDC, WRAPPED_SOL};
+use solana_trader_client_rust::common::quote::{SwapRequest, SwapSettings};
+use solana_trader_client_rust::common::signing::{ClientCapability, NoSignerError, SubmitParams};
+use solana_trader_client_rust::provider::broadcast::Broadcaster;
+use solana_trader_client_rust::provider::http::HTTPClient;
+use solana_trader_client_rust::strategy::orders::{
+    spawn_order_manager, OrderManagerConfig, PriceFeedSource, TriggerManager,
+};
+use solana_trader_client_rust::strategy::sniper::{
+    spawn_sniper, AuditLog, NewPoolSource, SniperConfig,
+};
+use solana_trader_client_rust::strategy::twap::{
+    spawn_execution, ExecutionConfig, ExecutionTarget,
+};
+use solana_trader_client_rust::strategy::SwapClient;
+use solana_trader_proto::api;
+
+// The signer check runs before any request is made, so these tests need neither keys nor
+// network access; building an HTTP client only needs an auth header to be present.
+fn read_only_client() -> anyhow::Result<HTTPClient> {
+    if std::env::var("AUTH_HEADER").is_err() {
+        std::env::set_var("AUTH_HEADER", "read-only-test");
+    }
+    HTTPClient::new_read_only(None)
+}
+
+fn assert_no_signer<T>(result: anyhow::Result<T>) {
+    let Err(err) = result else {
+        panic!("signing should fail on a read-only client");
+    };
+    assert_eq!(err.downcast_ref::<NoSignerError>(), Some(&NoSignerError));
+}
+
+struct NoFeed;
+
+#[async_trait]
+impl NewPoolSource for NoFeed {
+    async fn new_pools(
+        &mut self,
+        _include_cpmm: bool,
+    ) -> anyhow::Result<BoxStream<'static, anyhow::Result<api::GetNewRaydiumPoolsResponse>>> {
+        Ok(stream::empty().boxed())
+    }
+}
+
+#[async_trait]
+impl PriceFeedSource for NoFeed {
+    async fn token_prices(
+        &mut self,
+        _projects: Vec<api::Project>,
+        _tokens: Vec<String>,
+    ) -> anyhow::Result<BoxStream<'static, anyhow::Result<api::GetPricesStreamResponse>>> {
+        Ok(stream::empty().boxed())
+    }
+
+    async fn pool_reserves(
+        &mut self,
+        _projects: Vec<api::Project>,
+        _pools: Vec<String>,
+    ) -> anyhow::Result<BoxStream<'static, anyhow::Result<api::GetPoolReservesStreamResponse>>>
+    {
+        Ok(stream::empty().boxed())
+    }
+}
+
+#[tokio::test]
+async fn test_read_only_signing_paths_http() -> anyhow::Result<()> {
+    let client = read_only_client()?;
+    assert_eq!(client.capability(), ClientCapability::ReadOnly);
+
+    assert_no_signer(client.get_keypair());
+    assert_no_signer(
+        client
+            .sign_and_submit(
+                vec![api::TransactionMessage::default()],
+                SubmitParams::default(),
+                false,
+            )
+            .await,
+    );
+
+    let bundle = BundleBuilder::new()
+        .push(api::TransactionMessage::default())
+        .build()?;
+    assert_no_signer(client.submit_bundle(&bundle, SubmitParams::default()).await);
+
+    assert_no_signer(
+        client
+            .submit_raydium_swap_instructions(Default::default(), SubmitParams::default(), false)
+            .await,
+    );
+    assert_no_signer(
+        client
+            .submit_jupiter_swap_instructions(Default::default(), SubmitParams::default(), false)
+            .await,
+    );
+    assert_no_signer(
+        client
+            .submit_pump_swap(Default::default(), SubmitParams::default(), false)
+            .await,
+    );
+    assert_no_signer(
+        client
+            .submit_trade_swap(Default::default(), SubmitParams::default(), false)
+            .await,
+    );
+    assert_no_signer(
+        client
+            .execute_quote(
+                &api::GetJupiterQuotesResponse::default(),
+                &SwapSettings::default(),
+                SubmitParams::default(),
+                false,
+            )
+            .await,
+    );
+    assert_no_signer(
+        client
+            .execute_swap(
+                &SwapRequest::Jupiter(Default::default()),
+                SubmitParams::default(),
+                false,
+            )
+            .await,
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_read_only_clob_http() -> anyhow::Result<()> {
+    let client = read_only_client()?;
+
+    assert_no_signer(
+        client
+            .submit_order(Default::default(), SubmitParams::default(), false)
+            .await,
+    );
+    assert_no_signer(
+        client
+            .submit_cancel_order(Default::default(), SubmitParams::default(), false)
+            .await,
+    );
+    assert_no_signer(
+        client
+            .submit_cancel_all_orders(Default::default(), SubmitParams::default(), false)
+            .await,
+    );
+    assert_no_signer(
+        client
+            .submit_replace_order(Default::default(), SubmitParams::default(), false)
+            .await,
+    );
+    assert_no_signer(
+        client
+            .submit_settle(Default::default(), SubmitParams::default(), false)
+            .await,
+    );
+
+    assert_no_signer(
+        client
+            .submit_phoenix_order(Default::default(), SubmitParams::default(), false)
+            .await,
+    );
+    assert_no_signer(
+        client
+            .submit_phoenix_cancel_order(Default::default(), SubmitParams::default(), false)
+            .await,
+    );
+    assert_no_signer(
+        client
+            .submit_phoenix_cancel_all_orders(Default::default(), SubmitParams::default(), false)
+            .await,
+    );
+    assert_no_signer(
+        client
+            .submit_phoenix_replace_order(Default::default(), SubmitParams::default(), false)
+            .await,
+    );
+    assert_no_signer(
+        client
+            .submit_phoenix_settle(Default::default(), SubmitParams::default(), false)
+            .await,
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_read_only_broadcast_http() -> anyhow::Result<()> {
+    let client = read_only_client()?;
+
+    // Broadcast targets never sign, so they build without a keypair; the signing key has
+    // to come from a client that has one.
+    Broadcaster::http_regions(&[MAINNET_NY, MAINNET_UK])?;
+    assert_no_signer(client.get_keypair());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_read_only_strategies_http() -> anyhow::Result<()> {
+    let mut client = read_only_client()?;
+    assert_no_signer(SwapClient::require_signer(&client));
+    assert_no_signer(
+        SwapClient::submit(
+            &mut client,
+            vec![api::TransactionMessage::default()],
+            SubmitParams::default(),
+            false,
+        )
+        .await,
+    );
+
+    let target = ExecutionTarget::twap(
+        WRAPPED_SOL,
+        USDC,
+        TokenAmount::new(1_000_000_000, 9),
+        Duration::from_secs(60),
+        2,
+        SlippageBps::new(50)?,
+    );
+    assert_no_signer(spawn_execution(
+        read_only_client()?,
+        target,
+        ExecutionConfig::default(),
+    ));
+
+    assert_no_signer(
+        spawn_sniper(
+            NoFeed,
+            read_only_client()?,
+            SniperConfig::default(),
+            None,
+            AuditLog::new(std::io::sink()),
+        )
+        .await,
+    );
+
+    assert_no_signer(
+        spawn_order_manager(
+            NoFeed,
+            read_only_client()?,
+            TriggerManager::new(),
+            OrderManagerConfig::default(),
+        )
+        .await,
+    );
+
+    Ok(())
+}
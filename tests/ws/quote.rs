@@ -1,6 +1,11 @@
 use anyhow::Result;
 use solana_trader_client_rust::{
-    common::constants::{MAINNET_PUMP_NY, USDC, WRAPPED_SOL},
+    common::{
+        amounts::{SlippageBps, TokenAmount},
+        constants::{MAINNET_PUMP_NY, USDC, WRAPPED_SOL},
+        quote::BestQuoteRequest,
+        tokens::TokenRegistry,
+    },
     provider::ws::WebSocketClient,
 };
 use solana_trader_proto::api;
@@ -322,3 +327,45 @@ async fn test_get_jupiter_prices_ws(tokens: Vec<String>) -> Result<()> {
     ws.close().await?;
     Ok(())
 }
+
+#[test_case(
+    WRAPPED_SOL,
+    USDC,
+    "0.01",
+    100;
+    "SOL to USDC best quote via WebSocket"
+)]
+#[tokio::test]
+#[ignore]
+async fn test_best_quote_ws(
+    in_token: &str,
+    out_token: &str,
+    in_amount: &str,
+    slippage_bps: u16,
+) -> Result<()> {
+    let client = WebSocketClient::new(None).await?;
+
+    let decimals = TokenRegistry::bundled().resolve(in_token)?.decimals;
+    let request = BestQuoteRequest::new(
+        in_token,
+        out_token,
+        TokenAmount::from_ui(in_amount, decimals)?,
+        SlippageBps::new(slippage_bps)?,
+    );
+    let best = client.best_quote(&request).await?;
+
+    println!("Best quote: {:#?}", best.best);
+    println!("Alternatives: {}", best.alternatives.len());
+    println!("Failures: {:#?}", best.failures);
+
+    assert!(best.best.out_amount > 0.0, "Expected a positive out amount");
+    for alternative in &best.alternatives {
+        assert!(
+            alternative.out_amount_min <= best.best.out_amount_min,
+            "Alternatives should not beat the winning quote"
+        );
+    }
+
+    client.close().await?;
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use solana_trader_client_rust::common::bundle::BundleBuilder;
+use solana_trader_client_rust::common::quote::{SwapRequest, SwapSettings};
+use solana_trader_client_rust::common::signing::{ClientCapability, NoSignerError, SubmitParams};
+use solana_trader_client_rust::provider::ws::WebSocketClient;
+use solana_trader_proto::api;
+
+fn assert_no_signer<T: std::fmt::Debug>(result: anyhow::Result<T>) {
+    let err = result.expect_err("signing should fail on a read-only client");
+    assert_eq!(err.downcast_ref::<NoSignerError>(), Some(&NoSignerError));
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_read_only_signing_paths_ws() -> anyhow::Result<()> {
+    let client = WebSocketClient::new_read_only(None).await?;
+    assert_eq!(client.capability(), ClientCapability::ReadOnly);
+
+    assert_no_signer(client.get_keypair());
+    assert_no_signer(
+        client
+            .sign_and_submit(
+                vec![api::TransactionMessage::default()],
+                SubmitParams::default(),
+                false,
+            )
+            .await,
+    );
+
+    let bundle = BundleBuilder::new()
+        .push(api::TransactionMessage::default())
+        .build()?;
+    assert_no_signer(client.submit_bundle(&bundle, SubmitParams::default()).await);
+
+    assert_no_signer(
+        client
+            .submit_raydium_swap_instructions(Default::default(), SubmitParams::default(), false)
+            .await,
+    );
+    assert_no_signer(
+        client
+            .submit_jupiter_swap_instructions(Default::default(), SubmitParams::default(), false)
+            .await,
+    );
+    assert_no_signer(
+        client
+            .submit_pump_swap(Default::default(), SubmitParams::default(), false)
+            .await,
+    );
+    assert_no_signer(
+        client
+            .submit_trade_swap(Default::default(), SubmitParams::default(), false)
+            .await,
+    );
+    assert_no_signer(
+        client
+            .execute_quote(
+                &api::GetJupiterQuotesResponse::default(),
+                &SwapSettings::default(),
+                SubmitParams::default(),
+                false,
+            )
+            .await,
+    );
+    assert_no_signer(
+        client
+            .execute_swap(
+                &SwapRequest::Jupiter(Default::default()),
+                SubmitParams::default(),
+                false,
+            )
+            .await,
+    );
+
+    client.close().await?;
+    Ok(())
+}
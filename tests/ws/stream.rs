@@ -1,8 +1,20 @@
 use anyhow::Result;
 use futures_util::StreamExt;
 use solana_trader_client_rust::{
-    common::constants::{MAINNET_PUMP_NY, WRAPPED_SOL},
+    common::{
+        amounts::{SlippageBps, TokenAmount},
+        constants::{MAINNET_PUMP_NY, USDC, WRAPPED_SOL},
+    },
     provider::ws::WebSocketClient,
+    strategy::{
+        orders::{
+            spawn_order_manager, OrderEvent, OrderManagerConfig, OrderSide, PriceFeed,
+            TriggerCondition, TriggerManager, TriggerOrder,
+        },
+        portfolio::PriceSource,
+        pump_tracker::{spawn_pump_tracker, PumpTrackerConfig},
+        sniper::{spawn_sniper, AuditLog, SniperConfig},
+    },
 };
 use solana_trader_proto::api;
 use test_case::test_case;
@@ -381,3 +393,101 @@ async fn test_pump_fun_tokens_and_swaps_integration_ws() -> Result<()> {
     ws.close().await?;
     Ok(())
 }
+
+#[test_case(5; "track five pump.fun events")]
+#[tokio::test]
+#[ignore]
+async fn test_pump_tracker_ws(expected_events: usize) -> Result<()> {
+    let ws = WebSocketClient::new(Some(MAINNET_PUMP_NY.to_string())).await?;
+    let (tracker, mut events) = spawn_pump_tracker(ws, PumpTrackerConfig::default()).await?;
+
+    for event_num in 1..=expected_events {
+        let event = events
+            .next()
+            .await
+            .ok_or_else(|| anyhow::anyhow!("Tracker ended without events"))??;
+        println!("Tracker event {}: {:#?}", event_num, event);
+    }
+
+    let tokens = tracker.tokens().await;
+    println!("Tracking {} tokens", tokens.len());
+    assert!(!tokens.is_empty(), "Expected at least one tracked token");
+
+    tracker.stop();
+    Ok(())
+}
+
+#[test_case(2; "dry-run sniper over two new pools")]
+#[tokio::test]
+#[ignore]
+async fn test_pool_sniper_ws(expected_records: usize) -> Result<()> {
+    let source = WebSocketClient::new(None).await?;
+    let client = WebSocketClient::new(None).await?;
+    let config = SniperConfig {
+        min_quote_liquidity: 1.0,
+        ..Default::default()
+    };
+
+    let (sniper, mut records) =
+        spawn_sniper(source, client, config, None, AuditLog::sink()).await?;
+
+    for record_num in 1..=expected_records {
+        let record = records
+            .next()
+            .await
+            .ok_or_else(|| anyhow::anyhow!("Sniper ended without records"))??;
+        println!("Sniper record {}: {:#?}", record_num, record);
+        assert!(record.signatures.is_empty(), "Dry run must not submit");
+    }
+
+    sniper.stop();
+    Ok(())
+}
+
+#[test_case(WRAPPED_SOL, USDC; "far stop-loss on SOL stays open")]
+#[tokio::test]
+#[ignore]
+async fn test_trigger_orders_ws(base_token: &str, quote_token: &str) -> Result<()> {
+    let source = WebSocketClient::new(None).await?;
+    let client = WebSocketClient::new(None).await?;
+    let (orders, mut reports) = spawn_order_manager(
+        source,
+        client,
+        TriggerManager::new(),
+        OrderManagerConfig::default(),
+    )
+    .await?;
+
+    let order = TriggerOrder::new(
+        base_token,
+        quote_token,
+        OrderSide::Sell,
+        TokenAmount::new(1_000_000, 9),
+        TriggerCondition::StopLoss { price: 0.01 },
+        PriceFeed::Token {
+            source: PriceSource::Raydium,
+        },
+        SlippageBps::new(100)?,
+    );
+    let id = orders.place(order).await?;
+
+    let report = reports
+        .next()
+        .await
+        .ok_or_else(|| anyhow::anyhow!("Order manager ended without reports"))??;
+    assert_eq!(report.order_id, id);
+    assert_eq!(report.event, OrderEvent::Placed);
+
+    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+    let order = orders
+        .order(id)
+        .await
+        .ok_or_else(|| anyhow::anyhow!("Order {} missing", id))?;
+    println!("Order after 5s: {:#?}", order);
+    assert!(order.is_open());
+    assert!(order.last_price.is_some(), "Expected a price update");
+
+    assert!(orders.cancel(id).await?);
+    orders.stop();
+    Ok(())
+}